@@ -5,11 +5,7 @@ fn main() -> std::io::Result<()> {
     set_viewport(80, 24);
     clear_screen();
 
-    set_foreground_color(Color::Green);
-    set_attribute(Attribute::Bright);
-    println("Welcome to rpian-terminal!");
-    reset_color();
-    reset_attributes();
+    cprintln!(Color::Green, Attribute::Bright; "Welcome to rpian-terminal!");
 
     draw_box(5, 3, 70, 18, BoxStyle::Double);
     draw_box(10, 5, 60, 14, BoxStyle::SingleRounded);
@@ -19,9 +15,7 @@ fn main() -> std::io::Result<()> {
     let name = read_line();
 
     move_cursor_to(25, 15);
-    set_foreground_color(Color::Blue);
-    set_attribute(Attribute::Underscore);
-    println(&format!("Hello, {}!", name));
+    cprintln!(Color::Blue, Attribute::Underscore; "Hello, {}!", name);
 
     wait_for_seconds(2);
     clear_screen();
@@ -0,0 +1,18 @@
+use rpian_terminal::*;
+use markup::escape_markup;
+
+fn main() {
+    let name = "notes[1].txt";
+    println_markup("[bold green]Welcome to rpian-terminal![/]");
+    println_markup(&format!(
+        "[bold red]Error:[/] file [underline]{}[/] missing",
+        escape_markup(name)
+    ));
+    println_markup("[yellow on blue]outer [reverse]inner[/] outer again[/] plain");
+    println_markup("[sparkly]this tag is unknown[/]");
+
+    let mut screen = ScreenBuffer::new(40, 3);
+    screen.draw_markup(2, 2, "[cyan]Buffered [bold]markup[/] text[/]");
+    screen.render_at(1, 10);
+    println("");
+}
@@ -0,0 +1,39 @@
+use rpian_terminal::*;
+use gradient::*;
+use rbox::ShadeStyle;
+
+fn main() {
+    set_viewport(80, 40);
+    clear_screen();
+
+    let sunset = Gradient::new(&[Rgb::new(255, 94, 58), Rgb::new(255, 205, 2), Rgb::new(94, 44, 165)]);
+    move_cursor_to(2, 1);
+    print_gradient("Gradients in rpian-terminal", &sunset);
+
+    let directions = [
+        GradientDirection::Horizontal,
+        GradientDirection::Vertical,
+        GradientDirection::Diagonal,
+        GradientDirection::Radial,
+    ];
+    for (i, direction) in directions.iter().enumerate() {
+        draw_gradient_rectangle(2 + i as u16 * 18, 3, 16, 8, ShadeStyle::Solid, &sunset, *direction);
+    }
+
+    let spaces = [ColorSpace::Rgb, ColorSpace::Hsl, ColorSpace::Oklab];
+    for (i, space) in spaces.iter().enumerate() {
+        let red_to_blue = Gradient::new(&[Rgb::from(Color::Red), Rgb::from(Color::Blue)]).space(*space);
+        let mut line = Line::new();
+        line.x = 2;
+        line.y = 13 + i as u16 * 2;
+        line.size = 60;
+        line.style.hs = line::HorizontalLineStyle::Heavy;
+        draw_gradient_line(&line, &red_to_blue);
+        move_cursor_to(64, 13 + i as u16 * 2);
+        print(&format!("{:?}", space));
+    }
+
+    move_cursor_to(1, 20);
+    wait_for_seconds(3);
+    clear_screen();
+}
@@ -0,0 +1,42 @@
+use rpian_terminal::*;
+use rbox::BoxStyle;
+use theme::{draw_themed_box, draw_themed_line, print_with_role};
+
+fn draw_screen() {
+    clear_screen();
+    draw_themed_box(2, 2, 50, 12, BoxStyle::SingleRounded);
+    move_cursor_to(4, 3);
+    print_with_role(&format!("Theme: {}", theme::current_theme().name), Role::Primary);
+
+    for (i, role) in Role::ALL.iter().enumerate() {
+        move_cursor_to(4, 5 + i as u16);
+        print_with_role(&format!("{:<10} sample text", role.name()), *role);
+    }
+
+    let mut line = Line::new();
+    line.x = 30;
+    line.y = 8;
+    line.size = 18;
+    draw_themed_line(&line, Role::Accent);
+    move_cursor_to(1, 15);
+}
+
+fn main() {
+    set_viewport(80, 24);
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/ocean.theme");
+    let themes = match Theme::load(path) {
+        Ok(ocean) => vec![Theme::dark(), Theme::light(), ocean],
+        Err(e) => {
+            eprintln!("{}", e);
+            vec![Theme::dark(), Theme::light()]
+        }
+    };
+
+    for theme in themes {
+        set_theme(theme);
+        draw_screen();
+        wait_for_seconds(2);
+    }
+    clear_screen();
+}
@@ -0,0 +1,30 @@
+use rpian_terminal::*;
+use osc::{contrast_ratio, detect_theme, query_background, query_foreground, query_palette, readable_foreground};
+
+fn main() {
+    match query_foreground() {
+        Some(color) => println(&format!("Foreground: {}", color.to_hex())),
+        None => println("Foreground: terminal did not answer"),
+    }
+    match query_background() {
+        Some(background) => {
+            println(&format!("Background: {}", background.to_hex()));
+            let text = readable_foreground(background);
+            println(&format!("Most readable basic color: {:?}", text));
+            if let Some(foreground) = query_foreground() {
+                println(&format!("Contrast ratio: {:.1}:1", contrast_ratio(foreground, background)));
+            }
+        }
+        None => println("Background: terminal did not answer"),
+    }
+    for index in 0..8 {
+        if let Some(color) = query_palette(index) {
+            println(&format!("Palette {}: {}", index, color.to_hex()));
+        }
+    }
+
+    let theme = detect_theme();
+    println(&format!("Using the {} theme", theme.name));
+    set_theme(theme);
+    theme::print_with_role("This text uses the primary role.\n", Role::Primary);
+}
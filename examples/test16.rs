@@ -0,0 +1,25 @@
+use rpian_terminal::*;
+use ansi::{fit, truncate_with_ellipsis, visible_width};
+use rbox::{draw_box, BoxStyle};
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+
+    let messages = [
+        markup::markup_to_ansi("[bold green]OK[/] all files compiled").unwrap(),
+        markup::markup_to_ansi("[bold red]Error:[/] [underline]src/main.rs[/] could not be read because it is missing").unwrap(),
+        markup::markup_to_ansi("[yellow]Warning:[/] unused variable").unwrap(),
+    ];
+
+    let inner_width = 30;
+    draw_box(5, 3, inner_width as u16 + 2, messages.len() as u16 + 2, BoxStyle::Single);
+    for (i, message) in messages.iter().enumerate() {
+        move_cursor_to(6, 4 + i as u16);
+        print(&fit(&truncate_with_ellipsis(message, inner_width, "…"), inner_width));
+    }
+
+    move_cursor_to(5, 9);
+    println(&format!("The second message is {} columns wide.", visible_width(&messages[1])));
+    wait_for_seconds(2);
+}
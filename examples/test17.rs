@@ -0,0 +1,31 @@
+use rpian_terminal::*;
+use rbox::{draw_box, BoxStyle};
+use text::{draw_text, HorizontalAlign, Overflow, TextLayout, VerticalAlign};
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+
+    let story = "Rust is a multi-paradigm, general-purpose programming language that emphasizes \
+                 performance, type safety and concurrency. It enforces memory safety without a \
+                 garbage collector.\nThis text is wrapped, aligned and clipped by the text module.";
+
+    let aligns = [HorizontalAlign::Left, HorizontalAlign::Center, HorizontalAlign::Right, HorizontalAlign::Justify];
+    for (i, align) in aligns.iter().enumerate() {
+        let x = 2 + i as u16 * 19;
+        draw_box(x, 2, 18, 12, BoxStyle::Single);
+        let options = TextLayout {
+            align: *align,
+            vertical_align: VerticalAlign::Middle,
+            overflow: Overflow::Ellipsis,
+            hyphenate: true,
+            style: Style::new().fg(Color::Cyan),
+        };
+        draw_text(story, x + 1, 3, 16, 10, &options);
+        move_cursor_to(x + 1, 14);
+        print(&format!("{:?}", align));
+    }
+
+    move_cursor_to(1, 16);
+    wait_for_seconds(3);
+}
@@ -0,0 +1,34 @@
+use rpian_terminal::*;
+use figlet::{print_banner, FigFont};
+use rbox::{draw_box, BoxStyle};
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+    hide_cursor();
+
+    let font = FigFont::block();
+    let title = "RPIAN";
+    let width = font.render(title)[0].chars().count() as u16;
+    set_foreground_color(Color::Green);
+    print_banner(title, (80 - width) / 2, 2, &font);
+    reset_color();
+
+    let small = FigFont::mini();
+    set_foreground_color(Color::Cyan);
+    print_banner("terminal", 22, 8, &small);
+    reset_color();
+
+    draw_box(5, 12, 70, 8, BoxStyle::Double);
+    move_cursor_to(15, 15);
+    print("Enter your name: ");
+    show_cursor();
+    let name = read_line();
+
+    clear_screen();
+    set_foreground_color(Color::Blue);
+    print_banner(&format!("Hi {}", name), 2, 2, &FigFont::standard());
+    reset_color();
+    move_cursor_to(1, 9);
+    wait_for_seconds(2);
+}
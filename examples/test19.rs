@@ -0,0 +1,34 @@
+use rpian_terminal::*;
+use digits::{BigDigits, DigitStyle};
+use style::Style;
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+    hide_cursor();
+
+    let clock = BigDigits {
+        size: 2,
+        color: Style::new().fg(Color::Green),
+        ..BigDigits::default()
+    };
+    let countdown = BigDigits {
+        style: DigitStyle::Segment,
+        color: Style::new().fg(Color::Yellow),
+        ..BigDigits::default()
+    };
+
+    for remaining in (0..=10u32).rev() {
+        let elapsed = 10 - remaining;
+        let time = format!("12:{:02}:{:02}", 34 + elapsed / 60, elapsed % 60);
+        let x = (80 - clock.width(&time)) / 2;
+        clock.draw(&time, x, 3);
+
+        let timer = format!("-00:{:02}.0", remaining);
+        countdown.draw(&timer, (80 - countdown.width(&timer)) / 2, 12);
+        wait_for_seconds(1);
+    }
+
+    move_cursor_to(1, 20);
+    show_cursor();
+}
@@ -0,0 +1,44 @@
+use rpian_terminal::*;
+use rbox::draw_box;
+use region::{region_size, with_region};
+
+// Draws a framed panel that fills the current region.
+fn panel(title: &str, color: Color) {
+    let size = region_size();
+    set_foreground_color(color);
+    draw_box(1, 1, size.width, size.height, BoxStyle::SingleRounded);
+    reset_color();
+    move_cursor_to(3, 1);
+    print(&format!(" {} ", title));
+}
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+    hide_cursor();
+
+    let screen = Rect::new(1, 1, 79, 23);
+    let (left, right) = screen.split_horizontal(30);
+
+    with_region(left, || {
+        panel("Files", Color::Cyan);
+        for (i, name) in ["Cargo.toml", "README.md", "src/lib.rs"].iter().enumerate() {
+            move_cursor_to(3, 3 + i as u16);
+            print(name);
+        }
+    });
+
+    with_region(right, || {
+        panel("Preview", Color::Green);
+        // Nested region: the inside of the panel, so long lines are cut at its border.
+        with_region(Rect::new(2, 2, right.width - 2, right.height - 2), || {
+            for row in 1..=30 {
+                move_cursor_to(1, row);
+                print(&format!("{:>3} | a long line of text that does not fit inside the preview panel", row));
+            }
+        });
+    });
+
+    move_cursor_to(1, 24);
+    show_cursor();
+}
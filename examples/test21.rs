@@ -0,0 +1,53 @@
+use rpian_terminal::*;
+use layout::{Constraint, Grid, Layout};
+use rbox::draw_box_rect;
+use text::{draw_text_rect, HorizontalAlign, TextLayout};
+
+fn draw(frame: u32) {
+    clear_screen();
+    let (width, height) = get_viewport();
+    // Leave the last row free so drawing never scrolls the terminal.
+    let screen = Rect::new(1, 1, width - 1, height - 1);
+
+    let rows = Layout::vertical(vec![
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(3),
+    ])
+    .split(screen);
+    let body = Layout::horizontal(vec![Constraint::Percentage(25), Constraint::Min(20)])
+        .gap(1)
+        .split(rows[1]);
+
+    let centered = TextLayout { align: HorizontalAlign::Center, ..TextLayout::default() };
+    draw_box_rect(rows[0], BoxStyle::Double);
+    draw_text_rect("Dashboard - resize the terminal", rows[0].inset(1, 1), &centered);
+
+    draw_box_rect(body[0], BoxStyle::Single);
+    draw_text_rect("Menu\n\nOverview\nStatistics\nSettings", body[0].inset(2, 1), &TextLayout::default());
+
+    let cards = Grid::uniform(2, 2).gaps(1, 0).split(body[1]);
+    for (i, card) in cards.iter().flatten().enumerate() {
+        draw_box_rect(*card, BoxStyle::SingleRounded);
+        let label = format!("Card {} ({}x{})", i + 1, card.width, card.height);
+        draw_text_rect(&label, card.inset(1, 1), &centered);
+    }
+
+    draw_box_rect(rows[2], BoxStyle::Single);
+    draw_text_rect(&format!("Frame {}", frame), rows[2].inset(2, 1), &TextLayout::default());
+}
+
+fn main() {
+    hide_cursor();
+    terminal::sync_viewport();
+    draw(0);
+    for frame in 1..=20 {
+        wait_for_millis(500);
+        // Only redraw when the terminal size changes; the layout reflows on its own.
+        if terminal::sync_viewport() || frame == 20 {
+            draw(frame);
+        }
+    }
+    move_cursor_to(1, get_viewport().1);
+    show_cursor();
+}
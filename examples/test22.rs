@@ -0,0 +1,62 @@
+use rpian_terminal::*;
+use event::{Event, KeyCode};
+use layout::Constraint;
+use widget::{App, EventResult, Label, Panel, Stack, Widget};
+
+// A custom widget: a counter changed with the arrow keys while it has focus.
+struct Counter {
+    value: i32,
+    focused: bool,
+}
+
+impl Widget for Counter {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        let style = if self.focused {
+            Style::new().fg(Color::Yellow).attribute(Attribute::Bright)
+        } else {
+            Style::default()
+        };
+        buffer.put_str(area.x, area.y, &format!("< {} >", self.value), style);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        Size::new(available.width, 1)
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Key(key) if key.code == KeyCode::Left => self.value -= 1,
+            Event::Key(key) if key.code == KeyCode::Right => self.value += 1,
+            Event::Key(key) if key.code == KeyCode::Char('q') => return EventResult::Quit,
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
+
+fn main() {
+    let counters = Stack::horizontal()
+        .gap(1)
+        .child(Constraint::Fill(1), Panel::new("Red", Counter { value: 0, focused: false }))
+        .child(Constraint::Fill(1), Panel::new("Green", Counter { value: 10, focused: false }))
+        .child(Constraint::Fill(1), Panel::new("Blue", Counter { value: 100, focused: false }));
+
+    let root = Stack::vertical()
+        .margin(1)
+        .child(
+            Constraint::Length(3),
+            Panel::new("Widgets", Label::new("Tab / Shift-Tab: focus   Left / Right: change   q: quit")),
+        )
+        .child(Constraint::Length(3), counters)
+        .child(Constraint::Fill(1), Panel::new("Notes", Label::new("Resize the terminal to see the layout reflow.")));
+
+    App::new(root).run();
+}
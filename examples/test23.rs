@@ -0,0 +1,40 @@
+use rpian_terminal::*;
+use input::TextInput;
+use rbox::draw_box;
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+
+    draw_box(10, 5, 42, 3, BoxStyle::SingleRounded);
+    move_cursor_to(12, 5);
+    print(" Name ");
+    let mut name = TextInput::new()
+        .placeholder("Type your name")
+        .max_length(60)
+        .validator(|value| {
+            if value.trim().is_empty() {
+                Err(String::from("Name must not be empty"))
+            } else {
+                Ok(())
+            }
+        });
+
+    draw_box(10, 9, 42, 3, BoxStyle::SingleRounded);
+    move_cursor_to(12, 9);
+    print(" Age ");
+    let mut age = TextInput::new()
+        .placeholder("Digits only")
+        .max_length(3)
+        .filter(|ch| ch.is_ascii_digit());
+
+    let name = name.read_at(11, 6, 40);
+    let age = age.read_at(11, 10, 40);
+
+    move_cursor_to(10, 13);
+    match (name, age) {
+        (Some(name), Some(age)) => print(&format!("Hello {}, you are {}.", name, age)),
+        _ => print("Cancelled."),
+    }
+    move_cursor_to(1, 15);
+}
@@ -0,0 +1,46 @@
+use rpian_terminal::*;
+use readline::{History, LineEditor, ReadlineError};
+
+// A tiny calculator REPL with history saved next to the working directory.
+fn main() {
+    let commands = ["add", "sub", "mul", "div", "help", "history", "quit"];
+    let history = History::open(".calc_history", 200).unwrap_or_default();
+    let mut editor = LineEditor::new()
+        .history(history)
+        .prompt_style(Style::new().fg(Color::Green).attribute(Attribute::Bright))
+        .completer(move |line: &str, cursor: usize| {
+            // Only the first word is a command.
+            let start = readline::word_start(line, cursor);
+            if start > 0 {
+                return (start, Vec::new());
+            }
+            let prefix = &line[..cursor];
+            let matches = commands.iter().filter(|c| c.starts_with(prefix)).map(|c| c.to_string()).collect();
+            (0, matches)
+        });
+
+    println("Commands: add, sub, mul, div <a> <b>; history; quit. Try Tab, Up and Ctrl-R.");
+    loop {
+        let line = match editor.read_line("calc> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let numbers: Vec<f64> = words.iter().skip(1).filter_map(|w| w.parse().ok()).collect();
+        match (words.first().copied(), numbers.as_slice()) {
+            (Some("quit"), _) => break,
+            (Some("history"), _) => {
+                for (i, entry) in editor.history.entries().iter().enumerate() {
+                    println(&format!("{:4}  {}", i + 1, entry));
+                }
+            }
+            (Some("add"), [a, b]) => println(&format!("{}", a + b)),
+            (Some("sub"), [a, b]) => println(&format!("{}", a - b)),
+            (Some("mul"), [a, b]) => println(&format!("{}", a * b)),
+            (Some("div"), [a, b]) => println(&format!("{}", a / b)),
+            (None, _) => {}
+            _ => println("Usage: add|sub|mul|div <a> <b>, history, quit"),
+        }
+    }
+}
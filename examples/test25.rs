@@ -0,0 +1,27 @@
+use rpian_terminal::*;
+use prompt::{confirm, multi_select, number, password_with, select, PasswordMode};
+
+fn main() {
+    let languages = ["Rust", "Python", "C", "C++", "Go", "JavaScript", "TypeScript", "Haskell", "Zig"];
+    let Some(favourite) = select("Favourite language?", &languages) else {
+        return;
+    };
+    let Some(others) = multi_select("Which others do you use?", &languages, &[favourite]) else {
+        return;
+    };
+    let Some(years) = number("Years of experience?", 0..=60, Some(1)) else {
+        return;
+    };
+    let Some(secret) = password_with("Choose a password:", PasswordMode::Mask('•')) else {
+        return;
+    };
+    let subscribe = confirm("Subscribe to the newsletter?", false).unwrap_or(false);
+
+    println("");
+    println(&format!("Favourite: {}", languages[favourite]));
+    let names: Vec<&str> = others.iter().map(|&i| languages[i]).collect();
+    println(&format!("Also uses: {}", names.join(", ")));
+    println(&format!("Experience: {} years", years));
+    println(&format!("Password length: {}", secret.chars().count()));
+    println(&format!("Newsletter: {}", if subscribe { "yes" } else { "no" }));
+}
@@ -0,0 +1,46 @@
+use rpian_terminal::*;
+use form::{Field, FieldValue, Form};
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+    cprintln!(Color::Green, Attribute::Bright; "Create an account");
+
+    let form = Form::new("Registration")
+        .field(Field::text("username", "Username").required().max_length(16).validate(|value| match value {
+            FieldValue::Text(name) if name.contains(' ') => Err(String::from("No spaces allowed")),
+            _ => Ok(()),
+        }))
+        .field(Field::password("password", "Password").required())
+        .field(Field::password("confirm", "Repeat password").required())
+        .field(Field::number("age", "Age", 13.0, 120.0).placeholder("13-120"))
+        .field(Field::select("country", "Country", &["Germany", "Japan", "Kenya", "Peru", "Other"]))
+        .field(Field::date("birthday", "Birthday"))
+        .field(Field::checkbox("newsletter", "Newsletter", true))
+        .validate(|values| {
+            if values.text("password") == values.text("confirm") {
+                Ok(())
+            } else {
+                Err(String::from("The passwords do not match"))
+            }
+        });
+
+    match form.run(10, 3, 56) {
+        Some(values) => {
+            move_cursor_to(1, 22);
+            println(&format!(
+                "Welcome, {} from {}! Newsletter: {}",
+                values.text("username").unwrap_or_default(),
+                values.text("country").unwrap_or_default(),
+                values.bool("newsletter").unwrap_or(false),
+            ));
+            if let Some(birthday) = values.date("birthday") {
+                println(&format!("Birthday: {}", birthday));
+            }
+        }
+        None => {
+            move_cursor_to(1, 22);
+            println("Registration cancelled.");
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use rpian_terminal::*;
+use input::TextInput;
+use layout::Constraint;
+use widget::{App, Label, Stack};
+use window::{Window, WindowManager};
+
+fn main() {
+    let help = Label::new(
+        "Drag a title bar to move a window and its bottom-right corner to resize it.\n\n\
+         Keys: Alt-Tab next window, Alt-M move, Alt-R resize, Alt-N minimize, Alt-X close, Ctrl-C quit.",
+    );
+    let notes = Stack::vertical()
+        .gap(1)
+        .child(Constraint::Length(1), TextInput::new().placeholder("Type a note"))
+        .child(Constraint::Length(1), TextInput::new().placeholder("And another (Tab switches)"));
+    let lorem = Label::new(
+        "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor \
+         incididunt ut labore et dolore magna aliqua.",
+    );
+
+    let mut desktop = WindowManager::new();
+    desktop.add(Window::new("Help", Rect::new(2, 2, 44, 9), help).box_style(BoxStyle::SingleRounded));
+    desktop.add(Window::new("Notes", Rect::new(20, 8, 40, 6), notes).box_style(BoxStyle::Double));
+    desktop.add(Window::new("Lorem", Rect::new(50, 3, 26, 10), lorem).box_style(BoxStyle::Dashed));
+
+    let mut app = App::new(desktop);
+    app.mouse_capture = true;
+    app.run();
+}
@@ -0,0 +1,33 @@
+use rpian_terminal::*;
+use dialog::{confirm_dialog, input_dialog, message_box, Dialog};
+use rbox::draw_box;
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+
+    // Something for the dialogs to cover.
+    for row in 1..=22 {
+        move_cursor_to(1, row);
+        set_foreground_color(if row % 2 == 0 { Color::Cyan } else { Color::Green });
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(2);
+        print(&format!("{:02} {}", row, &text[..70]));
+    }
+    reset_color();
+    draw_box(5, 4, 30, 8, BoxStyle::SingleRounded);
+
+    let name = input_dialog("Name", "What should we call you?", "World").unwrap_or_default();
+    message_box("Hello", &format!("Hello, {}! The text behind this dialog comes back when it closes.", name));
+
+    let mut dialog = Dialog::new("Save", "Save changes to the document before closing?")
+        .buttons(&["Save", "Don't save", "Cancel"])
+        .box_style(BoxStyle::SingleRounded)
+        .shadow(Some(ShadeStyle::Dark));
+    let choice = dialog.run();
+
+    let again = confirm_dialog("Confirm", "Show the result?", true);
+    move_cursor_to(1, 24);
+    if again == Some(true) {
+        print(&format!("Name: {}  Save dialog: {:?}", name, choice));
+    }
+}
@@ -0,0 +1,37 @@
+use rpian_terminal::*;
+use layout::Constraint;
+use list::{FnSource, List, ScrollbarStyle, SelectionMode};
+use widget::{App, Label, Panel, Stack};
+
+fn main() {
+    // A million rows, generated only when they scroll into view.
+    let numbers = List::new(FnSource::new(1_000_000, |i| format!("Row {:>7}  square {}", i + 1, (i as u64 + 1).pow(2))))
+        .scrollbar(ScrollbarStyle::Block);
+
+    let fruits = vec![
+        "Apple", "Apricot", "Banana", "Blackberry", "Blueberry", "Cherry", "Coconut", "Cranberry", "Date",
+        "Dragonfruit", "Fig", "Grape", "Grapefruit", "Guava", "Kiwi", "Lemon", "Lime", "Lychee", "Mango",
+        "Melon", "Nectarine", "Orange", "Papaya", "Peach", "Pear", "Pineapple", "Plum", "Pomegranate",
+        "Raspberry", "Strawberry", "Tangerine", "Watermelon",
+    ];
+    let basket = List::new(fruits).selection_mode(SelectionMode::Multiple);
+
+    let root = Stack::vertical()
+        .child(
+            Constraint::Length(3),
+            Panel::new(
+                "Lists",
+                Label::new("Arrows/PgUp/PgDn: move  Space: toggle  type: search  Ctrl-N: next match  Ctrl-C: quit"),
+            ),
+        )
+        .child(
+            Constraint::Fill(1),
+            Stack::horizontal()
+                .child(Constraint::Fill(3), Panel::new("1,000,000 rows", numbers))
+                .child(Constraint::Fill(2), Panel::new("Fruit basket", basket)),
+        );
+
+    let mut app = App::new(root);
+    app.mouse_capture = true;
+    app.run();
+}
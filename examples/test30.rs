@@ -0,0 +1,44 @@
+use rpian_terminal::*;
+use layout::Constraint;
+use table::{Column, Table};
+use text::HorizontalAlign;
+
+fn planets() -> Table {
+    let planets = [
+        ["Mercury", "0.39", "4879", "0"],
+        ["Venus", "0.72", "12104", "0"],
+        ["Earth", "1.00", "12756", "1"],
+        ["Mars", "1.52", "6792", "2"],
+        ["Jupiter", "5.20", "142984", "95"],
+        ["Saturn", "9.54", "120536", "146"],
+        ["Uranus", "19.19", "51118", "28"],
+        ["Neptune", "30.07", "49528", "16"],
+        ["Pluto (dwarf planet, reclassified in 2006)", "39.48", "2376", "5"],
+    ];
+    let columns = vec![
+        Column::new("Name").width(Constraint::Max(24)),
+        Column::new("Distance").align(HorizontalAlign::Right).format(|value| format!("{} AU", value)),
+        Column::new("Diameter").align(HorizontalAlign::Right).format(|value| format!("{} km", value)),
+        Column::new("Moons").align(HorizontalAlign::Center),
+    ];
+    let mut table = Table::new(columns);
+    for planet in planets {
+        table.add_row(planet.iter().map(|cell| cell.to_string()).collect());
+    }
+    table
+}
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+    cprintln!(Color::Green, Attribute::Bright; "Report");
+    println(&planets().render_to_string());
+
+    let mut table = planets().box_style(BoxStyle::Double).zebra(Some(Style::new().bg(Color::Blue)));
+    move_cursor_to(1, 15);
+    println("Up/Down: move  1-4: sort by column  0: unsort  Enter: choose  Esc: quit");
+    if let Some(row) = table.run(Rect::new(1, 16, 60, 8)) {
+        move_cursor_to(1, 24);
+        print(&format!("You chose {}", table.rows()[row][0]));
+    }
+}
@@ -0,0 +1,36 @@
+use rpian_terminal::*;
+use csv::parse_csv;
+use table::Table;
+
+const RESULTS: &str = "\
+Student,Exercise,Score,Time (s),Comment\r
+Ada,Loops,92.5,341,\"Neat, well commented\"\r
+Ben,Loops,78,512,\r
+Chloé,Recursion,100,298,\"Said \"\"easy\"\"\"\r
+Dev,Recursion,64.25,880,\"Ran out of time;
+finished at home\"\r
+";
+
+fn main() {
+    cprintln!(Color::Green, Attribute::Bright; "Exercise results");
+    match Table::from_csv(RESULTS, ',') {
+        Ok(table) => println(&table.box_style(BoxStyle::SingleRounded).render_to_string()),
+        Err(e) => println(&e.to_string()),
+    }
+
+    cprintln!(Color::Green, Attribute::Bright; "Semicolon-separated, sorted by score");
+    match Table::from_csv("Name;Score\nEve;71\nFinn;88.5\nGus;59\n", ';') {
+        Ok(mut table) => {
+            table.sort_by(1, table::SortOrder::Descending);
+            println(&table.render_to_string());
+        }
+        Err(e) => println(&e.to_string()),
+    }
+
+    cprintln!(Color::Green, Attribute::Bright; "Broken input");
+    for broken in ["a,b\n\"unclosed,1\n", "a,\"b\"c\n"] {
+        if let Err(e) = parse_csv(broken) {
+            println(&e.to_string());
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use rpian_terminal::*;
+use std::fs;
+use std::path::PathBuf;
+use tree::{tree_lines, TreeItem, TreeNode, TreeStyle, TreeView};
+
+// A file or directory. Directories are only read when they are expanded.
+struct Entry(PathBuf);
+
+impl TreeItem for Entry {
+    fn label(&self) -> String {
+        let name = self.0.file_name().map(|name| name.to_string_lossy().into_owned());
+        let name = name.unwrap_or_else(|| self.0.display().to_string());
+        if self.0.is_dir() {
+            format!("{}/", name)
+        } else {
+            name
+        }
+    }
+
+    fn children(&self) -> Vec<Entry> {
+        let Ok(entries) = fs::read_dir(&self.0) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect();
+        paths.sort_by_key(|path| (!path.is_dir(), path.file_name().map(|name| name.to_ascii_lowercase())));
+        paths.into_iter().map(Entry).collect()
+    }
+
+    fn has_children(&self) -> bool {
+        self.0.is_dir()
+    }
+}
+
+fn main() {
+    set_viewport(80, 24);
+    clear_screen();
+    let tree = TreeNode::new("project")
+        .child(TreeNode::new("src").child(TreeNode::new("lib.rs")).child(TreeNode::new("main.rs")))
+        .child(TreeNode::new("README.md"));
+    let styles = [TreeStyle::Light, TreeStyle::Heavy, TreeStyle::Double, TreeStyle::Rounded];
+    for (column, style) in styles.into_iter().enumerate() {
+        for (row, line) in tree_lines(&tree, style).iter().enumerate() {
+            move_cursor_to(1 + column as u16 * 20, 1 + row as u16);
+            print(line);
+        }
+    }
+
+    move_cursor_to(1, 7);
+    println("Up/Down: move  Right/Left: expand/collapse  Space: toggle  Enter: choose  Esc: quit");
+    let mut view = TreeView::new(vec![Entry(PathBuf::from("."))]).style(TreeStyle::Rounded);
+    view.expand(&[0]);
+    let chosen = view.run(Rect::new(1, 8, 80, 16)).map(|entry| entry.0.display().to_string());
+    move_cursor_to(1, 24);
+    if let Some(path) = chosen {
+        print(&format!("You chose {}", path));
+    }
+}
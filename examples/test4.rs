@@ -1,25 +1,4 @@
-use std::io::{self, Write};
-
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().to_string()
-}
-
-fn ask_yes_no(question: &str) -> bool {
-    loop {
-        print!("{} (y/n): ", question);
-        io::stdout().flush().unwrap();
-        let answer = read_line().to_lowercase();
-        match answer.as_str() {
-            "y" | "yes" => return true,
-            "n" | "no" => return false,
-            _ => println!("Please answer with 'y' or 'n'."),
-        }
-    }
-}
+use rpian_terminal::prompt::confirm;
 
 fn test_unicode_support() -> bool {
     println!("Unicode Character Test for rpian-terminal");
@@ -52,7 +31,7 @@ fn test_unicode_support() -> bool {
     }
 
     println!();
-    ask_yes_no("Can you see all the above characters correctly?")
+    confirm("Can you see all the above characters correctly?", true).unwrap_or(false)
 }
 
 fn main() {
@@ -33,6 +33,12 @@ impl BraillePattern {
     }
 }
 
+impl Default for BraillePattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn char_to_braille(c: char) -> BraillePattern {
     let mut pattern = BraillePattern::new();
     match c.to_lowercase().next().unwrap() {
@@ -81,5 +87,5 @@ fn main() {
     println("Hello World");
     move_cursor_to(10, 11);
     print_braille("Hello World");
-    move_cursor_to(0, 22); // Move cursor to bottom for clean exit
+    move_cursor_to(1, 22); // Move cursor to bottom for clean exit
 }
\ No newline at end of file
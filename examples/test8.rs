@@ -31,7 +31,7 @@ fn main() {
     move_cursor_to(5, 27);
     println("Diagonal Lines: Forward, Backward, Forward Slash, Backward Slash");
 
-    move_cursor_to(0, 40);
+    move_cursor_to(1, 40);
     println("Press any key to exit...");
     read_key();
     clear_screen();
@@ -0,0 +1,138 @@
+#!/usr/bin/env python3
+"""Generates the FIGlet fonts bundled in src/figlet/fonts/ from a 5-row bitmap font.
+
+Run from the repository root:
+
+    python3 scripts/generate_figlet_fonts.py
+
+Two fonts are written:
+
+* block.flf - every pixel is two full blocks wide, 5 lines tall
+* mini.flf  - pixels are packed into half blocks, 3 lines tall
+
+Lowercase letters reuse the uppercase shapes. The bitmap below is original to this crate.
+"""
+
+GLYPHS = {
+    " ": ["...", "...", "...", "...", "..."],
+    "!": ["#", "#", "#", ".", "#"],
+    '"': ["#.#", "#.#", "...", "...", "..."],
+    "#": [".#.#.", "#####", ".#.#.", "#####", ".#.#."],
+    "$": [".###", "##..", ".##.", "..##", "###."],
+    "%": ["#..#", "..#.", ".#..", "#...", "#..#"],
+    "&": [".#..", "#.#.", ".#..", "#.#.", ".#.#"],
+    "'": ["#", "#", ".", ".", "."],
+    "(": [".#", "#.", "#.", "#.", ".#"],
+    ")": ["#.", ".#", ".#", ".#", "#."],
+    "*": ["...", "#.#", ".#.", "#.#", "..."],
+    "+": ["...", ".#.", "###", ".#.", "..."],
+    ",": ["..", "..", "..", ".#", "#."],
+    "-": ["...", "...", "###", "...", "..."],
+    ".": [".", ".", ".", ".", "#"],
+    "/": ["...#", "..#.", ".#..", "#...", "...."],
+    "0": [".##.", "#..#", "#.##", "##.#", ".##."],
+    "1": [".#.", "##.", ".#.", ".#.", "###"],
+    "2": ["###.", "...#", ".##.", "#...", "####"],
+    "3": ["###.", "...#", ".##.", "...#", "###."],
+    "4": ["#..#", "#..#", "####", "...#", "...#"],
+    "5": ["####", "#...", "###.", "...#", "###."],
+    "6": [".##.", "#...", "###.", "#..#", ".##."],
+    "7": ["####", "...#", "..#.", ".#..", ".#.."],
+    "8": [".##.", "#..#", ".##.", "#..#", ".##."],
+    "9": [".##.", "#..#", ".###", "...#", ".##."],
+    ":": [".", "#", ".", "#", "."],
+    ";": ["..", ".#", "..", ".#", "#."],
+    "<": ["..#", ".#.", "#..", ".#.", "..#"],
+    "=": ["...", "###", "...", "###", "..."],
+    ">": ["#..", ".#.", "..#", ".#.", "#.."],
+    "?": ["###.", "...#", ".##.", "....", ".#.."],
+    "@": [".###.", "#.###", "#.#.#", "#.###", ".#..."],
+    "A": [".##.", "#..#", "####", "#..#", "#..#"],
+    "B": ["###.", "#..#", "###.", "#..#", "###."],
+    "C": [".###", "#...", "#...", "#...", ".###"],
+    "D": ["###.", "#..#", "#..#", "#..#", "###."],
+    "E": ["####", "#...", "###.", "#...", "####"],
+    "F": ["####", "#...", "###.", "#...", "#..."],
+    "G": [".###", "#...", "#.##", "#..#", ".###"],
+    "H": ["#..#", "#..#", "####", "#..#", "#..#"],
+    "I": ["###", ".#.", ".#.", ".#.", "###"],
+    "J": ["..##", "...#", "...#", "#..#", ".##."],
+    "K": ["#..#", "#.#.", "##..", "#.#.", "#..#"],
+    "L": ["#...", "#...", "#...", "#...", "####"],
+    "M": ["#...#", "##.##", "#.#.#", "#...#", "#...#"],
+    "N": ["#..#", "##.#", "#.##", "#..#", "#..#"],
+    "O": [".##.", "#..#", "#..#", "#..#", ".##."],
+    "P": ["###.", "#..#", "###.", "#...", "#..."],
+    "Q": [".##.", "#..#", "#..#", "#.#.", ".#.#"],
+    "R": ["###.", "#..#", "###.", "#.#.", "#..#"],
+    "S": [".###", "#...", ".##.", "...#", "###."],
+    "T": ["#####", "..#..", "..#..", "..#..", "..#.."],
+    "U": ["#..#", "#..#", "#..#", "#..#", ".##."],
+    "V": ["#...#", "#...#", "#...#", ".#.#.", "..#.."],
+    "W": ["#...#", "#...#", "#.#.#", "##.##", "#...#"],
+    "X": ["#...#", ".#.#.", "..#..", ".#.#.", "#...#"],
+    "Y": ["#...#", ".#.#.", "..#..", "..#..", "..#.."],
+    "Z": ["####", "...#", ".##.", "#...", "####"],
+    "[": ["##", "#.", "#.", "#.", "##"],
+    "\\": ["#...", ".#..", "..#.", "...#", "...."],
+    "]": ["##", ".#", ".#", ".#", "##"],
+    "^": [".#.", "#.#", "...", "...", "..."],
+    "_": ["....", "....", "....", "....", "####"],
+    "`": ["#.", ".#", "..", "..", ".."],
+    "{": [".##", ".#.", "##.", ".#.", ".##"],
+    "|": ["#", "#", "#", "#", "#"],
+    "}": ["##.", ".#.", ".##", ".#.", "##."],
+    "~": ["....", ".#.#", "#.#.", "....", "...."],
+}
+
+# FIGlet fonts must also define these seven characters, in this order.
+GERMAN = [("Ä", "A"), ("Ö", "O"), ("Ü", "U"), ("ä", "A"), ("ö", "O"), ("ü", "U"), ("ß", "B")]
+
+
+def glyph(ch):
+    if "a" <= ch <= "z":
+        ch = ch.upper()
+    rows = GLYPHS[ch]
+    # A blank column after each glyph keeps letters apart.
+    return [row + "." for row in rows]
+
+
+def block_rows(rows):
+    return ["".join("██" if p == "#" else "  " for p in row) for row in rows]
+
+
+def mini_rows(rows):
+    padded = rows + ["." * len(rows[0])]
+    result = []
+    for top, bottom in zip(padded[0::2], padded[1::2]):
+        line = ""
+        for t, b in zip(top, bottom):
+            line += {("#", "#"): "█", ("#", "."): "▀", (".", "#"): "▄"}.get((t, b), " ")
+        result.append(line)
+    return result
+
+
+def write_font(path, name, render, height):
+    chars = [chr(c) for c in range(32, 127)] + [base for _, base in GERMAN]
+    glyphs = [render(glyph(ch)) for ch in chars]
+    max_length = max(len(row) for rows in glyphs for row in rows) + 2
+    lines = [
+        "flf2a$ {} {} {} -1 3 0 0".format(height, height, max_length),
+        "{} - a FIGlet font bundled with rpian-terminal.".format(name),
+        "Generated by scripts/generate_figlet_fonts.py. Lowercase letters share the",
+        "uppercase shapes.",
+    ]
+    for rows in glyphs:
+        for i, row in enumerate(rows):
+            lines.append(row + ("@@" if i == len(rows) - 1 else "@"))
+    with open(path, "w", encoding="utf-8") as f:
+        f.write("\n".join(lines) + "\n")
+
+
+def main():
+    write_font("src/figlet/fonts/block.flf", "block", block_rows, 5)
+    write_font("src/figlet/fonts/mini.flf", "mini", mini_rows, 3)
+
+
+if __name__ == "__main__":
+    main()
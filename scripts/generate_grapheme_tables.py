@@ -0,0 +1,134 @@
+#!/usr/bin/env python3
+"""Generates src/grapheme/tables.rs from the Unicode database bundled with Python.
+
+Run from the repository root:
+
+    python3 scripts/generate_grapheme_tables.py
+
+Python's `unicodedata` module does not expose the Grapheme_Cluster_Break or
+Extended_Pictographic properties, so they are derived here from the general category
+plus the fixed code point ranges listed in UAX #29 and UTS #51. The result matches the
+official property for all commonly used scripts and emoji; rare Prepend and spacing
+mark exceptions are approximated.
+
+Hangul syllables (U+AC00..U+D7A3) are not in the table; their LV/LVT class is computed
+in code.
+"""
+
+import sys
+import unicodedata
+
+OUTPUT = "src/grapheme/tables.rs"
+
+PREPEND = [
+    (0x0600, 0x0605), (0x06DD, 0x06DD), (0x070F, 0x070F), (0x0890, 0x0891),
+    (0x08E2, 0x08E2), (0x0D4E, 0x0D4E), (0x110BD, 0x110BD), (0x110CD, 0x110CD),
+    (0x111C2, 0x111C3), (0x1193F, 0x1193F), (0x11941, 0x11941), (0x11A3A, 0x11A3A),
+    (0x11A84, 0x11A89), (0x11D46, 0x11D46),
+]
+
+EXTEND_EXTRA = [
+    (0x200C, 0x200C), (0xFF9E, 0xFF9F), (0x1F3FB, 0x1F3FF), (0xE0020, 0xE007F),
+]
+
+SPACING_MARK_EXTRA = [(0x0E33, 0x0E33), (0x0EB3, 0x0EB3)]
+
+HANGUL_L = [(0x1100, 0x115F), (0xA960, 0xA97C)]
+HANGUL_V = [(0x1160, 0x11A7), (0xD7B0, 0xD7C6)]
+HANGUL_T = [(0x11A8, 0x11FF), (0xD7CB, 0xD7FB)]
+
+EXTENDED_PICTOGRAPHIC = [
+    (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049),
+    (0x2122, 0x2122), (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA),
+    (0x231A, 0x231B), (0x2328, 0x2328), (0x2388, 0x2388), (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2), (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x27BF),
+    (0x2934, 0x2935), (0x2B05, 0x2B07), (0x2B1B, 0x2B1C), (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D), (0x3297, 0x3297),
+    (0x3299, 0x3299), (0x1F000, 0x1F0FF), (0x1F10D, 0x1F10F), (0x1F12F, 0x1F12F),
+    (0x1F16C, 0x1F171), (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A),
+    (0x1F1AD, 0x1F1E5), (0x1F201, 0x1F20F), (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F23A), (0x1F23C, 0x1F23F), (0x1F249, 0x1F3FA), (0x1F400, 0x1F53D),
+    (0x1F546, 0x1F64F), (0x1F680, 0x1F6FF), (0x1F774, 0x1F77F), (0x1F7D5, 0x1F7FF),
+    (0x1F80C, 0x1F80F), (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F), (0x1F888, 0x1F88F),
+    (0x1F8AE, 0x1F8FF), (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1FAFF),
+    (0x1FC00, 0x1FFFD),
+]
+
+
+def within(cp, table):
+    return any(start <= cp <= end for start, end in table)
+
+
+def classify(cp):
+    if cp == 0x0D:
+        return "Cr"
+    if cp == 0x0A:
+        return "Lf"
+    if cp == 0x200D:
+        return "Zwj"
+    if 0x1F1E6 <= cp <= 0x1F1FF:
+        return "RegionalIndicator"
+    if within(cp, PREPEND):
+        return "Prepend"
+    if within(cp, EXTEND_EXTRA):
+        return "Extend"
+    if within(cp, HANGUL_L):
+        return "L"
+    if within(cp, HANGUL_V):
+        return "V"
+    if within(cp, HANGUL_T):
+        return "T"
+    if within(cp, SPACING_MARK_EXTRA):
+        return "SpacingMark"
+    if within(cp, EXTENDED_PICTOGRAPHIC):
+        return "ExtendedPictographic"
+    category = unicodedata.category(chr(cp))
+    if category in ("Cc", "Zl", "Zp", "Cf", "Cs"):
+        return "Control"
+    if category in ("Mn", "Me"):
+        return "Extend"
+    if category == "Mc":
+        return "SpacingMark"
+    return None
+
+
+def main():
+    entries = []
+    start = None
+    current = None
+    for cp in range(0x110000):
+        if 0xAC00 <= cp <= 0xD7A3:
+            kind = None
+        else:
+            kind = classify(cp)
+        if kind != current:
+            if current is not None:
+                entries.append((start, cp - 1, current))
+            start = cp
+            current = kind
+    if current is not None:
+        entries.append((start, 0x10FFFF, current))
+
+    lines = [
+        "// Generated by scripts/generate_grapheme_tables.py from Unicode {}. Do not edit by hand.".format(
+            unicodedata.unidata_version
+        ),
+        "",
+        "use super::BreakClass;",
+        "use super::BreakClass::*;",
+        "",
+        "/// Ranges of characters and their grapheme break class. Characters not listed are `Other`.",
+        "pub(crate) const BREAK_CLASSES: &[(u32, u32, BreakClass)] = &[",
+    ]
+    for first, last, kind in entries:
+        lines.append("    (0x{:04X}, 0x{:04X}, {}),".format(first, last, kind))
+    lines.append("];")
+    lines.append("")
+    with open(OUTPUT, "w", encoding="utf-8") as f:
+        f.write("\n".join(lines))
+    print("wrote {}".format(OUTPUT), file=sys.stderr)
+
+
+if __name__ == "__main__":
+    main()
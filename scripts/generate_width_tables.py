@@ -0,0 +1,82 @@
+#!/usr/bin/env python3
+"""Generates src/width/tables.rs from the Unicode database bundled with Python.
+
+Run from the repository root:
+
+    python3 scripts/generate_width_tables.py
+
+No network access is needed; the data comes from Python's `unicodedata` module, so the
+Unicode version of the tables is the one that module was built with.
+"""
+
+import sys
+import unicodedata
+
+OUTPUT = "src/width/tables.rs"
+
+
+def ranges(predicate):
+    result = []
+    start = None
+    for cp in range(0x110000):
+        if predicate(cp):
+            if start is None:
+                start = cp
+        elif start is not None:
+            result.append((start, cp - 1))
+            start = None
+    if start is not None:
+        result.append((start, 0x10FFFF))
+    return result
+
+
+# Unassigned code points in these blocks default to wide in EastAsianWidth.txt.
+WIDE_BLOCKS = [(0x3400, 0x4DBF), (0x4E00, 0x9FFF), (0xF900, 0xFAFF), (0x20000, 0x2FFFD), (0x30000, 0x3FFFD)]
+
+
+def is_wide(cp):
+    if unicodedata.category(chr(cp)) == "Cn":
+        return any(start <= cp <= end for start, end in WIDE_BLOCKS)
+    return unicodedata.east_asian_width(chr(cp)) in ("W", "F")
+
+
+def is_zero_width(cp):
+    if cp == 0x00AD:
+        # Soft hyphen is displayed by most terminals.
+        return False
+    if 0x1160 <= cp <= 0x11FF:
+        # Hangul medial vowels and final consonants join the preceding syllable.
+        return True
+    if cp == 0x200B:
+        return True
+    return unicodedata.category(chr(cp)) in ("Mn", "Me", "Cf")
+
+
+def table(name, entries):
+    lines = ["pub(crate) const {}: &[(u32, u32)] = &[".format(name)]
+    for start, end in entries:
+        lines.append("    (0x{:04X}, 0x{:04X}),".format(start, end))
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def main():
+    parts = [
+        "// Generated by scripts/generate_width_tables.py from Unicode {}. Do not edit by hand.".format(
+            unicodedata.unidata_version
+        ),
+        "",
+        "/// Ranges of characters that occupy two columns (East Asian Wide and Fullwidth).",
+        table("WIDE", ranges(is_wide)),
+        "",
+        "/// Ranges of characters that occupy no columns (combining marks and format characters).",
+        table("ZERO_WIDTH", ranges(is_zero_width)),
+        "",
+    ]
+    with open(OUTPUT, "w", encoding="utf-8") as f:
+        f.write("\n".join(parts))
+    print("wrote {}".format(OUTPUT), file=sys.stderr)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,188 @@
+//! This module provides string functions that understand escape sequences. They measure,
+//! cut and pad strings by the columns they occupy on screen, so text colored with
+//! `set_foreground_color`, `Style` or markup can be fitted into a fixed-width area, such
+//! as the inside of a box, without the escape sequences throwing off the count.
+//!
+//! When a cut ends in the middle of styled text, the result is closed with a reset so the
+//! style does not leak into whatever is drawn next.
+
+use crate::grapheme::{grapheme_width, graphemes};
+
+/// A piece of a string: either an escape sequence or a visible grapheme cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Escape(&'a str),
+    Grapheme(&'a str),
+}
+
+/// Splits a string into escape sequences and grapheme clusters.
+pub fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut result = Vec::new();
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < text.len() {
+        if bytes[i] == 0x1B {
+            let end = escape_end(bytes, i);
+            result.push(Segment::Escape(&text[i..end]));
+            i = end;
+        } else {
+            let end = text[i..].find('\x1B').map_or(text.len(), |offset| i + offset);
+            result.extend(graphemes(&text[i..end]).map(Segment::Grapheme));
+            i = end;
+        }
+    }
+    result
+}
+
+// Returns the index just past the escape sequence starting at `start`.
+fn escape_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    match bytes.get(i) {
+        // CSI: parameters and intermediates, then a final byte from '@' to '~'.
+        Some(b'[') => {
+            i += 1;
+            while i < bytes.len() && !(0x40..=0x7E).contains(&bytes[i]) {
+                i += 1;
+            }
+            (i + 1).min(bytes.len())
+        }
+        // OSC: ends with BEL or ESC '\'.
+        Some(b']') => {
+            i += 1;
+            while i < bytes.len() {
+                if bytes[i] == 0x07 {
+                    return i + 1;
+                }
+                if bytes[i] == 0x1B && bytes.get(i + 1) == Some(&b'\\') {
+                    return i + 2;
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        Some(_) => i + 1,
+        None => i,
+    }
+}
+
+fn is_sgr(sequence: &str) -> bool {
+    sequence.starts_with("\x1B[") && sequence.ends_with('m')
+}
+
+fn is_sgr_reset(sequence: &str) -> bool {
+    is_sgr(sequence) && sequence[2..sequence.len() - 1].split(';').all(|p| p.is_empty() || p.parse() == Ok(0))
+}
+
+/// Removes all escape sequences from a string.
+pub fn strip_ansi(text: &str) -> String {
+    segments(text)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Grapheme(cluster) => Some(cluster),
+            Segment::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the number of columns the string occupies on screen.
+pub fn visible_width(text: &str) -> usize {
+    segments(text)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Grapheme(cluster) => grapheme_width(cluster),
+            Segment::Escape(_) => 0,
+        })
+        .sum()
+}
+
+/// Returns the columns `start..end` of a string.
+///
+/// Styles switched on before `start` are carried into the result, and the result is reset
+/// at the end if a style is still active. Grapheme clusters are never split; a wide
+/// cluster that does not fit entirely inside the range is left out.
+pub fn slice(text: &str, start: usize, end: usize) -> String {
+    let mut output = String::new();
+    let mut active: Vec<&str> = Vec::new();
+    let mut column = 0;
+    let mut started = false;
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(sequence) => {
+                if is_sgr_reset(sequence) {
+                    active.clear();
+                } else if is_sgr(sequence) {
+                    active.push(sequence);
+                }
+                if started {
+                    output.push_str(sequence);
+                }
+            }
+            Segment::Grapheme(cluster) => {
+                let width = grapheme_width(cluster);
+                if column + width > end || (width == 0 && column == end) {
+                    break;
+                }
+                if column >= start {
+                    if !started {
+                        active.iter().for_each(|sequence| output.push_str(sequence));
+                        started = true;
+                    }
+                    output.push_str(cluster);
+                }
+                column += width;
+            }
+        }
+    }
+    if started && !active.is_empty() {
+        output.push_str("\x1B[0m");
+    }
+    output
+}
+
+/// Cuts a string down to at most `width` columns.
+pub fn truncate(text: &str, width: usize) -> String {
+    slice(text, 0, width)
+}
+
+/// Cuts a string down to at most `width` columns, ending it with `ellipsis` if anything
+/// had to be removed.
+pub fn truncate_with_ellipsis(text: &str, width: usize, ellipsis: &str) -> String {
+    if visible_width(text) <= width {
+        return text.to_string();
+    }
+    let ellipsis_width = visible_width(ellipsis);
+    if ellipsis_width >= width {
+        return truncate(ellipsis, width);
+    }
+    truncate(text, width - ellipsis_width) + ellipsis
+}
+
+/// Pads a string with spaces on the right to `width` columns.
+///
+/// Strings that are already wider are returned unchanged.
+pub fn pad_right(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(visible_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Pads a string with spaces on the left to `width` columns.
+///
+/// Strings that are already wider are returned unchanged.
+pub fn pad_left(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(visible_width(text));
+    format!("{}{}", " ".repeat(padding), text)
+}
+
+/// Pads a string with spaces on both sides to center it in `width` columns.
+///
+/// Strings that are already wider are returned unchanged.
+pub fn center(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(visible_width(text));
+    let left = padding / 2;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(padding - left))
+}
+
+/// Truncates and pads a string so it occupies exactly `width` columns.
+pub fn fit(text: &str, width: usize) -> String {
+    pad_right(&truncate(text, width), width)
+}
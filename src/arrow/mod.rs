@@ -79,3 +79,10 @@ pub fn arrow_symbol_to_char(symbol: ArrowSymbol) -> char {
         ArrowSymbol::CircularArrow => '↻',
     }
 }
+
+impl ArrowSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(arrow_symbol_to_char(*self))
+    }
+}
@@ -68,6 +68,11 @@ pub enum BrailleSymbol {
 }
 
 impl BrailleSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(self.to_char())
+    }
+
     /// Converts the BrailleSymbol enum variant to its corresponding Unicode character.
     pub fn to_char(self) -> char {
         match self {
@@ -66,3 +66,10 @@ pub fn opposite_color_piece(piece: ChessPieceSymbol) -> ChessPieceSymbol {
         ChessPieceSymbol::BlackPawn => ChessPieceSymbol::WhitePawn,
     }
 }
+
+impl ChessPieceSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(chess_piece_to_char(*self))
+    }
+}
@@ -55,4 +55,11 @@ pub fn circle_symbol_to_char(symbol: CircleSymbol) -> char {
         CircleSymbol::CircledMinus => '⊖',
         CircleSymbol::CircledTimes => '⊗',
     }
-}
\ No newline at end of file
+}
+
+impl CircleSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(circle_symbol_to_char(*self))
+    }
+}
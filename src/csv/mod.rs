@@ -0,0 +1,262 @@
+//! This module provides a small CSV parser that follows RFC 4180, and builds tables from
+//! CSV files.
+//!
+//! Fields are separated by a delimiter, usually a comma, and records by line breaks (CRLF,
+//! LF or CR). A field in double quotes may contain the delimiter and line breaks, and a
+//! doubled quote inside it stands for one quote. Blank lines are skipped and a leading
+//! byte order mark is ignored.
+//!
+//! `Table::from_csv` and `Table::load_csv` take the column headers from the first record
+//! and right-align columns whose cells are all numbers.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::table::Table;
+//!
+//! match Table::load_csv("results.csv", ',') {
+//!     Ok(table) => println(&table.render_to_string()),
+//!     Err(e) => println(&e.to_string()),
+//! }
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::table::{parse_number, Column, Table};
+use crate::text::HorizontalAlign;
+
+/// Errors that can occur while reading CSV.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The text is not valid CSV. `line` counts from 1.
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "could not read CSV: {}", e),
+            CsvError::Parse { line, message } => write!(f, "CSV line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// Parses comma-separated text into records of fields.
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, CsvError> {
+    parse_csv_with(text, ',')
+}
+
+/// Parses text with fields separated by `delimiter` into records of fields. The delimiter
+/// should not be a double quote or a line break.
+pub fn parse_csv_with(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, CsvError> {
+    let mut chars = text.strip_prefix('\u{FEFF}').unwrap_or(text).chars().peekable();
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    // Whether the current record has a quoted field, so `""` is not taken for a blank line.
+    let mut quoted = false;
+    let mut line = 1;
+    loop {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            quoted = true;
+            let start = line;
+            loop {
+                match chars.next() {
+                    None => {
+                        let message = String::from("quoted field is never closed");
+                        return Err(CsvError::Parse { line: start, message });
+                    }
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(ch) => {
+                        // Count "\r\n" once, at the '\n'.
+                        if ch == '\n' || (ch == '\r' && chars.peek() != Some(&'\n')) {
+                            line += 1;
+                        }
+                        field.push(ch);
+                    }
+                }
+            }
+            match chars.peek() {
+                None | Some('\r') | Some('\n') => {}
+                Some(&ch) if ch == delimiter => {}
+                Some(&ch) => {
+                    let message = format!("unexpected '{}' after a closing quote", ch);
+                    return Err(CsvError::Parse { line, message });
+                }
+            }
+        } else {
+            while let Some(&ch) = chars.peek() {
+                if ch == delimiter || ch == '\r' || ch == '\n' {
+                    break;
+                }
+                field.push(ch);
+                chars.next();
+            }
+        }
+        record.push(field);
+
+        match chars.next() {
+            Some(ch) if ch == delimiter => continue,
+            Some(ch) => {
+                if ch == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                line += 1;
+            }
+            None => {}
+        }
+        // A record holding a single unquoted empty field is a blank line.
+        let record = std::mem::take(&mut record);
+        let was_quoted = std::mem::take(&mut quoted);
+        if record.len() > 1 || !record[0].is_empty() || was_quoted {
+            records.push(record);
+        }
+        if chars.peek().is_none() {
+            return Ok(records);
+        }
+    }
+}
+
+// Returns true if a column has at least one value and every value is a finite number.
+fn is_numeric(records: &[Vec<String>], column: usize) -> bool {
+    let mut values = records
+        .iter()
+        .filter_map(|record| record.get(column))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .peekable();
+    values.peek().is_some() && values.all(|value| parse_number(value).is_some())
+}
+
+impl Table {
+    /// Builds a table from CSV text. The first record holds the column headers; columns
+    /// whose cells are all numbers are right-aligned.
+    pub fn from_csv(text: &str, delimiter: char) -> Result<Table, CsvError> {
+        let mut records = parse_csv_with(text, delimiter)?.into_iter();
+        let headers = records.next().unwrap_or_default();
+        let rows: Vec<Vec<String>> = records.collect();
+        let count = rows.iter().map(Vec::len).chain([headers.len()]).max().unwrap_or(0);
+        let columns = (0..count)
+            .map(|column| {
+                let column_header = headers.get(column).map_or("", String::as_str);
+                let align = if is_numeric(&rows, column) { HorizontalAlign::Right } else { HorizontalAlign::Left };
+                Column::new(column_header).align(align)
+            })
+            .collect();
+        let mut table = Table::new(columns);
+        for row in rows {
+            table.add_row(row);
+        }
+        Ok(table)
+    }
+
+    /// Builds a table from a CSV file, like `from_csv`.
+    pub fn load_csv<P: AsRef<Path>>(path: P, delimiter: char) -> Result<Table, CsvError> {
+        let text = fs::read_to_string(path).map_err(CsvError::Io)?;
+        Table::from_csv(&text, delimiter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter().map(|row| row.iter().map(|field| field.to_string()).collect()).collect()
+    }
+
+    fn parse_error_line(text: &str) -> usize {
+        match parse_csv(text) {
+            Err(CsvError::Parse { line, .. }) => line,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn splits_fields_and_records() {
+        let parsed = parse_csv("a,b,c\n1,2,3\n").unwrap();
+        assert_eq!(parsed, records(&[&["a", "b", "c"], &["1", "2", "3"]]));
+    }
+
+    #[test]
+    fn quoted_fields_keep_delimiters() {
+        let parsed = parse_csv("name,comment\nAda,\"neat, tidy\"\n").unwrap();
+        assert_eq!(parsed, records(&[&["name", "comment"], &["Ada", "neat, tidy"]]));
+    }
+
+    #[test]
+    fn quoted_fields_keep_line_breaks() {
+        let parsed = parse_csv("a,b\r\n\"one\r\ntwo\",x\r\n").unwrap();
+        assert_eq!(parsed, records(&[&["a", "b"], &["one\r\ntwo", "x"]]));
+    }
+
+    #[test]
+    fn doubled_quotes_become_one() {
+        let parsed = parse_csv("\"Said \"\"hi\"\"\",\"\"\"\"\n").unwrap();
+        assert_eq!(parsed, records(&[&["Said \"hi\"", "\""]]));
+    }
+
+    #[test]
+    fn accepts_all_line_breaks() {
+        let parsed = parse_csv("a\r\nb\nc\rd").unwrap();
+        assert_eq!(parsed, records(&[&["a"], &["b"], &["c"], &["d"]]));
+    }
+
+    #[test]
+    fn trailing_delimiter_adds_empty_field() {
+        let parsed = parse_csv("a,b,\n,,\n").unwrap();
+        assert_eq!(parsed, records(&[&["a", "b", ""], &["", "", ""]]));
+    }
+
+    #[test]
+    fn skips_blank_lines_and_byte_order_mark() {
+        let parsed = parse_csv("\u{FEFF}a,b\n\n\r\n1,2\n\n").unwrap();
+        assert_eq!(parsed, records(&[&["a", "b"], &["1", "2"]]));
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_records_holding_a_quoted_empty_field() {
+        let parsed = parse_csv("a\n\"\"\nb").unwrap();
+        assert_eq!(parsed, records(&[&["a"], &[""], &["b"]]));
+    }
+
+    #[test]
+    fn custom_delimiter() {
+        let parsed = parse_csv_with("a;\"b;c\";d,e\n", ';').unwrap();
+        assert_eq!(parsed, records(&[&["a", "b;c", "d,e"]]));
+        let parsed = parse_csv_with("a\tb\n", '\t').unwrap();
+        assert_eq!(parsed, records(&[&["a", "b"]]));
+    }
+
+    #[test]
+    fn unclosed_quote_reports_its_starting_line() {
+        assert_eq!(parse_error_line("a,b\n\"open,1\n2,3\n"), 2);
+        assert_eq!(parse_error_line("a\n\"x\r\ny\r\n"), 2);
+    }
+
+    #[test]
+    fn text_after_closing_quote_is_an_error() {
+        assert_eq!(parse_error_line("a\n\"multi\nline\"x\n"), 3);
+    }
+
+    #[test]
+    fn numeric_columns_are_finite_numbers() {
+        let rows = records(&[&["1.5", "NaN", ""], &["-2", "inf", "x"], &[" 3 ", "4", ""]]);
+        assert!(is_numeric(&rows, 0));
+        assert!(!is_numeric(&rows, 1));
+        assert!(!is_numeric(&rows, 2));
+        assert!(!is_numeric(&rows, 3));
+    }
+}
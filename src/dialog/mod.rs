@@ -0,0 +1,357 @@
+//! This module provides modal dialogs: message boxes, confirmation dialogs and input
+//! dialogs that appear centered in the viewport on top of whatever is on the screen.
+//!
+//! A dialog draws a box with a title, a wrapped message, an optional text input and a row
+//! of buttons, with a shadow in a `ShadeStyle` below and to the right. It takes all input
+//! until it is dismissed, then puts back the screen content it covered, which it saves
+//! beforehand with `mirror::save_area`.
+//!
+//! Tab and Shift-Tab choose a button and Enter presses it. Without an input, the arrow
+//! keys also choose a button and its first letter presses it. Esc and Ctrl-C cancel the
+//! dialog. When input is not a terminal, dialogs fall back to plain line-by-line questions.
+//!
+//! ```no_run
+//! use rpian_terminal::dialog::{confirm_dialog, input_dialog, message_box};
+//!
+//! if confirm_dialog("Quit", "Discard the unsaved changes?", false) == Some(true) {
+//!     message_box("Bye", "See you next time.");
+//! }
+//! let name = input_dialog("Rename", "New file name:", "notes.txt");
+//! ```
+
+use crate::event::{read_key_event, KeyCode, KeyEvent};
+use crate::geometry::{Rect, Size};
+use crate::input::TextInput;
+use crate::mirror::save_area;
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::text::wrap_text;
+use crate::theme::{role_style, Role};
+use crate::width::str_width;
+use crate::*;
+
+/// A modal dialog with a message, an optional text input and buttons.
+pub struct Dialog {
+    pub title: String,
+    pub message: String,
+    pub box_style: BoxStyle,
+    /// The shade of the shadow, or `None` for no shadow.
+    pub shadow: Option<ShadeStyle>,
+    /// The widest the dialog may be, including its border.
+    pub max_width: u16,
+    buttons: Vec<String>,
+    selected: usize,
+    input: Option<TextInput>,
+}
+
+impl Dialog {
+    /// Creates a dialog with a single OK button.
+    pub fn new(title: &str, message: &str) -> Self {
+        Dialog {
+            title: title.to_string(),
+            message: message.to_string(),
+            box_style: BoxStyle::Double,
+            shadow: Some(ShadeStyle::Medium),
+            max_width: 60,
+            buttons: vec![String::from("OK")],
+            selected: 0,
+            input: None,
+        }
+    }
+
+    /// Replaces the buttons. A dialog always has at least one button.
+    pub fn buttons(mut self, buttons: &[&str]) -> Self {
+        if !buttons.is_empty() {
+            self.buttons = buttons.iter().map(|button| button.to_string()).collect();
+            self.selected = self.selected.min(self.buttons.len() - 1);
+        }
+        self
+    }
+
+    /// Sets the button chosen when the dialog opens.
+    pub fn default_button(mut self, index: usize) -> Self {
+        self.selected = index.min(self.buttons.len() - 1);
+        self
+    }
+
+    pub fn box_style(mut self, box_style: BoxStyle) -> Self {
+        self.box_style = box_style;
+        self
+    }
+
+    pub fn shadow(mut self, shadow: Option<ShadeStyle>) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    pub fn max_width(mut self, max_width: u16) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Adds a text input below the message. Enter on the first button is refused while its
+    /// value is invalid.
+    pub fn input(mut self, input: TextInput) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// Returns the text typed into the input, if the dialog has one.
+    pub fn text(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.text())
+    }
+
+    // Works out the width inside the border and padding, and the wrapped message, cut
+    // short so the input and buttons still fit in the viewport.
+    fn content(&self) -> (u16, Vec<String>) {
+        let (viewport_width, viewport_height) = get_viewport();
+        let limit = self.max_width.min(viewport_width.saturating_sub(3)).saturating_sub(4).max(1);
+        let mut lines: Vec<String> =
+            wrap_text(&self.message, limit as usize, false).into_iter().map(|(line, _)| line).collect();
+        let max_lines = viewport_height.saturating_sub(self.input_rows() + 7).max(1) as usize;
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            let last = lines.pop().unwrap_or_default() + "…";
+            lines.push(crate::ansi::truncate_with_ellipsis(&last, limit as usize, "…"));
+        }
+        let widest = lines.iter().map(|line| str_width(line)).max().unwrap_or(0);
+        let buttons = self.button_labels().iter().map(|label| str_width(label) + 2).sum::<usize>() - 2;
+        let title = str_width(&self.title) + 2;
+        let input = if self.input.is_some() { 30 } else { 0 };
+        let width = widest.max(buttons).max(title).max(input).min(limit as usize);
+        (width as u16, lines)
+    }
+
+    // The rows taken by the input and its error line.
+    fn input_rows(&self) -> u16 {
+        if self.input.is_some() {
+            2
+        } else {
+            0
+        }
+    }
+
+    fn button_labels(&self) -> Vec<String> {
+        self.buttons.iter().map(|button| format!("[ {} ]", button)).collect()
+    }
+
+    /// Returns the area the dialog covers, without its shadow, centered in the viewport.
+    pub fn area(&self) -> Rect {
+        let (width, lines) = self.content();
+        let height = lines.len() as u16 + self.input_rows() + 6;
+        let size = Size::new(width + 4, height);
+        Rect::viewport().centered(size)
+    }
+
+    // Returns the area the dialog and its shadow cover.
+    fn covered_area(&self) -> Rect {
+        let area = self.area();
+        match self.shadow {
+            Some(_) => Rect::new(area.x, area.y, area.width + 2, area.height + 1),
+            None => area,
+        }
+    }
+
+    /// Draws the dialog into a buffer, with its top-left corner at the given position.
+    pub fn draw(&self, buffer: &mut ScreenBuffer, x: u16, y: u16) {
+        let (width, lines) = self.content();
+        let area = Rect::new(x, y, self.area().width, self.area().height);
+        if let Some(shade) = self.shadow {
+            buffer.draw_shaded_rect(Rect::new(x + 2, y + 1, area.width, area.height), shade, role_style(Role::Muted));
+        }
+        buffer.fill(area, ' ', Style::default());
+        buffer.draw_box(area, self.box_style, role_style(Role::Border));
+        if !self.title.is_empty() {
+            let title = crate::ansi::truncate(&format!(" {} ", self.title), area.width.saturating_sub(4) as usize);
+            let title_x = x + (area.width - str_width(&title) as u16) / 2;
+            buffer.put_str(title_x, y, &title, role_style(Role::Accent));
+        }
+
+        let left = x + 2;
+        let mut row = y + 2;
+        for line in &lines {
+            buffer.put_str(left, row, line, Style::default());
+            row += 1;
+        }
+        row += 1;
+        if let Some(input) = &self.input {
+            input.draw(Rect::new(left, row, width, 1), buffer, true);
+            if let Some(error) = input.error() {
+                buffer.put_str(left, row + 1, &crate::ansi::truncate(error, width as usize), role_style(Role::Error));
+            }
+            row += 2;
+        }
+
+        let labels = self.button_labels();
+        let total = labels.iter().map(|label| str_width(label) as u16 + 2).sum::<u16>() - 2;
+        let mut column = left + width.saturating_sub(total) / 2;
+        for (i, label) in labels.iter().enumerate() {
+            let role = if i == self.selected { Role::Selection } else { Role::Primary };
+            column = buffer.put_str(column, row, label, role_style(role)) + 2;
+        }
+    }
+
+    // Reacts to a key. Returns `Some(Some(button))` when a button is pressed and
+    // `Some(None)` when the dialog is cancelled.
+    fn handle_key(&mut self, key: &KeyEvent) -> Option<Option<usize>> {
+        let count = self.buttons.len();
+        match key.code {
+            KeyCode::Esc => return Some(None),
+            _ if key.is_ctrl('c') => return Some(None),
+            KeyCode::Tab if !key.shift => self.selected = (self.selected + 1) % count,
+            KeyCode::Tab | KeyCode::BackTab => self.selected = (self.selected + count - 1) % count,
+            KeyCode::Enter => {
+                // Only the first button accepts the input; the others, like Cancel, need
+                // not wait for it to be valid.
+                if let Some(input) = self.input.as_mut().filter(|_| self.selected == 0) {
+                    input.handle_key(key);
+                    if !input.is_valid() {
+                        input.reset_state();
+                        return None;
+                    }
+                }
+                return Some(Some(self.selected));
+            }
+            _ => match &mut self.input {
+                Some(input) => {
+                    input.handle_key(key);
+                }
+                None => match key.code {
+                    KeyCode::Left | KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+                    KeyCode::Right | KeyCode::Down => self.selected = (self.selected + 1).min(count - 1),
+                    KeyCode::Char(ch) => {
+                        let ch = ch.to_lowercase().next().unwrap_or(ch);
+                        let button = self
+                            .buttons
+                            .iter()
+                            .position(|button| button.to_lowercase().starts_with(ch));
+                        if button.is_some() {
+                            return Some(button);
+                        }
+                    }
+                    _ => {}
+                },
+            },
+        }
+        None
+    }
+
+    /// Shows the dialog until a button is pressed and returns its index, or `None` if the
+    /// dialog is cancelled or input ends. The screen under the dialog is restored.
+    pub fn run(&mut self) -> Option<usize> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            return self.run_lines();
+        }
+
+        let covered = self.covered_area();
+        let saved = save_area(covered);
+        let mut previous: Option<ScreenBuffer> = None;
+        let result = loop {
+            // Start from what was on screen, so the corners beside the shadow stay visible.
+            let mut frame = saved.contents().clone();
+            self.draw(&mut frame, 1, 1);
+            match &previous {
+                Some(previous) => frame.render_diff_at(previous, covered.x, covered.y),
+                None => frame.render_at(covered.x, covered.y),
+            }
+            match frame.cursor() {
+                Some(cursor) => {
+                    move_cursor_to(covered.x + cursor.x - 1, covered.y + cursor.y - 1);
+                    show_cursor();
+                }
+                None => hide_cursor(),
+            }
+            previous = Some(frame);
+
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            if let Some(result) = self.handle_key(&key) {
+                break result;
+            }
+        };
+
+        saved.restore();
+        show_cursor();
+        if !was_raw {
+            disable_raw_mode();
+        }
+        result
+    }
+
+    // Asks the dialog's question one line at a time, for when input is not a terminal.
+    fn run_lines(&mut self) -> Option<usize> {
+        if !self.title.is_empty() {
+            println(&format!("{}{}\x1B[0m", role_style(Role::Accent).to_ansi(), self.title));
+        }
+        println(&self.message);
+        if let Some(input) = &mut self.input {
+            loop {
+                print("> ");
+                let line = read_fallback_line()?;
+                input.set_value(&line);
+                match input.error() {
+                    Some(error) => println(&format!("{}{}\x1B[0m", role_style(Role::Error).to_ansi(), error)),
+                    None => return Some(self.selected),
+                }
+            }
+        }
+        if self.buttons.len() == 1 {
+            return Some(0);
+        }
+        let options: Vec<String> =
+            self.buttons.iter().enumerate().map(|(i, button)| format!("{}) {}", i + 1, button)).collect();
+        loop {
+            print(&format!("{} [{}]: ", options.join("  "), self.selected + 1));
+            let answer = read_fallback_line()?.to_lowercase();
+            if answer.is_empty() {
+                return Some(self.selected);
+            }
+            if let Ok(number) = answer.parse::<usize>() {
+                if (1..=self.buttons.len()).contains(&number) {
+                    return Some(number - 1);
+                }
+            }
+            if let Some(index) = self.buttons.iter().position(|button| button.to_lowercase().starts_with(&answer)) {
+                return Some(index);
+            }
+        }
+    }
+}
+
+// Reads a line for the fallback dialogs. Returns `None` at end of input.
+fn read_fallback_line() -> Option<String> {
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(line.trim().to_string()),
+        Err(e) => {
+            handle_io_error(e);
+            None
+        }
+    }
+}
+
+/// Shows a message with an OK button and waits until it is dismissed.
+pub fn message_box(title: &str, message: &str) {
+    Dialog::new(title, message).run();
+}
+
+/// Asks a yes/no question in a dialog. Returns `None` if it is cancelled.
+pub fn confirm_dialog(title: &str, message: &str, default: bool) -> Option<bool> {
+    let mut dialog = Dialog::new(title, message).buttons(&["Yes", "No"]).default_button(usize::from(!default));
+    dialog.run().map(|button| button == 0)
+}
+
+/// Asks for a line of text in a dialog, starting with `default`. Returns `None` if the
+/// dialog is cancelled.
+pub fn input_dialog(title: &str, message: &str, default: &str) -> Option<String> {
+    let input = TextInput::new().value(default).style(Style::new().attribute(Attribute::Underscore));
+    let mut dialog = Dialog::new(title, message).buttons(&["OK", "Cancel"]).input(input);
+    match dialog.run() {
+        Some(0) => dialog.text().map(str::to_string),
+        _ => None,
+    }
+}
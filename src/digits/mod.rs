@@ -0,0 +1,229 @@
+//! This module draws large digits for clocks, countdown timers and scoreboards.
+//!
+//! Digits are built like a seven-segment display. The segments can be drawn with the
+//! half-block or full-block characters from `rbox::BlockChar`, or with box-drawing lines
+//! whose corners and junctions come from `rbox::SingleBox`. Besides `0`-`9`, the symbols
+//! `:`, `.`, `-` and space are supported; any other character is drawn as a space.
+
+use crate::rbox::{block_char_to_char, single_box_to_char, BlockChar, SingleBox};
+use crate::screen::ScreenBuffer;
+use crate::style::{set_style, Style};
+use crate::*;
+
+/// The characters large digits are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitStyle {
+    /// Half blocks (`▀`, `▄`, `█`): two segment rows per line, the most compact style.
+    #[default]
+    HalfBlock,
+    /// Full blocks (`█`): one segment row per line.
+    FullBlock,
+    /// Box-drawing lines such as `─`, `│`, `┌` and `┼`.
+    Segment,
+}
+
+/// Settings for drawing large digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigDigits {
+    /// The length of each segment, in cells. Must be at least 1.
+    pub size: u16,
+    pub style: DigitStyle,
+    /// The colors and attributes the digits are drawn with.
+    pub color: Style,
+    /// The number of blank columns between characters.
+    pub spacing: u16,
+}
+
+impl Default for BigDigits {
+    fn default() -> Self {
+        BigDigits { size: 1, style: DigitStyle::HalfBlock, color: Style::default(), spacing: 1 }
+    }
+}
+
+// Segments in the usual order: top, top right, bottom right, bottom, bottom left,
+// top left, middle.
+fn segments(ch: char) -> Option<[bool; 7]> {
+    let bits: u8 = match ch {
+        '0' => 0b0111111,
+        '1' => 0b0000110,
+        '2' => 0b1011011,
+        '3' => 0b1001111,
+        '4' => 0b1100110,
+        '5' => 0b1101101,
+        '6' => 0b1111101,
+        '7' => 0b0000111,
+        '8' => 0b1111111,
+        '9' => 0b1101111,
+        '-' => 0b1000000,
+        ' ' => 0,
+        _ => return None,
+    };
+    Some(std::array::from_fn(|i| bits & (1 << i) != 0))
+}
+
+impl BigDigits {
+    /// Returns the number of pixel rows in a character: three horizontal segments and
+    /// two gaps of `size` rows between them.
+    fn pixel_height(&self) -> usize {
+        2 * self.size.max(1) as usize + 3
+    }
+
+    // Builds the pixel grid of a character, indexed as [row][column].
+    fn pixels(&self, ch: char) -> Vec<Vec<bool>> {
+        let n = self.size.max(1) as usize;
+        let height = self.pixel_height();
+        match ch {
+            ':' | '.' => {
+                let mut grid = vec![vec![false]; height];
+                if ch == ':' {
+                    grid[n.div_ceil(2)][0] = true;
+                    grid[n + 1 + n.div_ceil(2)][0] = true;
+                } else {
+                    grid[height - 1][0] = true;
+                }
+                grid
+            }
+            _ => {
+                let on = segments(ch).unwrap_or([false; 7]);
+                let width = n + 2;
+                let mut grid = vec![vec![false; width]; height];
+                let middle = n + 1;
+                let bottom = 2 * n + 2;
+                for (y, on_row) in [(0, on[0]), (middle, on[6]), (bottom, on[3])] {
+                    grid[y].iter_mut().for_each(|pixel| *pixel |= on_row);
+                }
+                for (y, row) in grid.iter_mut().enumerate() {
+                    let upper = y <= middle;
+                    let lower = y >= middle;
+                    row[width - 1] |= (upper && on[1]) || (lower && on[2]);
+                    row[0] |= (upper && on[5]) || (lower && on[4]);
+                }
+                grid
+            }
+        }
+    }
+
+    fn render_char(&self, ch: char) -> Vec<String> {
+        let grid = self.pixels(ch);
+        let width = grid[0].len();
+        let lit = |x: usize, y: usize| grid.get(y).and_then(|row| row.get(x)).copied().unwrap_or(false);
+        match self.style {
+            DigitStyle::FullBlock => grid
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|p| if *p { block_char_to_char(BlockChar::Full) } else { ' ' })
+                        .collect()
+                })
+                .collect(),
+            DigitStyle::HalfBlock => (0..grid.len())
+                .step_by(2)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| match (lit(x, y), lit(x, y + 1)) {
+                            (true, true) => block_char_to_char(BlockChar::Full),
+                            (true, false) => block_char_to_char(BlockChar::UpperHalf),
+                            (false, true) => block_char_to_char(BlockChar::LowerHalf),
+                            (false, false) => ' ',
+                        })
+                        .collect()
+                })
+                .collect(),
+            DigitStyle::Segment => (0..grid.len())
+                .map(|y| {
+                    (0..width)
+                        .map(|x| {
+                            if !lit(x, y) {
+                                return ' ';
+                            }
+                            let up = y > 0 && lit(x, y - 1);
+                            let down = lit(x, y + 1);
+                            let left = x > 0 && lit(x - 1, y);
+                            let right = lit(x + 1, y);
+                            segment_char(up, down, left, right)
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Renders text as lines of large characters.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        let mut lines = vec![String::new(); self.height() as usize];
+        for (i, ch) in text.chars().enumerate() {
+            if i > 0 {
+                lines.iter_mut().for_each(|line| line.push_str(&" ".repeat(self.spacing as usize)));
+            }
+            for (line, row) in lines.iter_mut().zip(self.render_char(ch)) {
+                line.push_str(&row);
+            }
+        }
+        lines
+    }
+
+    /// Returns the number of lines the characters occupy.
+    pub fn height(&self) -> u16 {
+        match self.style {
+            DigitStyle::HalfBlock => self.pixel_height().div_ceil(2) as u16,
+            DigitStyle::FullBlock | DigitStyle::Segment => self.pixel_height() as u16,
+        }
+    }
+
+    /// Returns the number of columns the given text occupies.
+    pub fn width(&self, text: &str) -> u16 {
+        self.render(text).first().map_or(0, |line| line.chars().count() as u16)
+    }
+
+    /// Draws text in large characters with the top-left corner at the given position.
+    pub fn draw(&self, text: &str, x: u16, y: u16) {
+        let (viewport_width, viewport_height) = get_viewport();
+        if x + self.width(text) > viewport_width || y + self.height() > viewport_height {
+            handle_boundary_error("Digits extend beyond viewport");
+            return;
+        }
+
+        set_style(self.color);
+        for (i, line) in self.render(text).iter().enumerate() {
+            move_cursor_to(x, y + i as u16);
+            print(line);
+        }
+        reset_color();
+    }
+}
+
+// Picks the box-drawing character that connects a segment cell to its lit neighbours.
+fn segment_char(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, true, true) | (false, false, true, false) | (false, false, false, true) => {
+            single_box_to_char(SingleBox::Horizontal)
+        }
+        (true, true, false, false) => single_box_to_char(SingleBox::Vertical),
+        (true, false, false, false) => '╵',
+        (false, true, false, false) => '╷',
+        (false, true, false, true) => single_box_to_char(SingleBox::TopLeft),
+        (false, true, true, false) => single_box_to_char(SingleBox::TopRight),
+        (true, false, false, true) => single_box_to_char(SingleBox::BottomLeft),
+        (true, false, true, false) => single_box_to_char(SingleBox::BottomRight),
+        (true, true, false, true) => single_box_to_char(SingleBox::VerticalLeft),
+        (true, true, true, false) => single_box_to_char(SingleBox::VerticalRight),
+        (false, true, true, true) => single_box_to_char(SingleBox::HorizontalDown),
+        (true, false, true, true) => single_box_to_char(SingleBox::HorizontalUp),
+        (true, true, true, true) => single_box_to_char(SingleBox::VerticalHorizontal),
+        (false, false, false, false) => '•',
+    }
+}
+
+impl ScreenBuffer {
+    /// Draws text in large characters into the buffer with the top-left corner at the
+    /// given position.
+    pub fn draw_big_digits(&mut self, x: u16, y: u16, text: &str, digits: &BigDigits) {
+        for (i, line) in digits.render(text).iter().enumerate() {
+            for (column, ch) in (x..).zip(line.chars()) {
+                if ch != ' ' {
+                    self.set_char(column, y + i as u16, ch, digits.color);
+                }
+            }
+        }
+    }
+}
@@ -77,3 +77,10 @@ pub fn smiley_symbol_to_char(symbol: EmojiSymbol) -> char {
         EmojiSymbol::Robot => '🤖',
     }
 }
+
+impl EmojiSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(smiley_symbol_to_char(*self))
+    }
+}
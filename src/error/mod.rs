@@ -16,16 +16,20 @@ pub unsafe fn set_error_handler(handler: &'static dyn ErrorHandler) {
 
 // Internal function to handle I/O errors
 pub fn handle_io_error(error: io::Error) {
-    unsafe {
-        ERROR_HANDLER.handle_io_error(error);
-    }
+    let handler = unsafe { ERROR_HANDLER };
+    handler.handle_io_error(error);
 }
 
 // Internal function to handle boundary errors
 pub fn handle_boundary_error(message: &str) {
-    unsafe {
-        ERROR_HANDLER.handle_boundary_error(message);
-    }
+    let handler = unsafe { ERROR_HANDLER };
+    handler.handle_boundary_error(message);
+}
+
+// Internal function to handle parse errors
+pub fn handle_parse_error(message: &str) {
+    let handler = unsafe { ERROR_HANDLER };
+    handler.handle_parse_error(message);
 }
 
 /// Trait for custom error handling in rpian-terminal
@@ -35,6 +39,11 @@ pub trait ErrorHandler {
 
     /// Handle a boundary error (e.g., cursor or drawing outside viewport)
     fn handle_boundary_error(&self, message: &str);
+
+    /// Handle a parse error (e.g., malformed markup or configuration text)
+    fn handle_parse_error(&self, message: &str) {
+        eprintln!("rpian-terminal Parse Error: {}", message);
+    }
 }
 
 /// Default error handler that logs errors to stderr
@@ -48,4 +57,8 @@ impl ErrorHandler for DefaultErrorHandler {
     fn handle_boundary_error(&self, message: &str) {
         eprintln!("rpian-terminal Boundary Error: {}", message);
     }
+
+    fn handle_parse_error(&self, message: &str) {
+        eprintln!("rpian-terminal Parse Error: {}", message);
+    }
 }
\ No newline at end of file
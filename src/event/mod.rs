@@ -0,0 +1,379 @@
+//! This module turns raw terminal input into key and mouse events.
+//!
+//! In raw mode (see the `terminal` module) keys arrive as bytes: printable characters as
+//! UTF-8, control keys as control codes and special keys such as the arrows as escape
+//! sequences. `parse_keys` decodes those bytes, and `read_event` and `poll_event` read and
+//! decode input in one step. After `enable_mouse_capture`, clicks, drags and the scroll
+//! wheel are reported as well.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::geometry::{Point, Size};
+use crate::print;
+use crate::terminal::{read_input, read_input_timeout, sync_viewport};
+
+/// A key, without modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    Char(char),
+    Enter,
+    Tab,
+    /// Shift-Tab.
+    BackTab,
+    Backspace,
+    Delete,
+    Insert,
+    Esc,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// A function key, `F(1)` to `F(12)`.
+    F(u8),
+}
+
+/// A key press together with the modifier keys held down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyEvent {
+    /// Creates a key event without modifiers.
+    pub fn new(code: KeyCode) -> Self {
+        KeyEvent { code, ctrl: false, alt: false, shift: false }
+    }
+
+    /// Creates the event for a character typed with Ctrl held down.
+    pub fn ctrl(ch: char) -> Self {
+        KeyEvent { ctrl: true, ..Self::new(KeyCode::Char(ch)) }
+    }
+
+    /// Creates the event for a character typed with Alt held down.
+    pub fn alt(ch: char) -> Self {
+        KeyEvent { alt: true, ..Self::new(KeyCode::Char(ch)) }
+    }
+
+    /// Returns true for Ctrl plus the given character.
+    pub fn is_ctrl(&self, ch: char) -> bool {
+        self.ctrl && !self.alt && self.code == KeyCode::Char(ch)
+    }
+
+    /// Returns the character typed, if this is a plain or shifted printable character.
+    pub fn printable(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(ch) if !self.ctrl && !self.alt && !ch.is_control() => Some(ch),
+            _ => None,
+        }
+    }
+}
+
+impl From<KeyCode> for KeyEvent {
+    fn from(code: KeyCode) -> Self {
+        KeyEvent::new(code)
+    }
+}
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// What the mouse did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseKind {
+    Press(MouseButton),
+    Release(MouseButton),
+    /// The mouse moved with a button held down.
+    Drag(MouseButton),
+    /// The mouse moved with no button held down. Only some terminals report this.
+    Moved,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// A mouse action at a 1-based terminal position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    pub kind: MouseKind,
+    pub x: u16,
+    pub y: u16,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl MouseEvent {
+    /// Returns the position of the event.
+    pub fn position(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+}
+
+/// Something that happened in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Key(KeyEvent),
+    /// Only reported after `enable_mouse_capture`.
+    Mouse(MouseEvent),
+    /// The terminal was resized; the viewport has already been updated.
+    Resize(Size),
+}
+
+/// Asks the terminal to report mouse clicks, drags and the scroll wheel as input.
+pub fn enable_mouse_capture() {
+    print("\x1B[?1000h\x1B[?1002h\x1B[?1006h");
+}
+
+/// Stops the terminal from reporting the mouse.
+pub fn disable_mouse_capture() {
+    print("\x1B[?1006l\x1B[?1002l\x1B[?1000l");
+}
+
+/// Decodes raw input bytes into key and mouse events.
+///
+/// A lone `ESC` byte is reported as the Esc key, and `ESC` followed by another key as that
+/// key with Alt held down. Mouse reports are expected in the SGR format switched on by
+/// `enable_mouse_capture`. Unrecognised escape sequences are dropped.
+pub fn parse_events(bytes: &[u8]) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let (event, length) = if bytes[i..].starts_with(b"\x1B[<") {
+            let (mouse, length) = parse_sgr_mouse(&bytes[i..]);
+            (mouse.map(Event::Mouse), length)
+        } else {
+            let (key, length) = parse_key(&bytes[i..]);
+            (key.map(Event::Key), length)
+        };
+        events.extend(event);
+        i += length.max(1);
+    }
+    events
+}
+
+/// Decodes raw input bytes into key events, dropping anything else.
+pub fn parse_keys(bytes: &[u8]) -> Vec<KeyEvent> {
+    parse_events(bytes)
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Key(key) => Some(key),
+            _ => None,
+        })
+        .collect()
+}
+
+// Decodes `ESC [ < button ; x ; y` followed by `M` for a press or `m` for a release.
+fn parse_sgr_mouse(bytes: &[u8]) -> (Option<MouseEvent>, usize) {
+    let Some(offset) = bytes[3..].iter().position(|b| *b == b'M' || *b == b'm') else {
+        return (None, bytes.len());
+    };
+    let end = offset + 3;
+    let params: Vec<u16> = std::str::from_utf8(&bytes[3..end])
+        .unwrap_or("")
+        .split(';')
+        .filter_map(|param| param.parse().ok())
+        .collect();
+    let [code, x, y] = params[..] else {
+        return (None, end + 1);
+    };
+    let button = match code & 3 {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Right),
+        _ => None,
+    };
+    // Bit 6 marks the scroll wheel and bit 5 motion; the low bits are the button.
+    let kind = match (code & 64 != 0, code & 32 != 0, button) {
+        (true, _, _) if code & 1 == 0 => MouseKind::ScrollUp,
+        (true, _, _) => MouseKind::ScrollDown,
+        (false, true, Some(button)) => MouseKind::Drag(button),
+        (false, true, None) => MouseKind::Moved,
+        (false, false, Some(button)) if bytes[end] == b'M' => MouseKind::Press(button),
+        (false, false, Some(button)) => MouseKind::Release(button),
+        (false, false, None) => return (None, end + 1),
+    };
+    let event = MouseEvent { kind, x, y, shift: code & 4 != 0, alt: code & 8 != 0, ctrl: code & 16 != 0 };
+    (Some(event), end + 1)
+}
+
+// Decodes the key at the start of `bytes`, returning it and the number of bytes used.
+fn parse_key(bytes: &[u8]) -> (Option<KeyEvent>, usize) {
+    match bytes[0] {
+        0x1B => match bytes.get(1) {
+            None => (Some(KeyEvent::new(KeyCode::Esc)), 1),
+            Some(b'[') => parse_csi(bytes),
+            Some(b'O') if bytes.len() > 2 => {
+                let code = match bytes[2] {
+                    b'P' => Some(KeyCode::F(1)),
+                    b'Q' => Some(KeyCode::F(2)),
+                    b'R' => Some(KeyCode::F(3)),
+                    b'S' => Some(KeyCode::F(4)),
+                    b'H' => Some(KeyCode::Home),
+                    b'F' => Some(KeyCode::End),
+                    b'A' => Some(KeyCode::Up),
+                    b'B' => Some(KeyCode::Down),
+                    b'C' => Some(KeyCode::Right),
+                    b'D' => Some(KeyCode::Left),
+                    _ => None,
+                };
+                (code.map(KeyEvent::new), 3)
+            }
+            Some(0x1B) => (Some(KeyEvent::new(KeyCode::Esc)), 1),
+            Some(_) => {
+                let (key, length) = parse_key(&bytes[1..]);
+                (key.map(|key| KeyEvent { alt: true, ..key }), length + 1)
+            }
+        },
+        b'\r' | b'\n' => (Some(KeyEvent::new(KeyCode::Enter)), 1),
+        b'\t' => (Some(KeyEvent::new(KeyCode::Tab)), 1),
+        0x7F | 0x08 => (Some(KeyEvent::new(KeyCode::Backspace)), 1),
+        0x00 => (Some(KeyEvent::ctrl(' ')), 1),
+        byte @ 0x01..=0x1A => (Some(KeyEvent::ctrl((b'a' + byte - 1) as char)), 1),
+        byte @ 0x1C..=0x1F => (Some(KeyEvent::ctrl((b'\\' + byte - 0x1C) as char)), 1),
+        byte => {
+            let length = match byte {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => 1,
+            };
+            let length = length.min(bytes.len());
+            let key = std::str::from_utf8(&bytes[..length])
+                .ok()
+                .and_then(|s| s.chars().next())
+                .map(|ch| KeyEvent::new(KeyCode::Char(ch)));
+            (key, length)
+        }
+    }
+}
+
+// Decodes `ESC [ parameters final`.
+fn parse_csi(bytes: &[u8]) -> (Option<KeyEvent>, usize) {
+    let Some(offset) = bytes[2..].iter().position(|b| (0x40..=0x7E).contains(b)) else {
+        return (None, bytes.len());
+    };
+    let end = offset + 2;
+    let params: Vec<u16> = std::str::from_utf8(&bytes[2..end])
+        .unwrap_or("")
+        .split(';')
+        .map(|param| param.parse().unwrap_or(0))
+        .collect();
+    let code = match bytes[end] {
+        b'A' => Some(KeyCode::Up),
+        b'B' => Some(KeyCode::Down),
+        b'C' => Some(KeyCode::Right),
+        b'D' => Some(KeyCode::Left),
+        b'H' => Some(KeyCode::Home),
+        b'F' => Some(KeyCode::End),
+        b'Z' => Some(KeyCode::BackTab),
+        b'P' => Some(KeyCode::F(1)),
+        b'Q' => Some(KeyCode::F(2)),
+        b'R' => Some(KeyCode::F(3)),
+        b'S' => Some(KeyCode::F(4)),
+        b'~' => match params[0] {
+            1 | 7 => Some(KeyCode::Home),
+            2 => Some(KeyCode::Insert),
+            3 => Some(KeyCode::Delete),
+            4 | 8 => Some(KeyCode::End),
+            5 => Some(KeyCode::PageUp),
+            6 => Some(KeyCode::PageDown),
+            11..=15 => Some(KeyCode::F((params[0] - 10) as u8)),
+            17..=21 => Some(KeyCode::F((params[0] - 11) as u8)),
+            23 | 24 => Some(KeyCode::F((params[0] - 12) as u8)),
+            _ => None,
+        },
+        _ => None,
+    };
+    // The second parameter, if present, is 1 plus a bit mask of Shift, Alt and Ctrl.
+    let modifiers = params.get(1).map_or(0, |m| m.saturating_sub(1));
+    let key = code.map(|code| KeyEvent {
+        code,
+        shift: modifiers & 1 != 0,
+        alt: modifiers & 2 != 0,
+        ctrl: modifiers & 4 != 0,
+    });
+    (key, end + 1)
+}
+
+// Events decoded from input but not returned yet.
+static PENDING: Mutex<VecDeque<Event>> = Mutex::new(VecDeque::new());
+
+fn next_pending(read: impl FnOnce() -> Vec<u8>) -> Option<Event> {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if pending.is_empty() {
+        pending.extend(parse_events(&read()));
+    }
+    pending.pop_front()
+}
+
+/// Waits for the next key press, skipping mouse events. Returns `None` at end of input.
+///
+/// Should be used in raw mode; otherwise input only arrives a whole line at a time.
+pub fn read_key_event() -> Option<KeyEvent> {
+    loop {
+        let mut end_of_input = false;
+        let event = next_pending(|| {
+            let input = read_input();
+            end_of_input = input.is_empty();
+            input
+        });
+        match event {
+            Some(Event::Key(key)) => return Some(key),
+            None if end_of_input => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Waits up to `timeout` for input or a change in terminal size.
+///
+/// Returns `None` if neither happened in time. Only waits in raw mode.
+pub fn poll_event(timeout: Duration) -> Option<Event> {
+    poll(timeout).0
+}
+
+// Like `poll_event`, but also reports whether input has ended. A read that times out
+// waits at least a tenth of a second, so one that comes back empty sooner hit the end
+// of input, for example because the terminal hung up.
+fn poll(timeout: Duration) -> (Option<Event>, bool) {
+    if sync_viewport() {
+        return (Some(Event::Resize(crate::get_viewport().into())), false);
+    }
+    let mut end_of_input = false;
+    let event = next_pending(|| {
+        let started = Instant::now();
+        let input = read_input_timeout(timeout);
+        end_of_input = input.is_empty() && started.elapsed() < Duration::from_millis(50);
+        input
+    });
+    (event, end_of_input)
+}
+
+/// Waits for the next input or change in terminal size, checking for resizes every
+/// `resize_interval`. Outside raw mode only key presses are reported. Returns `None` at
+/// end of input.
+pub fn read_event(resize_interval: Duration) -> Option<Event> {
+    if !crate::terminal::is_raw_mode() {
+        return read_key_event().map(Event::Key);
+    }
+    loop {
+        match poll(resize_interval) {
+            (Some(event), _) => return Some(event),
+            (None, true) => return None,
+            (None, false) => {}
+        }
+    }
+}
@@ -0,0 +1,432 @@
+//! This module reads fonts in the FIGlet `.flf` format and uses them to draw large banner
+//! text, such as a title on a welcome screen.
+//!
+//! Characters are joined according to the font's layout: drawn at full width, moved
+//! together until they touch (kerning), or overlapped by one column using the FIGlet
+//! smushing rules. Hardblanks are kept apart while joining and drawn as spaces.
+//!
+//! Three fonts are bundled: FIGlet's own `FigFont::standard()`, and `FigFont::block()` and
+//! `FigFont::mini()`, which are drawn at full width. Any other FIGlet font can be loaded with
+//! `FigFont::load`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::*;
+
+/// Switches on smushing rule 1: two equal characters become one.
+pub const SMUSH_EQUAL: u32 = 1;
+/// Switches on smushing rule 2: an underscore is replaced by `|/\[]{}()<>`.
+pub const SMUSH_LOWLINE: u32 = 2;
+/// Switches on smushing rule 3: the "higher" of `| /\ [] {} () <>` wins.
+pub const SMUSH_HIERARCHY: u32 = 4;
+/// Switches on smushing rule 4: opposite brackets become `|`.
+pub const SMUSH_PAIR: u32 = 8;
+/// Switches on smushing rule 5: `/\` becomes `|`, `\/` becomes `Y` and `><` becomes `X`.
+pub const SMUSH_BIG_X: u32 = 16;
+/// Switches on smushing rule 6: two hardblanks become one.
+pub const SMUSH_HARDBLANK: u32 = 32;
+/// Characters are moved together until they touch.
+pub const LAYOUT_KERNING: u32 = 64;
+/// Characters are overlapped by one column using the smushing rules.
+pub const LAYOUT_SMUSHING: u32 = 128;
+
+/// Errors that can occur while reading a FIGlet font.
+#[derive(Debug)]
+pub enum FigletError {
+    /// The font file could not be read.
+    Io(io::Error),
+    /// The first line is not a valid `flf2a` header.
+    InvalidHeader(String),
+    /// The font ended before all required characters were defined.
+    MissingCharacter(char),
+}
+
+impl fmt::Display for FigletError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FigletError::Io(e) => write!(f, "could not read font: {}", e),
+            FigletError::InvalidHeader(header) => write!(f, "invalid FIGlet header '{}'", header),
+            FigletError::MissingCharacter(ch) => write!(f, "font ends before character '{}'", ch),
+        }
+    }
+}
+
+impl std::error::Error for FigletError {}
+
+/// A FIGlet font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FigFont {
+    hardblank: char,
+    height: usize,
+    baseline: usize,
+    layout: u32,
+    glyphs: HashMap<char, Vec<Vec<char>>>,
+}
+
+const REQUIRED_EXTRA: [char; 7] = ['Ä', 'Ö', 'Ü', 'ä', 'ö', 'ü', 'ß'];
+
+fn parse_code(text: &str) -> Option<u32> {
+    let text = text.trim();
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else if text.len() > 1 && text.starts_with('0') {
+        u32::from_str_radix(&text[1..], 8).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+impl FigFont {
+    /// Parses a font from the text of an `.flf` file.
+    pub fn parse(text: &str) -> Result<FigFont, FigletError> {
+        let mut lines = text.lines();
+        let header = lines.next().unwrap_or("");
+        let invalid = || FigletError::InvalidHeader(header.to_string());
+        let signature = header.split_whitespace().next().ok_or_else(invalid)?;
+        let hardblank = signature.strip_prefix("flf2a").and_then(|rest| rest.chars().next()).ok_or_else(invalid)?;
+        let numbers: Vec<i64> = header
+            .split_whitespace()
+            .skip(1)
+            .map(|n| n.parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        if numbers.len() < 5 || numbers[0] < 1 {
+            return Err(invalid());
+        }
+        let height = numbers[0] as usize;
+        let baseline = numbers[1].max(1) as usize;
+        let old_layout = numbers[3];
+        let comment_lines = numbers[4].max(0) as usize;
+        let layout = match numbers.get(6) {
+            Some(full_layout) => *full_layout as u32,
+            None if old_layout < 0 => 0,
+            None if old_layout == 0 => LAYOUT_KERNING,
+            None => old_layout as u32 & 63 | LAYOUT_SMUSHING,
+        };
+
+        let mut lines = lines.skip(comment_lines);
+        let read_glyph = |lines: &mut dyn Iterator<Item = &str>| -> Option<Vec<Vec<char>>> {
+            let mut rows = Vec::with_capacity(height);
+            for _ in 0..height {
+                let line = lines.next()?.trim_end();
+                let endmark = line.chars().last().unwrap_or('@');
+                rows.push(line.trim_end_matches(endmark).chars().collect());
+            }
+            Some(rows)
+        };
+
+        let mut glyphs = HashMap::new();
+        let required = (32u8..127).map(char::from).chain(REQUIRED_EXTRA);
+        for ch in required {
+            let glyph = read_glyph(&mut lines).ok_or(FigletError::MissingCharacter(ch))?;
+            glyphs.insert(ch, glyph);
+        }
+        while let Some(tag) = lines.next() {
+            let code = tag.split_whitespace().next().and_then(parse_code).and_then(char::from_u32);
+            match (code, read_glyph(&mut lines)) {
+                (Some(ch), Some(glyph)) => {
+                    glyphs.insert(ch, glyph);
+                }
+                (None, Some(_)) => continue,
+                (_, None) => break,
+            }
+        }
+
+        Ok(FigFont { hardblank, height, baseline, layout, glyphs })
+    }
+
+    /// Loads a font from an `.flf` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<FigFont, FigletError> {
+        let text = fs::read_to_string(path).map_err(FigletError::Io)?;
+        FigFont::parse(&text)
+    }
+
+    /// The bundled `standard.flf` from FIGlet, six lines tall, which smushes characters
+    /// together with rules 1 to 4.
+    pub fn standard() -> FigFont {
+        FigFont::parse(include_str!("fonts/standard.flf")).unwrap()
+    }
+
+    /// A bundled font drawn with full blocks, five lines tall.
+    pub fn block() -> FigFont {
+        FigFont::parse(include_str!("fonts/block.flf")).unwrap()
+    }
+
+    /// A bundled font drawn with half blocks, three lines tall.
+    pub fn mini() -> FigFont {
+        FigFont::parse(include_str!("fonts/mini.flf")).unwrap()
+    }
+
+    /// Returns the number of lines in every rendered banner.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the line, counted from 1, on which the bottom of letters without
+    /// descenders sits.
+    pub fn baseline(&self) -> usize {
+        self.baseline
+    }
+
+    /// Returns the layout flags (`LAYOUT_*` and `SMUSH_*` constants) the font uses.
+    pub fn layout(&self) -> u32 {
+        self.layout
+    }
+
+    /// Returns a copy of the font that uses the given layout flags.
+    pub fn with_layout(mut self, layout: u32) -> FigFont {
+        self.layout = layout;
+        self
+    }
+
+    // Returns the character that `left` and `right` smush into, if they can be smushed.
+    fn smush(&self, left: char, right: char, left_width: usize, right_width: usize) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        if left_width < 2 || right_width < 2 || self.layout & LAYOUT_SMUSHING == 0 {
+            return None;
+        }
+        let hardblank = self.hardblank;
+        if self.layout & 63 == 0 {
+            // Universal smushing: the right character wins, except over a hardblank.
+            return Some(if right == hardblank { left } else { right });
+        }
+        if left == hardblank && right == hardblank {
+            return (self.layout & SMUSH_HARDBLANK != 0).then_some(left);
+        }
+        if left == hardblank || right == hardblank {
+            return None;
+        }
+        if self.layout & SMUSH_EQUAL != 0 && left == right {
+            return Some(left);
+        }
+        if self.layout & SMUSH_LOWLINE != 0 {
+            const REPLACERS: &str = "|/\\[]{}()<>";
+            if left == '_' && REPLACERS.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && REPLACERS.contains(left) {
+                return Some(left);
+            }
+        }
+        if self.layout & SMUSH_HIERARCHY != 0 {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |c: char| CLASSES.iter().position(|class| class.contains(c));
+            if let (Some(l), Some(r)) = (class(left), class(right)) {
+                if l != r {
+                    return Some(if l > r { left } else { right });
+                }
+            }
+        }
+        if self.layout & SMUSH_PAIR != 0 {
+            if let "[]" | "][" | "{}" | "}{" | "()" | ")(" = format!("{}{}", left, right).as_str() {
+                return Some('|');
+            }
+        }
+        if self.layout & SMUSH_BIG_X != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Works out by how many columns the next glyph can overlap the output so far.
+    fn overlap(&self, output: &[Vec<char>], glyph: &[Vec<char>], previous_width: usize) -> usize {
+        if self.layout & (LAYOUT_KERNING | LAYOUT_SMUSHING) == 0 {
+            return 0;
+        }
+        let glyph_width = glyph.iter().map(Vec::len).max().unwrap_or(0);
+        let mut amount = glyph_width;
+        for (line, row) in output.iter().zip(glyph) {
+            let trailing = line.iter().rev().take_while(|c| **c == ' ').count();
+            let leading = row.iter().take_while(|c| **c == ' ').count();
+            let mut row_amount = trailing + leading;
+            if trailing < line.len() && leading < row.len() {
+                let left = line[line.len() - 1 - trailing];
+                let right = row[leading];
+                if self.smush(left, right, previous_width, glyph_width).is_some() {
+                    row_amount += 1;
+                }
+            } else if trailing == line.len() {
+                row_amount = line.len() + leading;
+            }
+            amount = amount.min(row_amount);
+        }
+        amount.min(output.first().map_or(0, Vec::len))
+    }
+
+    /// Renders text as banner lines. Characters missing from the font are skipped and
+    /// line breaks in the text are ignored.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        let mut output: Vec<Vec<char>> = vec![Vec::new(); self.height];
+        let mut previous_width = 0;
+        for ch in text.chars() {
+            let Some(glyph) = self.glyphs.get(&ch) else {
+                continue;
+            };
+            let glyph_width = glyph.iter().map(Vec::len).max().unwrap_or(0);
+            let overlap = self.overlap(&output, glyph, previous_width);
+            for (line, row) in output.iter_mut().zip(glyph) {
+                let start = line.len() - overlap;
+                for (k, right) in row.iter().enumerate() {
+                    let position = start + k;
+                    if position < line.len() {
+                        let left = line[position];
+                        line[position] = self.smush(left, *right, previous_width, glyph_width).unwrap_or(*right);
+                    } else {
+                        line.push(*right);
+                    }
+                }
+                // Pad short rows so every line keeps the same width.
+                while line.len() < start + glyph_width {
+                    line.push(' ');
+                }
+            }
+            previous_width = glyph_width;
+        }
+        output
+            .into_iter()
+            .map(|line| line.into_iter().map(|c| if c == self.hardblank { ' ' } else { c }).collect())
+            .collect()
+    }
+}
+
+/// Draws banner text with its top-left corner at the given position, using the current
+/// style.
+pub fn print_banner(text: &str, x: u16, y: u16, font: &FigFont) {
+    for (i, line) in font.render(text).iter().enumerate() {
+        move_cursor_to(x, y + i as u16);
+        print(line);
+    }
+}
+
+impl ScreenBuffer {
+    /// Draws banner text into the buffer with its top-left corner at the given position.
+    pub fn draw_banner(&mut self, x: u16, y: u16, text: &str, font: &FigFont, style: Style) {
+        for (i, line) in font.render(text).iter().enumerate() {
+            self.put_str(x, y + i as u16, line, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Joins a one-line glyph for 'l' with one for 'r' under the given layout.
+    fn join(layout: u32, left: &str, right: &str) -> String {
+        let glyphs = [('l', left), ('r', right)].map(|(ch, row)| (ch, vec![row.chars().collect()]));
+        let font = FigFont { hardblank: '$', height: 1, baseline: 1, layout, glyphs: glyphs.into_iter().collect() };
+        font.render("lr").remove(0)
+    }
+
+    #[test]
+    fn standard_font_smushes_with_rules_one_to_four() {
+        let font = FigFont::standard();
+        assert_eq!(font.height(), 6);
+        assert_eq!(font.baseline(), 5);
+        let rules = SMUSH_EQUAL | SMUSH_LOWLINE | SMUSH_HIERARCHY | SMUSH_PAIR;
+        assert_eq!(font.layout() & 255, LAYOUT_SMUSHING | rules);
+    }
+
+    #[test]
+    fn standard_font_renders_hello() {
+        let expected = [
+            "  _   _      _ _       ",
+            " | | | | ___| | | ___  ",
+            " | |_| |/ _ \\ | |/ _ \\ ",
+            " |  _  |  __/ | | (_) |",
+            " |_| |_|\\___|_|_|\\___/ ",
+            "                       ",
+        ];
+        assert_eq!(FigFont::standard().render("Hello"), expected);
+    }
+
+    #[test]
+    fn standard_font_draws_hardblanks_as_spaces() {
+        let lines = FigFont::standard().render("a - b");
+        assert!(lines.iter().all(|line| !line.contains('$')));
+        assert_eq!(lines[3], " | (_| | |_____| | |_) |");
+    }
+
+    #[test]
+    fn full_width_keeps_every_column() {
+        assert_eq!(join(0, "a| ", " |b"), "a|  |b");
+    }
+
+    #[test]
+    fn kerning_moves_characters_until_they_touch() {
+        assert_eq!(join(LAYOUT_KERNING, "a| ", " |b"), "a||b");
+    }
+
+    #[test]
+    fn equal_rule_merges_equal_characters() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_EQUAL, "a|", "|b"), "a|b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_LOWLINE, "a|", "|b"), "a||b");
+    }
+
+    #[test]
+    fn lowline_rule_replaces_underscores() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_LOWLINE, "a_", "/b"), "a/b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_LOWLINE, "a[", "_b"), "a[b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_LOWLINE, "a_", "xb"), "a_xb");
+    }
+
+    #[test]
+    fn hierarchy_rule_keeps_the_higher_class() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_HIERARCHY, "a|", "/b"), "a/b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_HIERARCHY, "a>", "{b"), "a>b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_HIERARCHY, "a[", "]b"), "a[]b");
+    }
+
+    #[test]
+    fn pair_rule_turns_opposite_brackets_into_a_bar() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_PAIR, "a[", "]b"), "a|b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_PAIR, "a)", "(b"), "a|b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_PAIR, "a<", ">b"), "a<>b");
+    }
+
+    #[test]
+    fn big_x_rule_joins_slashes_and_angles() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_BIG_X, "a/", "\\b"), "a|b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_BIG_X, "a\\", "/b"), "aYb");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_BIG_X, "a>", "<b"), "aXb");
+    }
+
+    #[test]
+    fn hardblank_rule_merges_two_hardblanks() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_HARDBLANK, "a$", "$b"), "a b");
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_EQUAL, "a$", "$b"), "a  b");
+    }
+
+    #[test]
+    fn hardblanks_do_not_smush_with_other_characters() {
+        let all_rules = LAYOUT_SMUSHING | 63;
+        assert_eq!(join(all_rules, "a$", "|b"), "a |b");
+        assert_eq!(join(all_rules, "a|", "$b"), "a| b");
+    }
+
+    #[test]
+    fn universal_smushing_lets_the_right_character_win() {
+        assert_eq!(join(LAYOUT_SMUSHING, "a|", "/b"), "a/b");
+        assert_eq!(join(LAYOUT_SMUSHING, "a|", "$b"), "a|b");
+    }
+
+    #[test]
+    fn narrow_glyphs_are_not_smushed() {
+        assert_eq!(join(LAYOUT_SMUSHING | SMUSH_EQUAL, "|", "|"), "||");
+    }
+}
@@ -0,0 +1,712 @@
+//! This module provides forms: a framed group of labeled fields that the user fills in
+//! and submits.
+//!
+//! Fields are text, number, password, select, checkbox and date inputs. Tab, Shift-Tab
+//! and the Up/Down arrows move between fields and the Submit and Cancel buttons. Each
+//! field can have validators that run when the field is left and on submit, and the form
+//! can have validators that look at all values together. Errors are shown under the
+//! field, or above the buttons for whole-form errors.
+//!
+//! ```no_run
+//! use rpian_terminal::form::{Field, Form};
+//!
+//! let form = Form::new("Register")
+//!     .field(Field::text("name", "Name").required())
+//!     .field(Field::number("age", "Age", 0.0, 130.0))
+//!     .field(Field::checkbox("terms", "Accept terms", false))
+//!     .validate(|values| match values.bool("terms") {
+//!         Some(true) => Ok(()),
+//!         _ => Err(String::from("Please accept the terms")),
+//!     });
+//! if let Some(values) = form.run(5, 3, 50) {
+//!     println!("Hello {}", values.text("name").unwrap_or_default());
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::event::{read_key_event, Event, KeyCode, KeyEvent};
+use crate::geometry::{Rect, Size};
+use crate::input::TextInput;
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::theme::{role_style, Role};
+use crate::triangle::{triangle_symbol_to_char, TriangleSymbol};
+use crate::widget::{EventResult, Widget};
+use crate::width::str_width;
+use crate::*;
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Creates a date, returning `None` if the month or day does not exist.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        let date = Date { year, month, day };
+        ((1..=12).contains(&month) && day >= 1 && day <= date.days_in_month()).then_some(date)
+    }
+
+    /// Parses a date written as `YYYY-MM-DD`.
+    pub fn parse(text: &str) -> Option<Date> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Date::new(year, month, day)
+    }
+
+    pub fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    /// Returns the number of days in the date's month.
+    pub fn days_in_month(&self) -> u8 {
+        match self.month {
+            2 if Self::is_leap_year(self.year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The value of a field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// Text and password fields.
+    Text(String),
+    /// Number fields; `None` if left empty.
+    Number(Option<f64>),
+    /// Select fields: the index and text of the chosen option.
+    Selected(usize, String),
+    /// Checkbox fields.
+    Bool(bool),
+    /// Date fields; `None` if left empty.
+    Date(Option<Date>),
+}
+
+impl FieldValue {
+    /// Returns true for an empty text, number or date.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            FieldValue::Text(text) => text.is_empty(),
+            FieldValue::Number(number) => number.is_none(),
+            FieldValue::Date(date) => date.is_none(),
+            FieldValue::Selected(..) | FieldValue::Bool(_) => false,
+        }
+    }
+}
+
+/// Checks a field value, returning an error message if it is not acceptable.
+pub type FieldValidator = Box<dyn Fn(&FieldValue) -> Result<(), String>>;
+
+/// Checks all values of a form together.
+pub type FormValidator = Box<dyn Fn(&FormResult) -> Result<(), String>>;
+
+enum FieldKind {
+    Text(TextInput),
+    Number { input: TextInput, min: f64, max: f64 },
+    Select { options: Vec<String>, selected: usize },
+    Checkbox(bool),
+    Date(TextInput),
+}
+
+/// A labeled input in a form.
+pub struct Field {
+    name: String,
+    label: String,
+    kind: FieldKind,
+    required: bool,
+    validators: Vec<FieldValidator>,
+    error: Option<String>,
+}
+
+impl Field {
+    fn new(name: &str, label: &str, kind: FieldKind) -> Self {
+        Field { name: name.to_string(), label: label.to_string(), kind, required: false, validators: Vec::new(), error: None }
+    }
+
+    /// Creates a single-line text field.
+    pub fn text(name: &str, label: &str) -> Self {
+        Self::new(name, label, FieldKind::Text(TextInput::new()))
+    }
+
+    /// Creates a field for a number between `min` and `max`.
+    pub fn number(name: &str, label: &str, min: f64, max: f64) -> Self {
+        let input = TextInput::new().filter(|ch| ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.'));
+        Self::new(name, label, FieldKind::Number { input, min, max })
+    }
+
+    /// Creates a text field that shows `*` for every character.
+    pub fn password(name: &str, label: &str) -> Self {
+        Self::new(name, label, FieldKind::Text(TextInput::new().mask('*')))
+    }
+
+    /// Creates a field choosing one of the options with the Left/Right arrows or Space.
+    pub fn select(name: &str, label: &str, options: &[&str]) -> Self {
+        let options = options.iter().map(|option| option.to_string()).collect();
+        Self::new(name, label, FieldKind::Select { options, selected: 0 })
+    }
+
+    /// Creates a checkbox toggled with Space.
+    pub fn checkbox(name: &str, label: &str, checked: bool) -> Self {
+        Self::new(name, label, FieldKind::Checkbox(checked))
+    }
+
+    /// Creates a field for a date typed as `YYYY-MM-DD`.
+    pub fn date(name: &str, label: &str) -> Self {
+        let input = TextInput::new()
+            .placeholder("YYYY-MM-DD")
+            .max_length(10)
+            .filter(|ch| ch.is_ascii_digit() || ch == '-');
+        Self::new(name, label, FieldKind::Date(input))
+    }
+
+    /// Makes the field reject an empty value.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Sets the initial value of a text, number or date field, or the initially
+    /// selected option of a select field.
+    pub fn value(mut self, value: &str) -> Self {
+        match &mut self.kind {
+            FieldKind::Text(input) | FieldKind::Number { input, .. } | FieldKind::Date(input) => input.set_value(value),
+            FieldKind::Select { options, selected } => {
+                *selected = options.iter().position(|option| option == value).unwrap_or(*selected)
+            }
+            FieldKind::Checkbox(checked) => *checked = matches!(value, "true" | "yes" | "1"),
+        }
+        self
+    }
+
+    /// Sets the text shown in an empty text, number or password field.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        if let FieldKind::Text(input) | FieldKind::Number { input, .. } | FieldKind::Date(input) = &mut self.kind {
+            input.placeholder = placeholder.to_string();
+        }
+        self
+    }
+
+    /// Limits the number of characters in a text or password field.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        if let FieldKind::Text(input) = &mut self.kind {
+            input.max_length = Some(max_length);
+        }
+        self
+    }
+
+    /// Adds a validator, run when the field is left and when the form is submitted.
+    pub fn validate(mut self, validator: impl Fn(&FieldValue) -> Result<(), String> + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Returns the field's name, used to look up its value in a `FormResult`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the error from the last validation, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the current value.
+    pub fn value_of(&self) -> FieldValue {
+        match &self.kind {
+            FieldKind::Text(input) => FieldValue::Text(input.text().to_string()),
+            FieldKind::Number { input, .. } => FieldValue::Number(input.text().trim().parse().ok()),
+            FieldKind::Select { options, selected } => {
+                FieldValue::Selected(*selected, options.get(*selected).cloned().unwrap_or_default())
+            }
+            FieldKind::Checkbox(checked) => FieldValue::Bool(*checked),
+            FieldKind::Date(input) => FieldValue::Date(Date::parse(input.text())),
+        }
+    }
+
+    // Runs the built-in checks and the validators, storing and returning the result.
+    fn check(&mut self) -> bool {
+        let value = self.value_of();
+        let text = match &self.kind {
+            FieldKind::Text(input) | FieldKind::Number { input, .. } | FieldKind::Date(input) => input.text().trim(),
+            _ => "",
+        };
+        let result = if self.required && value.is_empty() {
+            Err(String::from("This field is required"))
+        } else {
+            match (&self.kind, &value) {
+                (FieldKind::Number { .. }, FieldValue::Number(None)) if !text.is_empty() => {
+                    Err(String::from("Enter a number"))
+                }
+                (FieldKind::Number { min, max, .. }, FieldValue::Number(Some(number)))
+                    if number < min || number > max =>
+                {
+                    Err(format!("Enter a number from {} to {}", min, max))
+                }
+                (FieldKind::Date(_), FieldValue::Date(None)) if !text.is_empty() => {
+                    Err(String::from("Enter a valid date as YYYY-MM-DD"))
+                }
+                _ => self.validators.iter().try_for_each(|validator| validator(&value)),
+            }
+        };
+        self.error = result.err();
+        self.error.is_none()
+    }
+
+    // Applies a key to the field's input. Returns true if the key was used.
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        match &mut self.kind {
+            FieldKind::Text(input) | FieldKind::Number { input, .. } | FieldKind::Date(input) => {
+                !matches!(key.code, KeyCode::Enter | KeyCode::Esc) && input.handle_key(key)
+            }
+            FieldKind::Select { options, selected } => {
+                let count = options.len().max(1);
+                match key.code {
+                    KeyCode::Left => *selected = (*selected + count - 1) % count,
+                    KeyCode::Right | KeyCode::Char(' ') => *selected = (*selected + 1) % count,
+                    _ => return false,
+                }
+                true
+            }
+            FieldKind::Checkbox(checked) => match key.code {
+                KeyCode::Char(' ') => {
+                    *checked = !*checked;
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    // Draws the input part of the field into the first row of `area`.
+    fn draw_input(&self, area: Rect, buffer: &mut ScreenBuffer, focused: bool) {
+        let accent = if focused { role_style(Role::Accent) } else { Style::default() };
+        match &self.kind {
+            FieldKind::Text(input) | FieldKind::Number { input, .. } | FieldKind::Date(input) => {
+                let mut field = ScreenBuffer::new(area.width, 1);
+                input.draw(field.area(), &mut field, focused);
+                for x in 1..=area.width {
+                    if let Some(cell) = field.get(x, 1) {
+                        if !cell.is_continuation() {
+                            let style = cell.style.patch(Style::from(Attribute::Underscore));
+                            buffer.set_grapheme(area.x + x - 1, area.y, &cell.symbol, style);
+                        }
+                    }
+                }
+                if let Some(cursor) = field.cursor() {
+                    buffer.set_cursor(Some(Point::new(area.x + cursor.x - 1, area.y)));
+                }
+            }
+            FieldKind::Select { options, selected } => {
+                let left = triangle_symbol_to_char(TriangleSymbol::SmallBlackLeftPointing);
+                let right = triangle_symbol_to_char(TriangleSymbol::SmallBlackRightPointing);
+                let option = options.get(*selected).map_or("", String::as_str);
+                let text = format!("{} {} {}", left, option, right);
+                buffer.put_str(area.x, area.y, &crate::ansi::truncate(&text, area.width as usize), accent);
+            }
+            FieldKind::Checkbox(checked) => {
+                buffer.put_str(area.x, area.y, if *checked { "[x]" } else { "[ ]" }, accent);
+            }
+        }
+    }
+}
+
+/// The submitted values of a form, looked up by field name.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FormResult {
+    pub values: Vec<(String, FieldValue)>,
+}
+
+impl FormResult {
+    /// Returns the value of the named field.
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.values.iter().find(|(field, _)| field == name).map(|(_, value)| value)
+    }
+
+    /// Returns the text of a text or password field, or the chosen option of a select.
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            FieldValue::Text(text) | FieldValue::Selected(_, text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self, name: &str) -> Option<f64> {
+        match self.get(name)? {
+            FieldValue::Number(number) => *number,
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the chosen option of a select field.
+    pub fn selected(&self, name: &str) -> Option<usize> {
+        match self.get(name)? {
+            FieldValue::Selected(index, _) => Some(*index),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            FieldValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn date(&self, name: &str) -> Option<Date> {
+        match self.get(name)? {
+            FieldValue::Date(date) => *date,
+            _ => None,
+        }
+    }
+}
+
+/// A framed group of fields with Submit and Cancel buttons.
+pub struct Form {
+    pub title: String,
+    pub box_style: BoxStyle,
+    fields: Vec<Field>,
+    validators: Vec<FormValidator>,
+    error: Option<String>,
+    // Fields first, then Submit, then Cancel.
+    focus: usize,
+    focused: bool,
+    submitted: bool,
+    cancelled: bool,
+}
+
+impl Form {
+    pub fn new(title: &str) -> Self {
+        Form {
+            title: title.to_string(),
+            box_style: BoxStyle::SingleRounded,
+            fields: Vec::new(),
+            validators: Vec::new(),
+            error: None,
+            focus: 0,
+            focused: true,
+            submitted: false,
+            cancelled: false,
+        }
+    }
+
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn box_style(mut self, box_style: BoxStyle) -> Self {
+        self.box_style = box_style;
+        self
+    }
+
+    /// Adds a validator that checks all values together on submit.
+    pub fn validate(mut self, validator: impl Fn(&FormResult) -> Result<(), String> + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Returns the current values of all fields.
+    pub fn values(&self) -> FormResult {
+        FormResult { values: self.fields.iter().map(|field| (field.name.clone(), field.value_of())).collect() }
+    }
+
+    /// Returns true once the form has been submitted with valid values.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Returns true once the form has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    fn submit_index(&self) -> usize {
+        self.fields.len()
+    }
+
+    fn cancel_index(&self) -> usize {
+        self.fields.len() + 1
+    }
+
+    fn label_width(&self) -> u16 {
+        self.fields.iter().map(|field| str_width(&field.label)).max().unwrap_or(0) as u16
+    }
+
+    // Moves focus, validating the field being left.
+    fn move_focus(&mut self, forward: bool) {
+        if let Some(field) = self.fields.get_mut(self.focus) {
+            field.check();
+        }
+        let count = self.fields.len() + 2;
+        self.focus = if forward { (self.focus + 1) % count } else { (self.focus + count - 1) % count };
+    }
+
+    /// Validates every field and the whole form. Returns the values if all are valid,
+    /// and otherwise focuses the first field with an error.
+    pub fn submit(&mut self) -> Option<FormResult> {
+        let mut first_error = None;
+        for (i, field) in self.fields.iter_mut().enumerate() {
+            if !field.check() && first_error.is_none() {
+                first_error = Some(i);
+            }
+        }
+        if let Some(i) = first_error {
+            self.focus = i;
+            self.error = None;
+            return None;
+        }
+        let values = self.values();
+        self.error = self.validators.iter().try_for_each(|validator| validator(&values)).err();
+        if self.error.is_some() {
+            return None;
+        }
+        self.submitted = true;
+        Some(values)
+    }
+
+    /// Applies a key press. Returns true if the key was used.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key.is_ctrl('c') || key.code == KeyCode::Esc {
+            self.cancelled = true;
+            return true;
+        }
+        if key.is_ctrl('s') {
+            self.submit();
+            return true;
+        }
+        if let Some(field) = self.fields.get_mut(self.focus) {
+            if field.handle_key(key) {
+                if field.error.is_some() {
+                    field.check();
+                }
+                return true;
+            }
+        }
+        match key.code {
+            KeyCode::Tab if !key.shift => self.move_focus(true),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up => self.move_focus(false),
+            KeyCode::Down => self.move_focus(true),
+            KeyCode::Left | KeyCode::Right if self.focus >= self.submit_index() => {
+                self.focus = if self.focus == self.submit_index() { self.cancel_index() } else { self.submit_index() }
+            }
+            KeyCode::Enter if self.focus == self.cancel_index() => self.cancelled = true,
+            KeyCode::Enter if self.focus == self.submit_index() => {
+                self.submit();
+            }
+            KeyCode::Enter => self.move_focus(true),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Shows the form in a box at the given position and lets the user fill it in.
+    ///
+    /// Returns the values once submitted, or `None` if cancelled. If standard input is not
+    /// a terminal, each field is asked for on its own line instead.
+    pub fn run(mut self, x: u16, y: u16, width: u16) -> Option<FormResult> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            return self.run_lines();
+        }
+
+        let height = self.preferred_size(Size::new(width, u16::MAX)).height;
+        let mut previous: Option<ScreenBuffer> = None;
+        let result = loop {
+            let mut buffer = ScreenBuffer::new(width, height);
+            self.render(buffer.area(), &mut buffer);
+            match &previous {
+                Some(previous) => buffer.render_diff_at(previous, x, y),
+                None => buffer.render_at(x, y),
+            }
+            match buffer.cursor() {
+                Some(cursor) => {
+                    move_cursor_to(x + cursor.x - 1, y + cursor.y - 1);
+                    show_cursor();
+                }
+                None => hide_cursor(),
+            }
+            previous = Some(buffer);
+
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            self.handle_key(&key);
+            if self.submitted {
+                break Some(self.values());
+            }
+            if self.cancelled {
+                break None;
+            }
+        };
+
+        show_cursor();
+        if !was_raw {
+            disable_raw_mode();
+        }
+        result
+    }
+
+    // Asks for each field on its own line, for input that is not a terminal.
+    fn run_lines(mut self) -> Option<FormResult> {
+        println(&self.title);
+        loop {
+            for field in &mut self.fields {
+                loop {
+                    print(&format!("{}: ", field.label));
+                    let answer = read_line_or_none()?;
+                    let answer = answer.trim();
+                    match &mut field.kind {
+                        FieldKind::Text(input) | FieldKind::Number { input, .. } | FieldKind::Date(input) => {
+                            input.set_value(answer)
+                        }
+                        FieldKind::Select { options, selected } => {
+                            if let Some(i) = options.iter().position(|option| option.eq_ignore_ascii_case(answer)) {
+                                *selected = i;
+                            }
+                        }
+                        FieldKind::Checkbox(checked) => {
+                            *checked = matches!(answer.to_lowercase().as_str(), "y" | "yes" | "true" | "1" | "x")
+                        }
+                    }
+                    if field.check() {
+                        break;
+                    }
+                    println(field.error().unwrap_or_default());
+                }
+            }
+            if let Some(values) = self.submit() {
+                return Some(values);
+            }
+            println(self.error.as_deref().unwrap_or_default());
+        }
+    }
+}
+
+// Reads a line from standard input, returning `None` at end of input.
+fn read_line_or_none() -> Option<String> {
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(line),
+        Err(e) => {
+            handle_io_error(e);
+            None
+        }
+    }
+}
+
+impl Widget for Form {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        let border = role_style(if self.focused { Role::Accent } else { Role::Border });
+        buffer.draw_box(area, self.box_style, border);
+        if !self.title.is_empty() && area.width > 6 {
+            let title = crate::ansi::truncate(&format!(" {} ", self.title), area.width as usize - 4);
+            buffer.put_str(area.x + 2, area.y, &title, role_style(Role::Primary));
+        }
+
+        let inner = area.inset(2, 1);
+        let label_width = self.label_width().min(inner.width / 2);
+        let input_x = inner.x + label_width + 2;
+        let input_width = inner.right().saturating_sub(input_x);
+        let mut y = inner.y + 1;
+        for (i, field) in self.fields.iter().enumerate() {
+            if y >= inner.bottom() {
+                return;
+            }
+            let focused = self.focused && self.focus == i;
+            let label_style = if focused { role_style(Role::Accent) } else { Style::default() };
+            let label = crate::ansi::truncate(&field.label, label_width as usize);
+            buffer.put_str(inner.x, y, &label, label_style);
+            if input_width > 0 {
+                field.draw_input(Rect::new(input_x, y, input_width, 1), buffer, focused);
+            }
+            if let Some(error) = &field.error {
+                let error = crate::ansi::truncate(error, input_width as usize);
+                buffer.put_str(input_x, y + 1, &error, role_style(Role::Error));
+            }
+            y += 2;
+        }
+
+        if let Some(error) = &self.error {
+            let error = crate::ansi::truncate(error, inner.width as usize);
+            buffer.put_str(inner.x, y, &error, role_style(Role::Error));
+        }
+        y += 1;
+        let buttons = [(self.submit_index(), "[ Submit ]"), (self.cancel_index(), "[ Cancel ]")];
+        let mut x = inner.right().saturating_sub(22);
+        for (index, label) in buttons {
+            let style = if self.focused && self.focus == index { role_style(Role::Selection) } else { Style::default() };
+            x = buffer.put_str(x, y, label, style) + 2;
+        }
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let input_width = self
+            .fields
+            .iter()
+            .map(|field| match &field.kind {
+                FieldKind::Select { options, .. } => options.iter().map(|o| str_width(o)).max().unwrap_or(0) + 4,
+                // Room for the input and for typical error messages below it.
+                _ => 26,
+            })
+            .max()
+            .unwrap_or(0) as u16;
+        let width = (self.label_width() + 2 + input_width + 4).max(26).max(str_width(&self.title) as u16 + 8);
+        // Border, blank row, two rows per field, the form error row and the buttons.
+        let height = 2 + 1 + 2 * self.fields.len() as u16 + 2;
+        Size::new(width.min(available.width), height.min(available.height))
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        // Tab leaves the form from its last and first items, so it can sit in a `Stack`.
+        let count = self.fields.len() + 2;
+        let leaving = match key.code {
+            KeyCode::Tab if !key.shift => self.focus + 1 == count,
+            KeyCode::Tab | KeyCode::BackTab => self.focus == 0,
+            _ => false,
+        };
+        if leaving || key.is_ctrl('c') {
+            return EventResult::Ignored;
+        }
+        if self.handle_key(key) {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn focus_from(&mut self, forward: bool) {
+        self.focused = true;
+        self.focus = if forward { 0 } else { self.fields.len() + 1 };
+    }
+}
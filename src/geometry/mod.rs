@@ -0,0 +1,297 @@
+//! This module provides typed geometry for positioning things on the terminal.
+//!
+//! Terminal coordinates are 1-based: the top-left cell is `(1, 1)`, the same as
+//! `move_cursor_to`. Code that works with 0-based indices (rows of a `Vec`, for example)
+//! should convert explicitly with `Point::from_zero_based` and `Point::to_zero_based`.
+
+use std::ops::{Add, Sub};
+
+/// A 1-based terminal position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: u16,
+    pub y: u16,
+}
+
+/// A signed distance between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Offset {
+    pub dx: i32,
+    pub dy: i32,
+}
+
+/// A width and height in cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+/// A rectangle of cells whose top-left corner is at the 1-based position `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Point {
+    /// The top-left cell of the terminal.
+    pub const ORIGIN: Point = Point { x: 1, y: 1 };
+
+    pub fn new(x: u16, y: u16) -> Self {
+        Point { x, y }
+    }
+
+    /// Converts a 0-based column and row into a terminal position.
+    pub fn from_zero_based(column: u16, row: u16) -> Self {
+        Point { x: column.saturating_add(1), y: row.saturating_add(1) }
+    }
+
+    /// Returns the 0-based column and row of this position.
+    pub fn to_zero_based(self) -> (u16, u16) {
+        (self.x.saturating_sub(1), self.y.saturating_sub(1))
+    }
+
+    /// Moves the point by a signed offset, returning `None` if it would leave the
+    /// 1-based coordinate range.
+    pub fn offset(self, dx: i32, dy: i32) -> Option<Point> {
+        let x = self.x as i32 + dx;
+        let y = self.y as i32 + dy;
+        if x < 1 || y < 1 || x > u16::MAX as i32 || y > u16::MAX as i32 {
+            return None;
+        }
+        Some(Point { x: x as u16, y: y as u16 })
+    }
+
+    /// Moves the point by a signed offset, clamping it to the 1-based coordinate range.
+    pub fn saturating_offset(self, dx: i32, dy: i32) -> Point {
+        let clamp = |value: i32| value.clamp(1, u16::MAX as i32) as u16;
+        Point { x: clamp(self.x as i32 + dx), y: clamp(self.y as i32 + dy) }
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Point::ORIGIN
+    }
+}
+
+impl From<(u16, u16)> for Point {
+    fn from((x, y): (u16, u16)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add<Offset> for Point {
+    type Output = Point;
+
+    /// Moves the point by an offset, clamping it to the 1-based coordinate range.
+    fn add(self, offset: Offset) -> Point {
+        self.saturating_offset(offset.dx, offset.dy)
+    }
+}
+
+impl Sub for Point {
+    type Output = Offset;
+
+    fn sub(self, other: Point) -> Offset {
+        Offset { dx: self.x as i32 - other.x as i32, dy: self.y as i32 - other.y as i32 }
+    }
+}
+
+impl Offset {
+    pub fn new(dx: i32, dy: i32) -> Self {
+        Offset { dx, dy }
+    }
+}
+
+impl Size {
+    pub fn new(width: u16, height: u16) -> Self {
+        Size { width, height }
+    }
+
+    /// Returns the number of cells.
+    pub fn area(&self) -> u32 {
+        self.width as u32 * self.height as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+impl From<(u16, u16)> for Size {
+    fn from((width, height): (u16, u16)) -> Self {
+        Size { width, height }
+    }
+}
+
+impl Rect {
+    pub fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Rect { x, y, width, height }
+    }
+
+    /// Creates a rectangle from its top-left corner and size.
+    pub fn from_point_size(position: Point, size: Size) -> Self {
+        Rect { x: position.x, y: position.y, width: size.width, height: size.height }
+    }
+
+    /// Returns a rectangle covering the whole viewport.
+    pub fn viewport() -> Self {
+        let (width, height) = crate::get_viewport();
+        Rect { x: 1, y: 1, width, height }
+    }
+
+    /// Returns the top-left corner.
+    pub fn position(&self) -> Point {
+        Point { x: self.x, y: self.y }
+    }
+
+    pub fn size(&self) -> Size {
+        Size { width: self.width, height: self.height }
+    }
+
+    /// Returns the first column inside the rectangle.
+    pub fn left(&self) -> u16 {
+        self.x
+    }
+
+    /// Returns the first row inside the rectangle.
+    pub fn top(&self) -> u16 {
+        self.y
+    }
+
+    /// Returns the column just past the right edge.
+    pub fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Returns the row just past the bottom edge.
+    pub fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Returns the number of cells.
+    pub fn area(&self) -> u32 {
+        self.size().area()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size().is_empty()
+    }
+
+    /// Returns the cell in the middle of the rectangle.
+    pub fn center(&self) -> Point {
+        Point { x: self.x + self.width / 2, y: self.y + self.height / 2 }
+    }
+
+    /// Returns true if the point is inside the rectangle.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.left() && point.x < self.right() && point.y >= self.top() && point.y < self.bottom()
+    }
+
+    /// Returns true if the other rectangle lies entirely inside this one.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.top() >= self.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Returns the overlapping part of two rectangles, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        if left >= right || top >= bottom {
+            return None;
+        }
+        Some(Rect { x: left, y: top, width: right - left, height: bottom - top })
+    }
+
+    /// Returns true if the rectangles share at least one cell.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the smallest rectangle containing both rectangles. Empty rectangles are
+    /// ignored.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        let left = self.left().min(other.left());
+        let top = self.top().min(other.top());
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Rect { x: left, y: top, width: right - left, height: bottom - top }
+    }
+
+    /// Shrinks the rectangle by `horizontal` columns on the left and right and by
+    /// `vertical` rows at the top and bottom.
+    pub fn inset(&self, horizontal: u16, vertical: u16) -> Rect {
+        self.inset_sides(horizontal, vertical, horizontal, vertical)
+    }
+
+    /// Shrinks the rectangle by a different amount on each side. The result is never
+    /// smaller than zero cells.
+    pub fn inset_sides(&self, left: u16, top: u16, right: u16, bottom: u16) -> Rect {
+        let width = self.width.saturating_sub(left.saturating_add(right));
+        let height = self.height.saturating_sub(top.saturating_add(bottom));
+        Rect {
+            x: self.x.saturating_add(left.min(self.width)),
+            y: self.y.saturating_add(top.min(self.height)),
+            width,
+            height,
+        }
+    }
+
+    /// Moves the rectangle by a signed offset, returning `None` if its corner would leave
+    /// the 1-based coordinate range.
+    pub fn offset(&self, dx: i32, dy: i32) -> Option<Rect> {
+        self.position().offset(dx, dy).map(|p| Rect::from_point_size(p, self.size()))
+    }
+
+    /// Splits the rectangle into a left part `columns` wide and the remaining right part.
+    pub fn split_horizontal(&self, columns: u16) -> (Rect, Rect) {
+        let columns = columns.min(self.width);
+        (
+            Rect { width: columns, ..*self },
+            Rect { x: self.x + columns, width: self.width - columns, ..*self },
+        )
+    }
+
+    /// Splits the rectangle into a top part `rows` high and the remaining bottom part.
+    pub fn split_vertical(&self, rows: u16) -> (Rect, Rect) {
+        let rows = rows.min(self.height);
+        (
+            Rect { height: rows, ..*self },
+            Rect { y: self.y + rows, height: self.height - rows, ..*self },
+        )
+    }
+
+    /// Returns a rectangle of the given size centered inside this one. The size is
+    /// clamped to fit.
+    pub fn centered(&self, size: Size) -> Rect {
+        let width = size.width.min(self.width);
+        let height = size.height.min(self.height);
+        Rect {
+            x: self.x + (self.width - width) / 2,
+            y: self.y + (self.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl Default for Rect {
+    fn default() -> Self {
+        Rect { x: 1, y: 1, width: 0, height: 0 }
+    }
+}
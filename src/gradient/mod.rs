@@ -0,0 +1,541 @@
+//! This module provides 24-bit colors, color interpolation and multi-stop gradients, along
+//! with helpers that paint a gradient across a string, a `Line` or a shaded rectangle.
+//!
+//! Gradients are drawn with truecolor escape sequences when the terminal supports them.
+//! Otherwise colors are reduced to the 256-color palette using ordered dithering, so smooth
+//! gradients still look smooth instead of banding.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::line::Line;
+use crate::rbox::{shade_char, ShadeStyle};
+use crate::*;
+
+/// A 24-bit color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Creates a color from its red, green and blue components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// Parses a color written as `#rrggbb`, `rrggbb` or `#rgb`.
+    pub fn from_hex(hex: &str) -> Option<Rgb> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let value = |s: &str| u8::from_str_radix(s, 16).ok();
+        match hex.len() {
+            6 => Some(Rgb::new(value(&hex[0..2])?, value(&hex[2..4])?, value(&hex[4..6])?)),
+            3 => Some(Rgb::new(
+                value(&hex[0..1])? * 17,
+                value(&hex[1..2])? * 17,
+                value(&hex[2..3])? * 17,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Formats the color as `#rrggbb`.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Converts the color to hue (0-360), saturation (0-1) and lightness (0-1).
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        if max == min {
+            return (0.0, 0.0, l);
+        }
+        let d = max - min;
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    /// Creates a color from hue (0-360), saturation (0-1) and lightness (0-1).
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Rgb {
+        if s == 0.0 {
+            let v = to_channel(l);
+            return Rgb::new(v, v, v);
+        }
+        let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+        let p = 2.0 * l - q;
+        let h = h.rem_euclid(360.0) / 360.0;
+        let hue = |mut t: f32| {
+            if t < 0.0 {
+                t += 1.0;
+            }
+            if t > 1.0 {
+                t -= 1.0;
+            }
+            if t < 1.0 / 6.0 {
+                p + (q - p) * 6.0 * t
+            } else if t < 0.5 {
+                q
+            } else if t < 2.0 / 3.0 {
+                p + (q - p) * (2.0 / 3.0 - t) * 6.0
+            } else {
+                p
+            }
+        };
+        Rgb::new(
+            to_channel(hue(h + 1.0 / 3.0)),
+            to_channel(hue(h)),
+            to_channel(hue(h - 1.0 / 3.0)),
+        )
+    }
+
+    /// Converts the color to OKLab lightness and a/b components.
+    pub fn to_oklab(&self) -> (f32, f32, f32) {
+        let r = srgb_to_linear(self.r);
+        let g = srgb_to_linear(self.g);
+        let b = srgb_to_linear(self.b);
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        (
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        )
+    }
+
+    /// Creates a color from OKLab lightness and a/b components.
+    pub fn from_oklab(l: f32, a: f32, b: f32) -> Rgb {
+        let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+        Rgb::new(
+            linear_to_srgb(4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_),
+            linear_to_srgb(-1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_),
+            linear_to_srgb(-0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_),
+        )
+    }
+}
+
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Self {
+        color_to_rgb(color)
+    }
+}
+
+fn to_channel(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn srgb_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    to_channel(c)
+}
+
+/// Returns the RGB value that terminals commonly use for one of the eight basic colors.
+pub fn color_to_rgb(color: Color) -> Rgb {
+    match color {
+        Color::Black => Rgb::new(0, 0, 0),
+        Color::Red => Rgb::new(205, 0, 0),
+        Color::Green => Rgb::new(0, 205, 0),
+        Color::Yellow => Rgb::new(205, 205, 0),
+        Color::Blue => Rgb::new(0, 0, 238),
+        Color::Magenta => Rgb::new(205, 0, 205),
+        Color::Cyan => Rgb::new(0, 205, 205),
+        Color::White => Rgb::new(229, 229, 229),
+    }
+}
+
+/// The color space in which two colors are blended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// Straight blending of the red, green and blue components.
+    Rgb,
+    /// Blending of hue, saturation and lightness; hue takes the shorter way round.
+    Hsl,
+    /// Blending in the perceptual OKLab space, which gives the most even-looking result.
+    #[default]
+    Oklab,
+}
+
+/// Blends two colors. `t` runs from 0 (all `from`) to 1 (all `to`).
+pub fn interpolate(from: Rgb, to: Rgb, t: f32, space: ColorSpace) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    match space {
+        ColorSpace::Rgb => Rgb::new(
+            lerp(from.r as f32, to.r as f32).round() as u8,
+            lerp(from.g as f32, to.g as f32).round() as u8,
+            lerp(from.b as f32, to.b as f32).round() as u8,
+        ),
+        ColorSpace::Hsl => {
+            let (h1, s1, l1) = from.to_hsl();
+            let (h2, s2, l2) = to.to_hsl();
+            let mut dh = h2 - h1;
+            if dh > 180.0 {
+                dh -= 360.0;
+            } else if dh < -180.0 {
+                dh += 360.0;
+            }
+            Rgb::from_hsl(h1 + dh * t, lerp(s1, s2), lerp(l1, l2))
+        }
+        ColorSpace::Oklab => {
+            let (l1, a1, b1) = from.to_oklab();
+            let (l2, a2, b2) = to.to_oklab();
+            Rgb::from_oklab(lerp(l1, l2), lerp(a1, a2), lerp(b1, b2))
+        }
+    }
+}
+
+/// A gradient made of color stops at positions between 0 and 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, Rgb)>,
+    space: ColorSpace,
+}
+
+impl Gradient {
+    /// Creates a gradient with the given colors spread evenly from 0 to 1.
+    pub fn new(colors: &[Rgb]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| (i as f32 / last, *color))
+            .collect();
+        Self::with_stops(stops)
+    }
+
+    /// Creates a gradient from explicit `(position, color)` stops.
+    ///
+    /// Positions are clamped to 0-1, stops at a NaN position are dropped and the rest
+    /// are sorted by position.
+    pub fn with_stops(stops: Vec<(f32, Rgb)>) -> Self {
+        let mut stops: Vec<(f32, Rgb)> = stops
+            .into_iter()
+            .filter(|(position, _)| !position.is_nan())
+            .map(|(position, color)| (position.clamp(0.0, 1.0), color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Gradient { stops, space: ColorSpace::default() }
+    }
+
+    /// Returns a copy of the gradient that blends in the given color space.
+    pub fn space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Returns the color at position `t` between 0 and 1. A NaN position gives the
+    /// first color.
+    pub fn at(&self, t: f32) -> Rgb {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self.stops.as_slice() {
+            [] => Rgb::default(),
+            [(_, only)] => *only,
+            stops => {
+                let first = stops[0];
+                let last = stops[stops.len() - 1];
+                if t <= first.0 {
+                    return first.1;
+                }
+                if t >= last.0 {
+                    return last.1;
+                }
+                let upper = stops.iter().position(|(position, _)| *position >= t).unwrap();
+                let (p1, c1) = stops[upper - 1];
+                let (p2, c2) = stops[upper];
+                let local = if p2 > p1 { (t - p1) / (p2 - p1) } else { 1.0 };
+                interpolate(c1, c2, local, self.space)
+            }
+        }
+    }
+
+    /// Returns `count` colors sampled evenly along the gradient.
+    pub fn colors(&self, count: usize) -> Vec<Rgb> {
+        (0..count).map(|i| self.at(fraction(i, count))).collect()
+    }
+}
+
+fn fraction(i: usize, count: usize) -> f32 {
+    if count <= 1 {
+        0.0
+    } else {
+        i as f32 / (count - 1) as f32
+    }
+}
+
+/// The direction in which a gradient runs across a rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    Radial,
+}
+
+/// How many colors the terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    TrueColor,
+    Ansi256,
+}
+
+// 0 means "not decided yet": the mode is detected from the environment on first use.
+static COLOR_MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Guesses the color mode from the `COLORTERM` environment variable.
+pub fn detect_color_mode() -> ColorMode {
+    match std::env::var("COLORTERM") {
+        Ok(value) if value.contains("truecolor") || value.contains("24bit") => ColorMode::TrueColor,
+        _ => ColorMode::Ansi256,
+    }
+}
+
+/// Overrides the detected color mode.
+pub fn set_color_mode(mode: ColorMode) {
+    let value = match mode {
+        ColorMode::TrueColor => 1,
+        ColorMode::Ansi256 => 2,
+    };
+    COLOR_MODE.store(value, Ordering::Relaxed);
+}
+
+/// Gets the color mode used for 24-bit colors, detecting it on first use.
+pub fn get_color_mode() -> ColorMode {
+    match COLOR_MODE.load(Ordering::Relaxed) {
+        1 => ColorMode::TrueColor,
+        2 => ColorMode::Ansi256,
+        _ => {
+            let mode = detect_color_mode();
+            set_color_mode(mode);
+            mode
+        }
+    }
+}
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn nearest_level(v: u8) -> usize {
+    CUBE_LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, level)| (v as i32 - **level as i32).abs())
+        .map(|(i, _)| i)
+        .unwrap()
+}
+
+/// Maps a color to the closest entry of the 256-color palette.
+pub fn rgb_to_ansi256(rgb: Rgb) -> u8 {
+    let (ri, gi, bi) = (nearest_level(rgb.r), nearest_level(rgb.g), nearest_level(rgb.b));
+    let cube = Rgb::new(CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let average = (rgb.r as u32 + rgb.g as u32 + rgb.b as u32) / 3;
+    let gray_index = ((average.saturating_sub(3)) / 10).min(23) as u8;
+    let gray_value = 8 + gray_index * 10;
+    let gray = Rgb::new(gray_value, gray_value, gray_value);
+    if distance(rgb, gray) < distance(rgb, cube) {
+        232 + gray_index
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+fn distance(a: Rgb, b: Rgb) -> i32 {
+    let dr = a.r as i32 - b.r as i32;
+    let dg = a.g as i32 - b.g as i32;
+    let db = a.b as i32 - b.b as i32;
+    dr * dr + dg * dg + db * db
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Maps a color to the 256-color cube using ordered dithering.
+///
+/// Neighbouring cells get slightly different palette entries so that, seen together,
+/// they average out to the requested color.
+pub fn dither_to_ansi256(rgb: Rgb, x: u16, y: u16) -> u8 {
+    let threshold = (BAYER_4X4[y as usize % 4][x as usize % 4] as f32 + 0.5) / 16.0;
+    let level = |v: u8| {
+        let upper = CUBE_LEVELS.iter().position(|level| *level >= v).unwrap();
+        if upper == 0 || CUBE_LEVELS[upper] == v {
+            return upper as u8;
+        }
+        let low = CUBE_LEVELS[upper - 1] as f32;
+        let high = CUBE_LEVELS[upper] as f32;
+        if (v as f32 - low) / (high - low) > threshold {
+            upper as u8
+        } else {
+            upper as u8 - 1
+        }
+    };
+    16 + 36 * level(rgb.r) + 6 * level(rgb.g) + level(rgb.b)
+}
+
+fn foreground_sequence(rgb: Rgb, x: u16, y: u16) -> String {
+    match get_color_mode() {
+        ColorMode::TrueColor => format!("\x1B[38;2;{};{};{}m", rgb.r, rgb.g, rgb.b),
+        ColorMode::Ansi256 => format!("\x1B[38;5;{}m", dither_to_ansi256(rgb, x, y)),
+    }
+}
+
+/// Sets a 24-bit foreground color for subsequent text output.
+///
+/// Falls back to the closest 256-color palette entry when truecolor is unavailable.
+pub fn set_foreground_rgb(rgb: Rgb) {
+    match get_color_mode() {
+        ColorMode::TrueColor => print(&format!("\x1B[38;2;{};{};{}m", rgb.r, rgb.g, rgb.b)),
+        ColorMode::Ansi256 => print(&format!("\x1B[38;5;{}m", rgb_to_ansi256(rgb))),
+    }
+}
+
+/// Sets a 24-bit background color for subsequent text output.
+///
+/// Falls back to the closest 256-color palette entry when truecolor is unavailable.
+pub fn set_background_rgb(rgb: Rgb) {
+    match get_color_mode() {
+        ColorMode::TrueColor => print(&format!("\x1B[48;2;{};{};{}m", rgb.r, rgb.g, rgb.b)),
+        ColorMode::Ansi256 => print(&format!("\x1B[48;5;{}m", rgb_to_ansi256(rgb))),
+    }
+}
+
+/// Returns the text with each character colored along the gradient, from left to right.
+///
+/// The returned string ends by resetting the colors.
+pub fn gradient_text(text: &str, gradient: &Gradient) -> String {
+    let count = text.chars().count();
+    let mut output = String::new();
+    for (i, ch) in text.chars().enumerate() {
+        output.push_str(&foreground_sequence(gradient.at(fraction(i, count)), i as u16, 0));
+        output.push(ch);
+    }
+    output.push_str("\x1B[0m");
+    output
+}
+
+/// Prints the text at the cursor with each character colored along the gradient.
+pub fn print_gradient(text: &str, gradient: &Gradient) {
+    print(&gradient_text(text, gradient));
+}
+
+/// Draws a line with its cells colored along the gradient, from its start to its end.
+pub fn draw_gradient_line(line: &Line, gradient: &Gradient) {
+    let cells = line.cells();
+    let count = cells.len();
+    for (i, (x, y, ch)) in cells.into_iter().enumerate() {
+        move_cursor_to(x, y);
+        print(&foreground_sequence(gradient.at(fraction(i, count)), x, y));
+        put_char(ch);
+    }
+    reset_color();
+}
+
+/// Draws a shaded rectangle, like `rbox::draw_shaded_rectangle`, colored with the gradient.
+///
+/// # Arguments
+///
+/// * `x` - The x-coordinate of the top-left corner
+/// * `y` - The y-coordinate of the top-left corner
+/// * `width` - The width of the rectangle
+/// * `height` - The height of the rectangle
+/// * `style` - The ShadeStyle to use
+/// * `gradient` - The gradient to color the rectangle with
+/// * `direction` - The direction in which the gradient runs
+pub fn draw_gradient_rectangle(
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    style: ShadeStyle,
+    gradient: &Gradient,
+    direction: GradientDirection,
+) {
+    let (viewport_width, viewport_height) = get_viewport();
+
+    if x + width > viewport_width || y + height > viewport_height {
+        handle_boundary_error("Rectangle extends beyond viewport");
+        return;
+    }
+
+    let shade_char = shade_char(style);
+
+    for dy in 0..height {
+        move_cursor_to(x, y + dy);
+        let mut row = String::new();
+        for dx in 0..width {
+            let t = gradient_position(dx, dy, width, height, direction);
+            row.push_str(&foreground_sequence(gradient.at(t), x + dx, y + dy));
+            row.push(shade_char);
+        }
+        print(&row);
+    }
+    reset_color();
+}
+
+/// Returns where a cell of a `width` x `height` area falls along a gradient, from 0 to 1.
+pub fn gradient_position(dx: u16, dy: u16, width: u16, height: u16, direction: GradientDirection) -> f32 {
+    let fx = fraction(dx as usize, width as usize);
+    let fy = fraction(dy as usize, height as usize);
+    match direction {
+        GradientDirection::Horizontal => fx,
+        GradientDirection::Vertical => fy,
+        GradientDirection::Diagonal => (fx + fy) / 2.0,
+        GradientDirection::Radial => {
+            // Terminal cells are about twice as tall as they are wide.
+            let cx = (width as f32 - 1.0) / 2.0;
+            let cy = (height as f32 - 1.0) / 2.0;
+            let distance = |x: f32, y: f32| ((x - cx).powi(2) + ((y - cy) * 2.0).powi(2)).sqrt();
+            let max = distance(0.0, 0.0);
+            if max == 0.0 {
+                0.0
+            } else {
+                distance(dx as f32, dy as f32) / max
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = Rgb::new(255, 0, 0);
+    const GREEN: Rgb = Rgb::new(0, 255, 0);
+    const BLUE: Rgb = Rgb::new(0, 0, 255);
+
+    #[test]
+    fn nan_position_gives_the_first_color() {
+        let gradient = Gradient::new(&[RED, GREEN, BLUE]);
+        assert_eq!(gradient.at(f32::NAN), RED);
+        assert_eq!(gradient.at(f32::INFINITY), BLUE);
+        assert_eq!(gradient.at(f32::NEG_INFINITY), RED);
+    }
+
+    #[test]
+    fn nan_stops_are_dropped() {
+        let gradient = Gradient::with_stops(vec![(0.0, RED), (f32::NAN, GREEN), (1.0, BLUE)]);
+        assert_eq!(gradient.at(0.0), RED);
+        assert_eq!(gradient.at(1.0), BLUE);
+        assert_ne!(gradient.at(0.5), GREEN);
+    }
+}
@@ -0,0 +1,190 @@
+//! This module splits text into grapheme clusters: the characters a reader perceives as
+//! one, such as `e` followed by a combining accent, a flag made of two regional indicator
+//! letters, or an emoji family joined with zero-width joiners.
+//!
+//! Truncating text, moving a cursor through an input field or placing text into screen
+//! cells should always work on whole clusters, so that a user-perceived character is never
+//! split in two. The break rules follow the extended grapheme cluster rules of UAX #29;
+//! the character classes in `tables.rs` are generated by
+//! `scripts/generate_grapheme_tables.py`.
+
+mod tables;
+
+use crate::width::char_width;
+use tables::BREAK_CLASSES;
+
+/// The grapheme break class of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BreakClass {
+    Other,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    ExtendedPictographic,
+}
+
+fn break_class(ch: char) -> BreakClass {
+    let cp = ch as u32;
+    if (0x20..0x7F).contains(&cp) {
+        return BreakClass::Other;
+    }
+    if (0xAC00..=0xD7A3).contains(&cp) {
+        return if (cp - 0xAC00).is_multiple_of(28) { BreakClass::Lv } else { BreakClass::Lvt };
+    }
+    match BREAK_CLASSES.binary_search_by(|(start, end, _)| {
+        if cp < *start {
+            std::cmp::Ordering::Greater
+        } else if cp > *end {
+            std::cmp::Ordering::Less
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }) {
+        Ok(i) => BREAK_CLASSES[i].2,
+        Err(_) => BreakClass::Other,
+    }
+}
+
+// State carried between characters to apply the emoji (GB11) and flag (GB12/13) rules.
+#[derive(Default)]
+struct BreakState {
+    // An extended pictographic character followed only by Extend characters so far.
+    pictographic: bool,
+    // Number of regional indicators in the current run.
+    regional_indicators: usize,
+}
+
+// Decides whether there is a boundary between `before` and `after`, and updates `state`.
+fn is_boundary(before: BreakClass, after: BreakClass, state: &mut BreakState) -> bool {
+    use BreakClass::*;
+
+    let pictographic_before_zwj = state.pictographic && before == Zwj;
+    state.pictographic = match after {
+        ExtendedPictographic => true,
+        Extend | Zwj => state.pictographic && before != Zwj,
+        _ => false,
+    };
+    let odd_regional_indicators = !state.regional_indicators.is_multiple_of(2);
+    state.regional_indicators = if after == RegionalIndicator { state.regional_indicators + 1 } else { 0 };
+
+    match (before, after) {
+        (Cr, Lf) => false,
+        (Control | Cr | Lf, _) | (_, Control | Cr | Lf) => true,
+        (L, L | V | Lv | Lvt) => false,
+        (Lv | V, V | T) => false,
+        (Lvt | T, T) => false,
+        (_, Extend | Zwj | SpacingMark) => false,
+        (Prepend, _) => false,
+        (Zwj, ExtendedPictographic) if pictographic_before_zwj => false,
+        (RegionalIndicator, RegionalIndicator) => !odd_regional_indicators,
+        _ => true,
+    }
+}
+
+/// An iterator over the grapheme clusters of a string.
+pub struct Graphemes<'a> {
+    text: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.text.is_empty() {
+            return None;
+        }
+        let end = next_boundary(self.text, 0);
+        let (cluster, rest) = self.text.split_at(end);
+        self.text = rest;
+        Some(cluster)
+    }
+}
+
+/// Returns an iterator over the grapheme clusters of a string.
+pub fn graphemes(text: &str) -> Graphemes<'_> {
+    Graphemes { text }
+}
+
+/// Returns the byte index of the first cluster boundary after `index`.
+///
+/// `index` must be a cluster boundary itself; returns `text.len()` at the end of the text.
+pub fn next_boundary(text: &str, index: usize) -> usize {
+    let mut chars = text[index..].char_indices();
+    let Some((_, first)) = chars.next() else {
+        return text.len();
+    };
+    let mut state = BreakState::default();
+    let mut before = break_class(first);
+    state.pictographic = before == BreakClass::ExtendedPictographic;
+    state.regional_indicators = usize::from(before == BreakClass::RegionalIndicator);
+    for (offset, ch) in chars {
+        let after = break_class(ch);
+        if is_boundary(before, after, &mut state) {
+            return index + offset;
+        }
+        before = after;
+    }
+    text.len()
+}
+
+/// Returns the byte index of the last cluster boundary before `index`.
+///
+/// `index` must be a cluster boundary itself; returns 0 at the start of the text.
+pub fn prev_boundary(text: &str, index: usize) -> usize {
+    let mut last = 0;
+    let mut position = 0;
+    while position < index {
+        last = position;
+        position = next_boundary(text, position);
+    }
+    last
+}
+
+/// Returns the number of grapheme clusters in a string.
+pub fn grapheme_count(text: &str) -> usize {
+    graphemes(text).count()
+}
+
+/// Returns the number of columns a single grapheme cluster occupies.
+///
+/// Emoji sequences joined with zero-width joiners, emoji with the emoji presentation
+/// selector (U+FE0F) and flags are drawn as a single two-column glyph.
+pub fn grapheme_width(cluster: &str) -> usize {
+    let mut chars = cluster.chars();
+    let Some(first) = chars.next() else {
+        return 0;
+    };
+    let class = break_class(first);
+    let joined = cluster.chars().any(|c| c == '\u{200D}' || c == '\u{FE0F}');
+    if (class == BreakClass::ExtendedPictographic && joined)
+        || (class == BreakClass::RegionalIndicator && chars.next().is_some())
+    {
+        return 2;
+    }
+    cluster.chars().map(char_width).find(|w| *w > 0).unwrap_or(0)
+}
+
+/// Returns the longest prefix of the text, made of whole clusters, that fits in `width`
+/// columns.
+pub fn truncate_to_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    let mut end = 0;
+    for cluster in graphemes(text) {
+        used += grapheme_width(cluster);
+        if used > width {
+            break;
+        }
+        end += cluster.len();
+    }
+    &text[..end]
+}
@@ -0,0 +1,470 @@
+//! This module provides an editable single-line text input.
+//!
+//! Unlike `read_line`, which leaves editing to the terminal's canonical mode, a
+//! `TextInput` handles every key itself: it supports cursor movement, word-wise deletion,
+//! insert and overwrite modes, a placeholder, a maximum length and validation, and it
+//! scrolls horizontally when the text is wider than the field. It can be used as a
+//! `Widget` inside an `App`, or on its own with `TextInput::read_at`.
+//!
+//! The cursor always sits on a grapheme cluster boundary, so accented letters and emoji
+//! are edited as single characters.
+
+use std::cell::Cell;
+
+use crate::event::{read_key_event, Event, KeyCode, KeyEvent};
+use crate::geometry::{Rect, Size};
+use crate::grapheme::{grapheme_count, grapheme_width, graphemes, next_boundary, prev_boundary};
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::theme::{role_style, Role};
+use crate::widget::{EventResult, Widget};
+use crate::width::str_width;
+use crate::*;
+
+/// Checks the value of a text input, returning an error message if it is not acceptable.
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// An editable single line of text.
+///
+/// Keys: Left/Right, Home/End (Ctrl-A/Ctrl-E), Ctrl-Left/Ctrl-Right (Alt-B/Alt-F) move by
+/// word, Backspace/Delete (Ctrl-D), Ctrl-W or Alt-Backspace and Alt-D or Ctrl-Delete
+/// delete a word, Ctrl-U and Ctrl-K delete to the start and end, Insert toggles
+/// overwrite mode, Enter submits and Esc cancels.
+pub struct TextInput {
+    value: String,
+    // Byte offset of the cursor in `value`, always on a grapheme boundary.
+    cursor: usize,
+    /// Shown in the `Role::Muted` style while the value is empty.
+    pub placeholder: String,
+    /// The maximum number of grapheme clusters.
+    pub max_length: Option<usize>,
+    /// Replace the character under the cursor instead of inserting before it.
+    pub overwrite: bool,
+    /// The style the value is drawn with. Invalid values are drawn in `Role::Error`.
+    pub style: Style,
+    /// If set, every visible character is drawn as this character.
+    pub mask: Option<char>,
+    validator: Option<Validator>,
+    filter: Option<Box<dyn Fn(char) -> bool>>,
+    error: Option<String>,
+    focused: bool,
+    submitted: bool,
+    cancelled: bool,
+    // The first visible column, updated when rendering.
+    scroll: Cell<usize>,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        TextInput {
+            value: String::new(),
+            cursor: 0,
+            placeholder: String::new(),
+            max_length: None,
+            overwrite: false,
+            style: Style::default(),
+            mask: None,
+            validator: None,
+            filter: None,
+            error: None,
+            focused: false,
+            submitted: false,
+            cancelled: false,
+            scroll: Cell::new(0),
+        }
+    }
+
+    /// Sets the initial value and places the cursor at its end.
+    pub fn value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Draws every character as `mask`, for passwords.
+    pub fn mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Sets a function that checks the value after every change and before submitting.
+    pub fn validator(mut self, validator: impl Fn(&str) -> Result<(), String> + 'static) -> Self {
+        self.validator = Some(Box::new(validator));
+        self.validate();
+        self
+    }
+
+    /// Sets a function that decides which typed characters are accepted.
+    pub fn filter(mut self, filter: impl Fn(char) -> bool + 'static) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Returns the current text.
+    pub fn text(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text, dropping characters the filter rejects and truncating it to the
+    /// maximum length, and moves the cursor to the end.
+    pub fn set_value(&mut self, value: &str) {
+        let value: String = match &self.filter {
+            Some(filter) => value.chars().filter(|&ch| filter(ch)).collect(),
+            None => value.to_string(),
+        };
+        self.value = match self.max_length {
+            Some(max) => graphemes(&value).take(max).collect(),
+            None => value,
+        };
+        self.cursor = self.value.len();
+        self.validate();
+    }
+
+    /// Returns the cursor position as a byte offset into `text()`.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to a byte offset, rounded up to the next grapheme boundary.
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = self.boundary_at_or_after(cursor.min(self.value.len()));
+    }
+
+    /// Inserts text at the cursor, ignoring the filter and overwrite mode. Text that
+    /// would exceed the maximum length is cut off.
+    pub fn insert_str(&mut self, text: &str) {
+        let mut text = text.to_string();
+        if let Some(max) = self.max_length {
+            let room = max.saturating_sub(grapheme_count(&self.value));
+            text = graphemes(&text).take(room).collect();
+        }
+        self.value.insert_str(self.cursor, &text);
+        let end = self.cursor + text.len();
+        self.cursor = self.boundary_at_or_after(end);
+        self.validate();
+    }
+
+    /// Returns the cursor position in grapheme clusters from the start.
+    pub fn cursor_position(&self) -> usize {
+        grapheme_count(&self.value[..self.cursor])
+    }
+
+    /// Returns the validation error for the current value, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns true once Enter has been pressed with a valid value.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Returns true once Esc or Ctrl-C has been pressed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Clears the submitted and cancelled flags so the input can be edited again.
+    pub fn reset_state(&mut self) {
+        self.submitted = false;
+        self.cancelled = false;
+    }
+
+    fn validate(&mut self) {
+        self.error = self.validator.as_ref().and_then(|validator| validator(&self.value).err());
+    }
+
+    fn insert(&mut self, ch: char) {
+        if self.filter.as_ref().is_some_and(|filter| !filter(ch)) {
+            return;
+        }
+        let mut end = self.cursor;
+        if self.overwrite && self.cursor < self.value.len() {
+            end = next_boundary(&self.value, self.cursor);
+        }
+        let mut updated = String::with_capacity(self.value.len() + ch.len_utf8());
+        updated.push_str(&self.value[..self.cursor]);
+        updated.push(ch);
+        updated.push_str(&self.value[end..]);
+        if self.max_length.is_some_and(|max| grapheme_count(&updated) > max) {
+            return;
+        }
+        // The new character may have joined the cluster before it, such as a combining
+        // accent, so find the boundary after it rather than assuming it starts one.
+        let inserted_end = self.cursor + ch.len_utf8();
+        self.value = updated;
+        self.cursor = self.boundary_at_or_after(inserted_end);
+    }
+
+    fn boundary_at_or_after(&self, index: usize) -> usize {
+        let mut boundary = 0;
+        while boundary < index && boundary < self.value.len() {
+            boundary = next_boundary(&self.value, boundary);
+        }
+        boundary
+    }
+
+    // Deletes the text between two byte offsets and puts the cursor at the start.
+    fn delete_range(&mut self, start: usize, end: usize) {
+        if start < end {
+            self.value.replace_range(start..end, "");
+            self.cursor = start;
+        }
+    }
+
+    fn is_word(cluster: &str) -> bool {
+        cluster.chars().next().is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+    }
+
+    // The start of the word before the cursor, skipping any separators first.
+    fn previous_word(&self) -> usize {
+        let mut position = self.cursor;
+        let mut seen_word = false;
+        while position > 0 {
+            let start = prev_boundary(&self.value, position);
+            let word = Self::is_word(&self.value[start..position]);
+            if seen_word && !word {
+                break;
+            }
+            seen_word |= word;
+            position = start;
+        }
+        position
+    }
+
+    // The end of the word after the cursor, skipping any separators first.
+    fn next_word(&self) -> usize {
+        let mut position = self.cursor;
+        let mut seen_word = false;
+        while position < self.value.len() {
+            let end = next_boundary(&self.value, position);
+            let word = Self::is_word(&self.value[position..end]);
+            if seen_word && !word {
+                break;
+            }
+            seen_word |= word;
+            position = end;
+        }
+        position
+    }
+
+    /// Applies a key press. Returns true if the key was used.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let before = self.value.clone();
+        let word_modifier = key.ctrl || key.alt;
+        match key.code {
+            KeyCode::Left if word_modifier => self.cursor = self.previous_word(),
+            KeyCode::Right if word_modifier => self.cursor = self.next_word(),
+            KeyCode::Left => self.cursor = prev_boundary(&self.value, self.cursor),
+            KeyCode::Right if self.cursor < self.value.len() => {
+                self.cursor = next_boundary(&self.value, self.cursor)
+            }
+            KeyCode::Right => {}
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            KeyCode::Backspace if word_modifier => self.delete_range(self.previous_word(), self.cursor),
+            KeyCode::Backspace => self.delete_range(prev_boundary(&self.value, self.cursor), self.cursor),
+            KeyCode::Delete if word_modifier => self.delete_range(self.cursor, self.next_word()),
+            KeyCode::Delete if self.cursor < self.value.len() => {
+                self.delete_range(self.cursor, next_boundary(&self.value, self.cursor))
+            }
+            KeyCode::Delete => {}
+            KeyCode::Insert => self.overwrite = !self.overwrite,
+            KeyCode::Enter => {
+                self.validate();
+                self.submitted = self.is_valid();
+            }
+            KeyCode::Esc => self.cancelled = true,
+            KeyCode::Char(ch) if key.ctrl => match ch {
+                'a' => self.cursor = 0,
+                'e' => self.cursor = self.value.len(),
+                'b' => self.cursor = prev_boundary(&self.value, self.cursor),
+                'f' if self.cursor < self.value.len() => self.cursor = next_boundary(&self.value, self.cursor),
+                'd' if self.cursor < self.value.len() => {
+                    self.delete_range(self.cursor, next_boundary(&self.value, self.cursor))
+                }
+                'h' => self.delete_range(prev_boundary(&self.value, self.cursor), self.cursor),
+                'w' => self.delete_range(self.previous_word(), self.cursor),
+                'u' => self.delete_range(0, self.cursor),
+                'k' => self.value.truncate(self.cursor),
+                'c' => self.cancelled = true,
+                'f' | 'd' => {}
+                _ => return false,
+            },
+            KeyCode::Char(ch) if key.alt => match ch {
+                'b' => self.cursor = self.previous_word(),
+                'f' => self.cursor = self.next_word(),
+                'd' => self.delete_range(self.cursor, self.next_word()),
+                _ => return false,
+            },
+            KeyCode::Char(ch) => self.insert(ch),
+            _ => return false,
+        }
+        if self.value != before {
+            self.validate();
+        }
+        true
+    }
+
+    // Returns the text as drawn, with every cluster replaced by the mask if one is set.
+    fn display_text(&self, text: &str) -> String {
+        match self.mask {
+            Some(mask) => graphemes(text).map(|_| mask).collect(),
+            None => text.to_string(),
+        }
+    }
+
+    /// Draws the input into `area` of a buffer, using its first row. When `show_cursor`
+    /// is true, the buffer's cursor is placed at the editing position.
+    pub fn draw(&self, area: Rect, buffer: &mut ScreenBuffer, show_cursor: bool) {
+        if area.is_empty() {
+            return;
+        }
+        let width = area.width as usize;
+        buffer.fill(Rect::new(area.x, area.y, area.width, 1), ' ', self.style);
+
+        if self.value.is_empty() {
+            if !self.placeholder.is_empty() {
+                let placeholder = crate::ansi::truncate(&self.placeholder, width);
+                buffer.put_str(area.x, area.y, &placeholder, self.style.patch(role_style(Role::Muted)));
+            }
+            self.scroll.set(0);
+        } else {
+            let text_style = if self.is_valid() { self.style } else { self.style.patch(role_style(Role::Error)) };
+            let cursor_column = str_width(&self.display_text(&self.value[..self.cursor]));
+            // Keep the cursor, including the cell it sits on, inside the field, without
+            // leaving blank space on the right after text is deleted.
+            let total_width = str_width(&self.display_text(&self.value)) + 1;
+            let mut scroll = self.scroll.get().min(total_width.saturating_sub(width));
+            if cursor_column < scroll {
+                scroll = cursor_column;
+            } else if cursor_column >= scroll + width {
+                scroll = cursor_column + 1 - width;
+            }
+            self.scroll.set(scroll);
+
+            let mut column = 0;
+            for cluster in graphemes(&self.display_text(&self.value)) {
+                let cluster_width = grapheme_width(cluster);
+                if column >= scroll && column + cluster_width <= scroll + width {
+                    buffer.set_grapheme(area.x + (column - scroll) as u16, area.y, cluster, text_style);
+                }
+                column += cluster_width;
+                if column >= scroll + width {
+                    break;
+                }
+            }
+        }
+
+        if show_cursor {
+            let cursor_column = str_width(&self.display_text(&self.value[..self.cursor]));
+            let x = area.x + (cursor_column - self.scroll.get()).min(width - 1) as u16;
+            buffer.set_cursor(Some(Point::new(x, area.y)));
+        }
+    }
+
+    /// Edits the input at the given position until Enter or Esc is pressed.
+    ///
+    /// Returns the value, or `None` if editing was cancelled. If standard input is not a
+    /// terminal, reads a line with `read_line` instead.
+    pub fn read_at(&mut self, x: u16, y: u16, width: u16) -> Option<String> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            move_cursor_to(x, y);
+            let line = read_line();
+            self.set_value(&line);
+            // `set_value` applies the length limit and filter, so return what it kept.
+            return self.is_valid().then(|| self.value.clone());
+        }
+
+        self.reset_state();
+        let mut previous: Option<ScreenBuffer> = None;
+        let result = loop {
+            let mut buffer = ScreenBuffer::new(width, 1);
+            self.draw(buffer.area(), &mut buffer, true);
+            match &previous {
+                Some(previous) => buffer.render_diff_at(previous, x, y),
+                None => buffer.render_at(x, y),
+            }
+            if let Some(cursor) = buffer.cursor() {
+                move_cursor_to(x + cursor.x - 1, y);
+                show_cursor();
+            }
+            previous = Some(buffer);
+
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            self.handle_key(&key);
+            if self.submitted {
+                break Some(self.value.clone());
+            }
+            if self.cancelled {
+                break None;
+            }
+        };
+
+        if !was_raw {
+            disable_raw_mode();
+        }
+        result
+    }
+}
+
+impl Widget for TextInput {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        self.draw(area, buffer, self.focused);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        Size::new(available.width, 1.min(available.height))
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        // Leave Tab for moving focus and Ctrl-C for quitting the application.
+        if matches!(key.code, KeyCode::Tab | KeyCode::BackTab) || key.is_ctrl('c') {
+            return EventResult::Ignored;
+        }
+        if self.handle_key(key) {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
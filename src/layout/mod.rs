@@ -0,0 +1,258 @@
+//! This module splits a `Rect` into smaller rectangles by constraints, for building
+//! screens out of panels.
+//!
+//! Because the solver works from whatever rectangle it is given, a layout computed from
+//! `Rect::viewport()` on every redraw reflows by itself when the viewport changes, for
+//! example after `terminal::sync_viewport` picks up a resized terminal.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::layout::{Constraint, Layout};
+//!
+//! let rows = Layout::vertical(vec![Constraint::Length(3), Constraint::Fill(1)]).split(Rect::viewport());
+//! let columns = Layout::horizontal(vec![Constraint::Percentage(30), Constraint::Fill(1)])
+//!     .gap(1)
+//!     .split(rows[1]);
+//! for area in [rows[0], columns[0], columns[1]] {
+//!     rbox::draw_box_rect(area, BoxStyle::Single);
+//! }
+//! ```
+
+use crate::geometry::Rect;
+
+/// How much space one part of a layout asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// Exactly this many cells.
+    Length(u16),
+    /// A percentage of the available space.
+    Percentage(u16),
+    /// A fraction of the available space, `numerator / denominator`.
+    Ratio(u32, u32),
+    /// At least this many cells; takes a share of leftover space when nothing fills.
+    Min(u16),
+    /// At most this many cells; takes a share of leftover space when nothing fills.
+    Max(u16),
+    /// A share of the leftover space, in proportion to the weight.
+    Fill(u16),
+}
+
+/// The axis a layout splits along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutDirection {
+    /// Side by side, left to right.
+    Horizontal,
+    /// Stacked, top to bottom.
+    #[default]
+    Vertical,
+}
+
+/// Splits a rectangle along one axis.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Layout {
+    pub direction: LayoutDirection,
+    pub constraints: Vec<Constraint>,
+    /// Blank columns left and right of the parts.
+    pub horizontal_margin: u16,
+    /// Blank rows above and below the parts.
+    pub vertical_margin: u16,
+    /// Blank cells between neighbouring parts.
+    pub gap: u16,
+}
+
+impl Layout {
+    pub fn new(direction: LayoutDirection, constraints: Vec<Constraint>) -> Self {
+        Layout { direction, constraints, ..Default::default() }
+    }
+
+    /// Creates a layout that places parts side by side.
+    pub fn horizontal(constraints: Vec<Constraint>) -> Self {
+        Self::new(LayoutDirection::Horizontal, constraints)
+    }
+
+    /// Creates a layout that stacks parts from top to bottom.
+    pub fn vertical(constraints: Vec<Constraint>) -> Self {
+        Self::new(LayoutDirection::Vertical, constraints)
+    }
+
+    /// Sets the same margin on all four sides.
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.horizontal_margin = margin;
+        self.vertical_margin = margin;
+        self
+    }
+
+    /// Sets separate margins for the left and right and for the top and bottom.
+    pub fn margins(mut self, horizontal: u16, vertical: u16) -> Self {
+        self.horizontal_margin = horizontal;
+        self.vertical_margin = vertical;
+        self
+    }
+
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Splits the rectangle into one rectangle per constraint.
+    pub fn split(&self, area: Rect) -> Vec<Rect> {
+        let inner = area.inset(self.horizontal_margin, self.vertical_margin);
+        let horizontal = self.direction == LayoutDirection::Horizontal;
+        let length = if horizontal { inner.width } else { inner.height };
+        let gaps = self.gap as u32 * self.constraints.len().saturating_sub(1) as u32;
+        let available = (length as u32).saturating_sub(gaps) as u16;
+
+        let mut position = if horizontal { inner.x } else { inner.y };
+        let end = position.saturating_add(length);
+        solve(&self.constraints, available)
+            .into_iter()
+            .map(|size| {
+                let start = position.min(end);
+                let size = size.min(end - start);
+                position = start.saturating_add(size).saturating_add(self.gap);
+                if horizontal {
+                    Rect::new(start, inner.y, size, inner.height)
+                } else {
+                    Rect::new(inner.x, start, inner.width, size)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits a rectangle into rows and columns of cells.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Grid {
+    pub columns: Vec<Constraint>,
+    pub rows: Vec<Constraint>,
+    /// Blank cells around the whole grid.
+    pub margin: u16,
+    pub column_gap: u16,
+    pub row_gap: u16,
+}
+
+impl Grid {
+    pub fn new(columns: Vec<Constraint>, rows: Vec<Constraint>) -> Self {
+        Grid { columns, rows, ..Default::default() }
+    }
+
+    /// Creates a grid of equally sized cells.
+    pub fn uniform(columns: usize, rows: usize) -> Self {
+        Self::new(vec![Constraint::Fill(1); columns], vec![Constraint::Fill(1); rows])
+    }
+
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the gaps between columns and between rows.
+    pub fn gaps(mut self, column_gap: u16, row_gap: u16) -> Self {
+        self.column_gap = column_gap;
+        self.row_gap = row_gap;
+        self
+    }
+
+    /// Splits the rectangle into cells, returned row by row.
+    pub fn split(&self, area: Rect) -> Vec<Vec<Rect>> {
+        let columns = Layout::horizontal(self.columns.clone()).gap(self.column_gap);
+        Layout::vertical(self.rows.clone())
+            .margin(self.margin)
+            .gap(self.row_gap)
+            .split(area)
+            .into_iter()
+            .map(|row| columns.split(row))
+            .collect()
+    }
+}
+
+/// Works out the size of each part so that together they fill `available` cells.
+///
+/// Fixed, percentage and ratio parts get their size first, then `Min` parts their
+/// minimum. When there are `Fill` parts, leftover space goes to `Max` parts up to their
+/// maximum and the rest to the `Fill` parts by weight; otherwise it is shared between `Min`
+/// and `Max` parts. If the parts do not fit, they are shrunk from the last
+/// one backwards, with `Min` parts shrunk only as a last resort.
+pub fn solve(constraints: &[Constraint], available: u16) -> Vec<u16> {
+    let available = available as u32;
+    let mut sizes: Vec<u32> = constraints
+        .iter()
+        .map(|constraint| match *constraint {
+            Constraint::Length(length) | Constraint::Min(length) => length as u32,
+            Constraint::Percentage(percent) => available * percent.min(100) as u32 / 100,
+            Constraint::Ratio(_, 0) => 0,
+            Constraint::Ratio(numerator, denominator) => {
+                (available as u64 * numerator.min(denominator) as u64 / denominator as u64) as u32
+            }
+            Constraint::Max(_) | Constraint::Fill(_) => 0,
+        })
+        .collect();
+
+    let total: u32 = sizes.iter().sum();
+    if total > available {
+        let mut excess = total - available;
+        for shrink_min in [false, true] {
+            for (size, constraint) in sizes.iter_mut().zip(constraints).rev() {
+                if matches!(constraint, Constraint::Min(_)) == shrink_min {
+                    let cut = excess.min(*size);
+                    *size -= cut;
+                    excess -= cut;
+                }
+            }
+        }
+    } else {
+        let has_fill = constraints.iter().any(|c| matches!(c, Constraint::Fill(_)));
+        let mut remaining = available - total;
+        if has_fill {
+            let mut capped: Vec<(usize, u32, u32)> = constraints
+                .iter()
+                .enumerate()
+                .filter_map(|(i, constraint)| match *constraint {
+                    Constraint::Max(max) => Some((i, 1, max as u32)),
+                    _ => None,
+                })
+                .collect();
+            distribute(&mut sizes, &mut capped, remaining);
+            remaining = available - sizes.iter().sum::<u32>();
+        }
+        // (index, weight, cap)
+        let mut growable: Vec<(usize, u32, u32)> = constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(i, constraint)| match *constraint {
+                Constraint::Fill(weight) if has_fill => Some((i, weight.max(1) as u32, u32::MAX)),
+                Constraint::Min(_) if !has_fill => Some((i, 1, u32::MAX)),
+                Constraint::Max(max) if !has_fill => Some((i, 1, max as u32)),
+                _ => None,
+            })
+            .collect();
+        distribute(&mut sizes, &mut growable, remaining);
+    }
+
+    sizes.into_iter().map(|size| size as u16).collect()
+}
+
+// Shares `remaining` cells between the growable parts by weight without exceeding caps.
+fn distribute(sizes: &mut [u32], growable: &mut Vec<(usize, u32, u32)>, mut remaining: u32) {
+    while remaining > 0 && !growable.is_empty() {
+        let total_weight: u32 = growable.iter().map(|(_, weight, _)| weight).sum();
+        let mut given = 0;
+        for (index, weight, cap) in growable.iter() {
+            let share = (remaining as u64 * *weight as u64 / total_weight as u64) as u32;
+            let share = share.min(cap - sizes[*index]);
+            sizes[*index] += share;
+            given += share;
+        }
+        remaining -= given;
+        if given == 0 {
+            // Rounding left less than one cell per part: hand out single cells in order.
+            for (index, _, cap) in growable.iter() {
+                if remaining > 0 && sizes[*index] < *cap {
+                    sizes[*index] += 1;
+                    remaining -= 1;
+                }
+            }
+        }
+        growable.retain(|(index, _, cap)| sizes[*index] < *cap);
+    }
+}
@@ -20,6 +20,36 @@ pub mod star;
 pub mod triangle;
 pub mod line;
 pub mod circle;
+pub mod style;
+pub mod screen;
+pub mod markup;
+mod macros;
+pub mod gradient;
+pub mod theme;
+pub mod terminal;
+pub mod osc;
+pub mod ansi;
+pub mod width;
+pub mod grapheme;
+pub mod text;
+pub mod figlet;
+pub mod digits;
+pub mod geometry;
+pub mod region;
+pub mod layout;
+pub mod event;
+pub mod widget;
+pub mod input;
+pub mod readline;
+pub mod prompt;
+pub mod form;
+pub mod window;
+pub mod mirror;
+pub mod dialog;
+pub mod list;
+pub mod table;
+pub mod csv;
+pub mod tree;
 
 // Define static variables for viewport size
 static VIEWPORT_WIDTH: AtomicU16 = AtomicU16::new(80);
@@ -67,8 +97,16 @@ pub fn reset_color() {
     print("\x1B[0m");
 }
 
-/// Moves the cursor to the specified position in the terminal.
+/// Moves the cursor to the specified position in the terminal. Positions are 1-based and,
+/// while a region is active, relative to the region (see the `region` module).
 pub fn move_cursor_to(x: u16, y: u16) {
+    if x == 0 || y == 0 {
+        handle_boundary_error("Cursor position is outside viewport");
+        return;
+    }
+    if region::move_cursor(x, y) {
+        return;
+    }
     let (viewport_width, viewport_height) = get_viewport();
     if x > viewport_width || y > viewport_height {
         handle_boundary_error("Cursor position is outside viewport");
@@ -77,6 +115,11 @@ pub fn move_cursor_to(x: u16, y: u16) {
     print(&format!("\x1B[{};{}H", y, x));
 }
 
+/// Moves the cursor to the specified `Point`.
+pub fn move_cursor_to_point(point: Point) {
+    move_cursor_to(point.x, point.y);
+}
+
 /// Clears the entire screen and moves the cursor to the top-left corner.
 pub fn clear_screen() {
     print("\x1B[2J");
@@ -205,6 +248,9 @@ pub fn get_viewport() -> (u16, u16) {
 
 /// Writes the given text to the standard output and flushes the buffer.
 pub fn print(text: &str) {
+    let clipped = region::clip_output(text);
+    let text = clipped.as_deref().unwrap_or(text);
+    mirror::record(text);
     if let Err(e) = io::stdout().write_all(text.as_bytes()).and_then(|_| io::stdout().flush()) {
         handle_io_error(e);
     }
@@ -224,5 +270,11 @@ pub fn put_char(ch: char) {
 pub use line::{Line, Direction, LineStyle};
 pub use rbox::{BoxStyle, ShadeStyle};
 pub use circle::CircleSymbol;
+pub use style::{set_style, Style};
+pub use screen::ScreenBuffer;
+pub use markup::{print_markup, println_markup};
+pub use gradient::{Gradient, Rgb};
+pub use theme::{set_theme, Role, Theme};
+pub use geometry::{Offset, Point, Rect, Size};
 
 // You might want to add any new public functions or types here that are part of the main API
\ No newline at end of file
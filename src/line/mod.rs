@@ -3,6 +3,7 @@ use star::star_symbol_to_char;
 
 use crate::*;
 use crate::move_cursor_to;
+use crate::width::char_width;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum HorizontalLineStyle {
@@ -37,6 +38,11 @@ pub fn get_horizontal_line_char(style: &HorizontalLineStyle) -> char {
     }
 }
 
+/// Draws a horizontal line `size` columns long.
+///
+/// Wide line characters such as `HorizontalLineStyle::Wavy` take two columns each; any
+/// column left over at the end is filled with a space so the line always ends where
+/// expected.
 pub fn horizontal_line(x: u16, y: u16, size: usize, style: HorizontalLineStyle) {
     let (viewport_width, viewport_height) = get_viewport();
     if x >= viewport_width || y >= viewport_height {
@@ -49,11 +55,15 @@ pub fn horizontal_line(x: u16, y: u16, size: usize, style: HorizontalLineStyle)
     }
 
     let line_char = get_horizontal_line_char(&style);
+    let char_columns = char_width(line_char).max(1);
     move_cursor_to(x, y);
 
-    for _ in 0..size {
+    for _ in 0..size / char_columns {
         put_char(line_char);
     }
+    for _ in 0..size % char_columns {
+        put_char(' ');
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -190,19 +200,14 @@ pub fn get_vertice(v: &VerticeStyle) -> char {
     }
 }
 
-fn decrement(v: &mut u8) -> &mut u8 {
-    *v -= 1;
-    v
-}
-
-fn increment(v: &mut u8) -> &mut u8 {
-    *v += 1;
-    v
+/// Returns the number of columns a vertex occupies.
+pub fn get_vertice_width(v: &VerticeStyle) -> usize {
+    char_width(get_vertice(v))
 }
 
 pub struct Line {
-    pub x: u8,
-    pub y: u8,
+    pub x: u16,
+    pub y: u16,
     pub size: usize,
     pub style: LineStyle,
     pub direction: Direction,
@@ -237,106 +242,108 @@ impl Line {
         }
     }
 
-    pub fn draw(&self, show: bool) {
-        match self.direction {
-            Direction::East => {
-                draw(
-                    self,
-                    if show { get_horizontal_line_char(&self.style.hs) } else { ' ' },
-                    |a, _| { increment(a); },
-                );
-            }
-            Direction::West => {
-                draw(
-                    self,
-                    if show { get_horizontal_line_char(&self.style.hs) } else { ' ' },
-                    |a, _| { decrement(a); },
-                );
-            }
-            Direction::North => {
-                draw(
-                    self,
-                    if show { get_vertical_line_char(&self.style.vs) } else { ' ' },
-                    |_, b| { decrement(b); },
-                );
-            }
-            Direction::South => {
-                draw(
-                    self,
-                    if show { get_vertical_line_char(&self.style.vs) } else { ' ' },
-                    |_, b| { increment(b); },
-                );
-            }
-            Direction::NorthWest => {
-                draw(
-                    self,
-                    if show { get_diagonal_line_char(&DiagonalLineStyle::BackwardDiagonal) } else { ' ' },
-                    |a, b| { decrement(a); decrement(b); },
-                );
-            }
-            Direction::NorthEast => {
-                draw(
-                    self,
-                    if show { get_diagonal_line_char(&DiagonalLineStyle::ForwardDiagonal) } else { ' ' },
-                    |a, b| { increment(a); decrement(b); },
-                );
-            }
-            Direction::SouthWest => {
-                draw(
-                    self,
-                    if show { get_diagonal_line_char(&DiagonalLineStyle::ForwardDiagonal) } else { ' ' },
-                    |a, b| { decrement(a); increment(b); },
-                );
-            }
-            Direction::SouthEast => {
-                draw(
-                    self,
-                    if show { get_diagonal_line_char(&DiagonalLineStyle::BackwardDiagonal) } else { ' ' },
-                    |a, b| { increment(a); increment(b); },
-                );
-            }
-        }
+    /// Returns the position the line starts at.
+    pub fn start(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
 
-        fn draw(l: &Line, ch: char, op: fn(&mut u8, &mut u8)) {
-            let mut current_x = l.x;
-            let mut current_y = l.y;
-            let mut i = 0;
-            while i < l.size {
-                move_cursor_to(current_x as u16, current_y as u16);
-                if i == 0 && l.style.enabless {
-                    put_char(if ch != ' ' { get_vertice(&l.style.ss) } else { ' ' })
-                } else if i == l.size - 1 && l.style.enablees {
-                    put_char(if ch != ' ' { get_vertice(&l.style.es) } else { ' ' });
+    /// Returns the position of the last cell the line covers, or `None` if no cell is
+    /// visible.
+    pub fn end(&self) -> Option<Point> {
+        self.cells().last().map(|(x, y, _)| Point::new(*x, *y))
+    }
+
+    /// Returns the smallest rectangle containing every cell the line covers.
+    pub fn bounds(&self) -> Rect {
+        self.cells().iter().fold(Rect::new(self.x, self.y, 0, 0), |bounds, (x, y, ch)| {
+            let width = char_width(*ch).max(1) as u16;
+            bounds.union(&Rect::new(*x, *y, width, 1))
+        })
+    }
+
+    /// Returns the position and character of every cell the line covers, in drawing order.
+    ///
+    /// Horizontal lines advance by the width of each character, so a wide vertex such as
+    /// `CircleSymbol::RedCircle` does not have its right half overwritten by the line.
+    pub fn cells(&self) -> Vec<(u16, u16, char)> {
+        let (ch, dx, dy): (char, i32, i32) = match self.direction {
+            Direction::East => (get_horizontal_line_char(&self.style.hs), 1, 0),
+            Direction::West => (get_horizontal_line_char(&self.style.hs), -1, 0),
+            Direction::North => (get_vertical_line_char(&self.style.vs), 0, -1),
+            Direction::South => (get_vertical_line_char(&self.style.vs), 0, 1),
+            Direction::NorthWest => (get_diagonal_line_char(&DiagonalLineStyle::BackwardDiagonal), -1, -1),
+            Direction::NorthEast => (get_diagonal_line_char(&DiagonalLineStyle::ForwardDiagonal), 1, -1),
+            Direction::SouthWest => (get_diagonal_line_char(&DiagonalLineStyle::ForwardDiagonal), -1, 1),
+            Direction::SouthEast => (get_diagonal_line_char(&DiagonalLineStyle::BackwardDiagonal), 1, 1),
+        };
+
+        let chars: Vec<char> = (0..self.size)
+            .map(|i| {
+                if i == 0 && self.style.enabless {
+                    get_vertice(&self.style.ss)
+                } else if i == self.size - 1 && self.style.enablees {
+                    get_vertice(&self.style.es)
                 } else {
-                    put_char(ch);
+                    ch
                 }
-                op(&mut current_x, &mut current_y);
-                i += 1;
+            })
+            .collect();
+
+        let mut cells = Vec::with_capacity(self.size);
+        let mut current_x = self.x as i32;
+        let mut current_y = self.y as i32;
+        for (i, cell_char) in chars.iter().enumerate() {
+            if current_x < 1 || current_y < 1 {
+                break;
             }
+            cells.push((current_x as u16, current_y as u16, *cell_char));
+            let step_x = match (dx, dy) {
+                (1, 0) => char_width(*cell_char).max(1) as i32,
+                (-1, 0) => -(chars.get(i + 1).map_or(1, |next| char_width(*next).max(1)) as i32),
+                _ => dx,
+            };
+            current_x += step_x;
+            current_y += dy;
         }
+        cells
+    }
+
+    pub fn draw(&self, show: bool) {
+        for (x, y, ch) in self.cells() {
+            move_cursor_to(x, y);
+            put_char(if show { ch } else { ' ' });
+        }
+    }
+}
+
+impl Default for Line {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 pub trait Shape {
     fn show(&self, time: Option<u8>);
     fn hide(&self);
-    fn move_to(&mut self, x: u8, y: u8);
+    fn move_to(&mut self, x: u16, y: u16);
+
+    /// Moves the shape so that it starts at the given point.
+    fn move_to_point(&mut self, point: Point) {
+        self.move_to(point.x, point.y);
+    }
 }
 
 impl Shape for Line {
-    fn move_to(&mut self, x: u8, y: u8) {
+    fn move_to(&mut self, x: u16, y: u16) {
         self.x = x;
         self.y = y;
     }
 
     fn show(&self, time: Option<u8>) {
         self.draw(true);
-        match time {
-            Some(x) => {
-                wait_for_seconds(x as u64);
-                self.hide();
-            },
-            None => {}
+        if let Some(x) = time {
+            wait_for_seconds(x as u64);
+            self.hide();
         }
     }
 
@@ -0,0 +1,617 @@
+//! This module provides a scrollable list widget for long lists of items.
+//!
+//! Items come from a `ListSource`, which is only asked for the items that are visible, so
+//! a list can show millions of generated rows as cheaply as a dozen. The list keeps a
+//! cursor, an optional single or multiple selection, a scrollbar drawn with box-drawing or
+//! block characters and an incremental search: typing jumps to the next item containing
+//! the typed text.
+//!
+//! Up, Down, PageUp, PageDown, Home and End move the cursor. Space toggles the item under
+//! the cursor in `SelectionMode::Multiple`, and Enter selects it in `SelectionMode::Single`.
+//! While searching, Ctrl-N jumps to the next match, Backspace shortens the search and Esc
+//! ends it. The mouse wheel scrolls, a click moves the cursor and a click or drag on the
+//! scrollbar jumps through the list.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::list::{FnSource, List};
+//!
+//! let mut list = List::new(FnSource::new(1_000_000, |i| format!("Row {}", i + 1)));
+//! if let Some(selected) = list.run(Rect::new(1, 1, 30, 10)) {
+//!     println(&format!("Picked {:?}", selected));
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use crate::ansi::truncate;
+use crate::event::{read_key_event, Event, KeyCode, KeyEvent, MouseButton, MouseKind};
+use crate::geometry::{Rect, Size};
+use crate::rbox::{block_char_to_char, double_box_to_char, single_box_to_char, BlockChar, DoubleBox, SingleBox};
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::theme::{role_style, Role};
+use crate::triangle::{triangle_symbol_to_char, TriangleSymbol};
+use crate::widget::{EventResult, Widget};
+use crate::width::str_width;
+use crate::*;
+
+/// Supplies the items of a `List` on demand.
+pub trait ListSource {
+    /// Returns the number of items.
+    fn len(&self) -> usize;
+
+    /// Returns the text of an item. Only called for items that are drawn or searched.
+    fn item(&self, index: usize) -> String;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Display> ListSource for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn item(&self, index: usize) -> String {
+        self[index].to_string()
+    }
+}
+
+/// A list source that generates each item with a function.
+pub struct FnSource<F> {
+    pub len: usize,
+    pub item: F,
+}
+
+impl<F: Fn(usize) -> String> FnSource<F> {
+    pub fn new(len: usize, item: F) -> Self {
+        FnSource { len, item }
+    }
+}
+
+impl<F: Fn(usize) -> String> ListSource for FnSource<F> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn item(&self, index: usize) -> String {
+        (self.item)(index)
+    }
+}
+
+// The most items listed when input is not a terminal.
+const FALLBACK_ITEMS: usize = 100;
+
+/// How many items can be selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// Only the cursor matters; nothing is marked as selected.
+    None,
+    /// Enter selects the item under the cursor.
+    #[default]
+    Single,
+    /// Space toggles any number of items.
+    Multiple,
+}
+
+/// How the scrollbar is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollbarStyle {
+    /// A thin `│` track with a `║` thumb.
+    #[default]
+    Line,
+    /// A `░` track with a `█` thumb.
+    Block,
+    /// No scrollbar.
+    Hidden,
+}
+
+/// A scrollable, searchable list of items from a `ListSource`.
+pub struct List {
+    source: Box<dyn ListSource>,
+    pub selection_mode: SelectionMode,
+    /// Drawn beside the item under the cursor.
+    pub pointer: TriangleSymbol,
+    /// Drawn beside selected items.
+    pub selected_marker: TriangleSymbol,
+    /// The style of the item under the cursor while the list has focus.
+    pub highlight: Style,
+    /// The style of selected items.
+    pub selected_style: Style,
+    pub scrollbar: ScrollbarStyle,
+    cursor: usize,
+    selected: BTreeSet<usize>,
+    search: String,
+    // Where the cursor was when the search started, so Backspace can search again from there.
+    search_origin: usize,
+    // Whether some item contains the query, worked out when the search changes so drawing
+    // does not have to fetch every item.
+    search_found: bool,
+    focused: bool,
+    // True while the scrollbar thumb is being dragged.
+    dragging: bool,
+    submitted: bool,
+    cancelled: bool,
+    // The first visible item and the area of the last render.
+    offset: Cell<usize>,
+    area: Cell<Rect>,
+}
+
+impl List {
+    pub fn new(source: impl ListSource + 'static) -> Self {
+        List {
+            source: Box::new(source),
+            selection_mode: SelectionMode::Single,
+            pointer: TriangleSymbol::SmallBlackRightPointing,
+            selected_marker: TriangleSymbol::RightPointingTriangle,
+            highlight: role_style(Role::Selection),
+            selected_style: role_style(Role::Accent),
+            scrollbar: ScrollbarStyle::Line,
+            cursor: 0,
+            selected: BTreeSet::new(),
+            search: String::new(),
+            search_origin: 0,
+            search_found: false,
+            focused: false,
+            dragging: false,
+            submitted: false,
+            cancelled: false,
+            offset: Cell::new(0),
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    pub fn selection_mode(mut self, selection_mode: SelectionMode) -> Self {
+        self.selection_mode = selection_mode;
+        self
+    }
+
+    pub fn pointer(mut self, pointer: TriangleSymbol) -> Self {
+        self.pointer = pointer;
+        self
+    }
+
+    pub fn selected_marker(mut self, selected_marker: TriangleSymbol) -> Self {
+        self.selected_marker = selected_marker;
+        self
+    }
+
+    pub fn highlight(mut self, highlight: Style) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    pub fn selected_style(mut self, selected_style: Style) -> Self {
+        self.selected_style = selected_style;
+        self
+    }
+
+    pub fn scrollbar(mut self, scrollbar: ScrollbarStyle) -> Self {
+        self.scrollbar = scrollbar;
+        self
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    /// Returns the index of the item under the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor; the list scrolls to it on the next render.
+    pub fn set_cursor(&mut self, index: usize) {
+        self.cursor = index.min(self.len().saturating_sub(1));
+    }
+
+    /// Returns the first visible item.
+    pub fn offset(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Returns the indices of the selected items in ascending order.
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Selects an item, replacing the selection in `SelectionMode::Single`.
+    pub fn select(&mut self, index: usize) {
+        match self.selection_mode {
+            SelectionMode::None => {}
+            SelectionMode::Single => self.selected = BTreeSet::from([index]),
+            SelectionMode::Multiple => {
+                self.selected.insert(index);
+            }
+        }
+    }
+
+    pub fn deselect(&mut self, index: usize) {
+        self.selected.remove(&index);
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Returns the text typed so far in an incremental search.
+    pub fn search_query(&self) -> &str {
+        &self.search
+    }
+
+    /// Returns true once Enter has been pressed.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Returns true once Esc or Ctrl-C has been pressed outside a search.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Clears the submitted and cancelled flags.
+    pub fn reset_state(&mut self) {
+        self.submitted = false;
+        self.cancelled = false;
+    }
+
+    // The number of item rows in the last rendered area.
+    fn rows(&self) -> usize {
+        let height = self.area.get().height as usize;
+        if self.search.is_empty() {
+            height
+        } else {
+            height.saturating_sub(1)
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let last = self.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + delta).clamp(0, last.max(0)) as usize;
+    }
+
+    // Scrolls so the cursor is visible in `rows` rows.
+    fn scroll_to_cursor(&self, rows: usize) {
+        let mut offset = self.offset.get().min(self.len().saturating_sub(rows));
+        if self.cursor < offset {
+            offset = self.cursor;
+        } else if rows > 0 && self.cursor >= offset + rows {
+            offset = self.cursor + 1 - rows;
+        }
+        self.offset.set(offset);
+    }
+
+    // Finds the first item at or after `start`, wrapping around, that contains the query.
+    fn find(&self, start: usize) -> Option<usize> {
+        let count = self.len();
+        let query = self.search.to_lowercase();
+        (0..count).map(|i| (start + i) % count).find(|&i| self.source.item(i).to_lowercase().contains(&query))
+    }
+
+    fn search_from(&mut self, start: usize) {
+        let found = self.find(start);
+        if let Some(index) = found {
+            self.cursor = index;
+        }
+        self.search_found = found.is_some();
+    }
+
+    /// Reacts to a key. Returns false if the key was not used.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let page = self.rows().max(1) as isize;
+        if !self.search.is_empty() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.search.clear();
+                    return true;
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                    if !self.search.is_empty() {
+                        self.search_from(self.search_origin);
+                    }
+                    return true;
+                }
+                _ if key.is_ctrl('n') => {
+                    self.search_from(self.cursor + 1);
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        if let Some(ch) = key.printable() {
+            if ch != ' ' || !self.search.is_empty() || self.selection_mode != SelectionMode::Multiple {
+                if self.search.is_empty() {
+                    self.search_origin = self.cursor;
+                }
+                self.search.push(ch);
+                self.search_from(self.search_origin);
+                return true;
+            }
+        }
+        match key.code {
+            KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Down => self.move_cursor(1),
+            KeyCode::PageUp => self.move_cursor(-page),
+            KeyCode::PageDown => self.move_cursor(page),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len().saturating_sub(1),
+            KeyCode::Char(' ') if self.selection_mode == SelectionMode::Multiple && !self.is_empty() => {
+                if !self.selected.remove(&self.cursor) {
+                    self.selected.insert(self.cursor);
+                }
+            }
+            KeyCode::Enter => {
+                if self.selection_mode == SelectionMode::Single && !self.is_empty() {
+                    self.select(self.cursor);
+                }
+                self.submitted = true;
+            }
+            KeyCode::Esc => self.cancelled = true,
+            _ if key.is_ctrl('c') => self.cancelled = true,
+            _ => return false,
+        }
+        self.search.clear();
+        true
+    }
+
+    // Returns the column of the scrollbar, if one is shown.
+    fn scrollbar_column(&self, area: Rect, rows: usize) -> Option<u16> {
+        (self.scrollbar != ScrollbarStyle::Hidden && rows > 0 && self.len() > rows && area.width > 3)
+            .then(|| area.right() - 1)
+    }
+
+    fn handle_mouse(&mut self, kind: MouseKind, x: u16, y: u16) -> bool {
+        let area = self.area.get();
+        if let MouseKind::Press(_) = kind {
+            if area.contains(Point::new(x, y)) {
+                self.search.clear();
+            }
+        }
+        let rows = self.rows();
+        let scrollbar = self.scrollbar_column(area, rows);
+        let inside = area.contains(Point::new(x, y)) && ((y - area.top()) as usize) < rows;
+        let max_offset = self.len().saturating_sub(rows);
+        let offset = match kind {
+            MouseKind::ScrollUp if inside => self.offset.get().saturating_sub(3),
+            MouseKind::ScrollDown if inside => self.offset.get() + 3,
+            MouseKind::Press(MouseButton::Left) if inside && scrollbar == Some(x) => {
+                self.dragging = true;
+                self.scrollbar_offset(y)
+            }
+            MouseKind::Drag(MouseButton::Left) if self.dragging => self.scrollbar_offset(y),
+            MouseKind::Release(_) if self.dragging => {
+                self.dragging = false;
+                return true;
+            }
+            MouseKind::Press(MouseButton::Left) if inside => {
+                let index = self.offset.get() + (y - area.top()) as usize;
+                if index < self.len() {
+                    self.cursor = index;
+                    match self.selection_mode {
+                        SelectionMode::Multiple if !self.selected.remove(&index) => {
+                            self.selected.insert(index);
+                        }
+                        SelectionMode::Single => self.select(index),
+                        _ => {}
+                    }
+                }
+                self.search.clear();
+                return true;
+            }
+            _ => return false,
+        };
+        // Scroll the view and keep the cursor inside it.
+        let offset = offset.min(max_offset);
+        self.offset.set(offset);
+        self.cursor = self.cursor.clamp(offset, (offset + rows).saturating_sub(1).max(offset));
+        self.set_cursor(self.cursor);
+        self.search.clear();
+        true
+    }
+
+    // Returns the first visible item for a click or drag at row `y` of the scrollbar.
+    fn scrollbar_offset(&self, y: u16) -> usize {
+        let area = self.area.get();
+        let rows = self.rows();
+        let row = y.clamp(area.top(), area.top() + rows.max(1) as u16 - 1) - area.top();
+        row as usize * self.len().saturating_sub(rows) / rows.saturating_sub(1).max(1)
+    }
+
+    /// Draws the visible items into `area` of a buffer. `active` highlights the cursor.
+    pub fn draw(&self, area: Rect, buffer: &mut ScreenBuffer, active: bool) {
+        self.area.set(area);
+        if area.is_empty() {
+            return;
+        }
+        let rows = self.rows();
+        self.scroll_to_cursor(rows);
+        let offset = self.offset.get();
+        let scrollbar = self.scrollbar_column(area, rows);
+        let text_width = area.width.saturating_sub(2 + u16::from(scrollbar.is_some())) as usize;
+
+        for row in 0..rows {
+            let index = offset + row;
+            if index >= self.len() {
+                break;
+            }
+            let y = area.y + row as u16;
+            let is_cursor = index == self.cursor;
+            let is_selected = self.selected.contains(&index);
+            let style = match (is_cursor && active, is_selected) {
+                (true, _) => self.highlight,
+                (false, true) => self.selected_style,
+                (false, false) => Style::default(),
+            };
+            let marker = if is_cursor {
+                triangle_symbol_to_char(self.pointer)
+            } else if is_selected {
+                triangle_symbol_to_char(self.selected_marker)
+            } else {
+                ' '
+            };
+            let width = area.width - u16::from(scrollbar.is_some());
+            buffer.fill(Rect::new(area.x, y, width, 1), ' ', style);
+            buffer.set_char(area.x, y, marker, style);
+            buffer.put_str(area.x + 2, y, &truncate(&self.source.item(index), text_width), style);
+        }
+
+        // The thumb maths needs at least one item row.
+        if let (Some(x), true) = (scrollbar, rows > 0) {
+            let (track, thumb) = match self.scrollbar {
+                ScrollbarStyle::Block => (block_char_to_char(BlockChar::LightShade), block_char_to_char(BlockChar::Full)),
+                _ => (single_box_to_char(SingleBox::Vertical), double_box_to_char(DoubleBox::Vertical)),
+            };
+            let count = self.len();
+            let thumb_size = (rows * rows / count).clamp(1, rows);
+            let max_offset = count - rows;
+            let thumb_start = offset * (rows - thumb_size) / max_offset.max(1);
+            let style = role_style(Role::Border);
+            for row in 0..rows {
+                let in_thumb = (thumb_start..thumb_start + thumb_size).contains(&row);
+                buffer.set_char(x, area.y + row as u16, if in_thumb { thumb } else { track }, style);
+            }
+        }
+
+        if !self.search.is_empty() {
+            let role = if self.search_found { Role::Accent } else { Role::Error };
+            let y = area.bottom() - 1;
+            buffer.fill(Rect::new(area.x, y, area.width, 1), ' ', Style::default());
+            let text = truncate(&format!("/{}", self.search), area.width as usize);
+            buffer.put_str(area.x, y, &text, role_style(role));
+        }
+    }
+
+    /// Shows the list in `area` until Enter or Esc is pressed.
+    ///
+    /// Returns the selected indices, which in `SelectionMode::Single` is the item chosen
+    /// with Enter and in `SelectionMode::None` the item under the cursor, or `None` if the
+    /// list was cancelled. If standard input is not a
+    /// terminal, prints the items and reads their numbers from a line instead.
+    pub fn run(&mut self, area: Rect) -> Option<Vec<usize>> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            return self.run_lines();
+        }
+
+        self.reset_state();
+        hide_cursor();
+        let mut previous: Option<ScreenBuffer> = None;
+        let result = loop {
+            let mut buffer = ScreenBuffer::new(area.width, area.height);
+            self.draw(buffer.area(), &mut buffer, true);
+            match &previous {
+                Some(previous) => buffer.render_diff_at(previous, area.x, area.y),
+                None => buffer.render_at(area.x, area.y),
+            }
+            previous = Some(buffer);
+
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            self.handle_key(&key);
+            if self.submitted {
+                if self.selection_mode == SelectionMode::None {
+                    break Some(vec![self.cursor]);
+                }
+                break Some(self.selected());
+            }
+            if self.cancelled {
+                break None;
+            }
+        };
+        show_cursor();
+
+        if !was_raw {
+            disable_raw_mode();
+        }
+        result
+    }
+
+    // Lists the items and reads a line of item numbers, for when input is not a terminal.
+    fn run_lines(&mut self) -> Option<Vec<usize>> {
+        let count = self.len();
+        let digits = count.to_string().len();
+        for index in 0..count.min(FALLBACK_ITEMS) {
+            println(&format!("{:>width$}) {}", index + 1, self.source.item(index), width = digits));
+        }
+        if count > FALLBACK_ITEMS {
+            println(&format!("... and {} more", count - FALLBACK_ITEMS));
+        }
+        let mut line = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(e) => {
+                handle_io_error(e);
+                return None;
+            }
+        }
+        let numbers = line.split(|ch: char| ch == ',' || ch.is_whitespace()).filter(|part| !part.is_empty());
+        let indices: Vec<usize> = numbers
+            .filter_map(|part| part.parse::<usize>().ok())
+            .filter(|number| (1..=count).contains(number))
+            .map(|number| number - 1)
+            .collect();
+        match self.selection_mode {
+            SelectionMode::Multiple => indices.iter().for_each(|&index| self.select(index)),
+            _ => {
+                if let Some(&index) = indices.first() {
+                    self.cursor = index;
+                    self.select(index);
+                }
+            }
+        }
+        Some(self.selected())
+    }
+}
+
+impl Widget for List {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        self.draw(area, buffer, self.focused);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let shown = self.len().min(available.height as usize);
+        let widest = (0..shown).map(|i| str_width(&self.source.item(i))).max().unwrap_or(0);
+        Size::new(((widest + 3) as u16).min(available.width), shown as u16)
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            // Leave Tab for moving focus, and Esc and Ctrl-C for the application.
+            Event::Key(key) if matches!(key.code, KeyCode::Tab | KeyCode::BackTab) => EventResult::Ignored,
+            Event::Key(key) if (key.code == KeyCode::Esc && self.search.is_empty()) || key.is_ctrl('c') => {
+                EventResult::Ignored
+            }
+            Event::Key(key) if self.handle_key(key) => EventResult::Consumed,
+            Event::Mouse(mouse) if self.handle_mouse(mouse.kind, mouse.x, mouse.y) => EventResult::Consumed,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.search.clear();
+        }
+    }
+}
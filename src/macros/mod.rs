@@ -0,0 +1,56 @@
+//! This module provides the `cprint!` and `cprintln!` macros for styled formatted output.
+
+/// Prints formatted text with a style, then resets the terminal style.
+///
+/// Style directives come first, separated by commas and ended with `;`. Each directive is
+/// anything that converts into a `Style`: a `Color` sets the foreground, an `Attribute`
+/// switches that attribute on, and a `Style` is layered on as is. The rest of the arguments
+/// are passed to `format!`. Without directives the text is printed unstyled.
+///
+/// ```no_run
+/// use rpian_terminal::*;
+///
+/// let name = "Asha";
+/// cprint!(Color::Green, Attribute::Bright; "Hello, {}! ", name);
+/// cprint!(Style::new().fg(Color::White).bg(Color::Blue); "{} new messages", 3);
+/// ```
+#[macro_export]
+macro_rules! cprint {
+    ($fmt:literal) => {
+        $crate::print(&format!($fmt))
+    };
+    ($fmt:literal, $($arg:tt)*) => {
+        $crate::print(&format!($fmt, $($arg)*))
+    };
+    ($($directive:expr),+ ; $($arg:tt)+) => {{
+        let style = $crate::Style::new()$(.patch($crate::Style::from($directive)))+;
+        $crate::print(&format!("{}{}\x1B[0m", style.to_ansi(), format!($($arg)+)))
+    }};
+}
+
+/// Prints formatted text with a style, resets the terminal style and ends the line.
+///
+/// Takes the same arguments as `cprint!`.
+///
+/// ```no_run
+/// use rpian_terminal::*;
+///
+/// cprintln!(Color::Red, Attribute::Underscore; "{} errors found", 2);
+/// cprintln!();
+/// ```
+#[macro_export]
+macro_rules! cprintln {
+    () => {
+        $crate::println("")
+    };
+    ($fmt:literal) => {
+        $crate::println(&format!($fmt))
+    };
+    ($fmt:literal, $($arg:tt)*) => {
+        $crate::println(&format!($fmt, $($arg)*))
+    };
+    ($($directive:expr),+ ; $($arg:tt)+) => {{
+        let style = $crate::Style::new()$(.patch($crate::Style::from($directive)))+;
+        $crate::println(&format!("{}{}\x1B[0m", style.to_ansi(), format!($($arg)+)))
+    }};
+}
@@ -0,0 +1,260 @@
+//! This module implements a small inline markup language for styled output.
+//!
+//! Text between `[tag]` and `[/]` is drawn with the style described by the tag, e.g.
+//! `"[bold red]Error:[/] file [underline]notes.txt[/] missing"`. A tag is a list of
+//! space-separated words:
+//!
+//! * a color name (`black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`)
+//!   sets the foreground color,
+//! * `on` followed by a color name sets the background color,
+//! * an attribute name (`bold`/`bright`, `dim`, `underline`/`underscore`, `blink`,
+//!   `reverse`, `hidden`) switches that attribute on,
+//! * `reset` starts from the terminal defaults instead of the outer style.
+//!
+//! Tags nest: an inner tag adds to the style of the outer one, and closing it restores the
+//! outer style. `[/]` closes the innermost tag; `[/bold red]` does the same but also checks
+//! that the innermost tag was `[bold red]`. A literal `[` is written as `\[` and a literal
+//! backslash as `\\`; any other backslash is kept as it is.
+
+use std::fmt;
+
+use crate::error::handle_parse_error;
+use crate::screen::ScreenBuffer;
+use crate::style::{attribute_from_name, color_from_name, Style};
+use crate::*;
+
+/// A run of text that shares a single style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// Errors that can occur while parsing markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A tag contained a word that is not a color or attribute name.
+    UnknownTag(String),
+    /// A closing tag was found with no open tag to close.
+    UnexpectedClose(String),
+    /// A named closing tag did not match the innermost open tag.
+    MismatchedClose { expected: String, found: String },
+    /// A tag was opened with `[` but never finished with `]`.
+    UnterminatedTag,
+    /// The text ended while tags were still open.
+    UnclosedTag(String),
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkupError::UnknownTag(tag) => write!(f, "unknown markup tag '{}'", tag),
+            MarkupError::UnexpectedClose(tag) => write!(f, "closing tag '[/{}]' has no matching open tag", tag),
+            MarkupError::MismatchedClose { expected, found } => {
+                write!(f, "closing tag '[/{}]' does not match open tag '[{}]'", found, expected)
+            }
+            MarkupError::UnterminatedTag => write!(f, "markup tag is missing its closing ']'"),
+            MarkupError::UnclosedTag(tag) => write!(f, "markup tag '[{}]' is never closed", tag),
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// Parses a single tag body such as `"bold red on white"` into a style.
+pub fn parse_tag(tag: &str) -> Result<Style, MarkupError> {
+    let mut style = Style::new();
+    let mut words = tag.split_whitespace();
+    while let Some(word) = words.next() {
+        if word.eq_ignore_ascii_case("on") {
+            let color = words
+                .next()
+                .and_then(color_from_name)
+                .ok_or_else(|| MarkupError::UnknownTag(tag.to_string()))?;
+            style = style.bg(color);
+        } else if let Some(color) = color_from_name(word) {
+            style = style.fg(color);
+        } else if let Some(attribute) = attribute_from_name(word) {
+            style = style.attribute(attribute);
+        } else {
+            return Err(MarkupError::UnknownTag(word.to_string()));
+        }
+    }
+    Ok(style)
+}
+
+/// Parses markup into a list of styled spans, starting from the default style.
+pub fn parse_markup(markup: &str) -> Result<Vec<Span>, MarkupError> {
+    parse_markup_with_base(markup, Style::default())
+}
+
+/// Parses markup into a list of styled spans. Tags are layered on top of `base`.
+pub fn parse_markup_with_base(markup: &str, base: Style) -> Result<Vec<Span>, MarkupError> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut stack: Vec<(String, Style)> = Vec::new();
+    let mut text = String::new();
+    let mut chars = markup.chars().peekable();
+
+    fn flush(spans: &mut Vec<Span>, text: &mut String, style: Style) {
+        if text.is_empty() {
+            return;
+        }
+        match spans.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(text),
+            _ => spans.push(Span { text: text.clone(), style }),
+        }
+        text.clear();
+    }
+
+    while let Some(ch) = chars.next() {
+        let current = stack.last().map(|(_, style)| *style).unwrap_or(base);
+        match ch {
+            '\\' => match chars.next_if(|next| *next == '[' || *next == '\\') {
+                Some(escaped) => text.push(escaped),
+                None => text.push('\\'),
+            },
+            '[' => {
+                let mut tag = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(MarkupError::UnterminatedTag),
+                    }
+                }
+                flush(&mut spans, &mut text, current);
+                let tag = tag.trim();
+                if let Some(name) = tag.strip_prefix('/') {
+                    let name = name.trim();
+                    match stack.pop() {
+                        None => return Err(MarkupError::UnexpectedClose(name.to_string())),
+                        Some((open, _)) if !name.is_empty() && !same_tag(&open, name) => {
+                            return Err(MarkupError::MismatchedClose {
+                                expected: open,
+                                found: name.to_string(),
+                            })
+                        }
+                        Some(_) => {}
+                    }
+                } else {
+                    let tag_style = parse_tag(tag)?;
+                    let style = if tag.split_whitespace().any(|word| word.eq_ignore_ascii_case("reset")) {
+                        tag_style
+                    } else {
+                        current.patch(tag_style)
+                    };
+                    stack.push((tag.to_string(), style));
+                }
+            }
+            _ => text.push(ch),
+        }
+    }
+
+    if let Some((open, _)) = stack.pop() {
+        return Err(MarkupError::UnclosedTag(open));
+    }
+    flush(&mut spans, &mut text, base);
+    Ok(spans)
+}
+
+fn same_tag(a: &str, b: &str) -> bool {
+    a.split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .eq(b.split_whitespace().map(str::to_ascii_lowercase))
+}
+
+/// Escapes `[` and `\` so the text is printed literally when used inside markup.
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch == '[' || ch == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Converts markup into a string containing the equivalent escape sequences.
+///
+/// The returned string always ends by resetting the terminal style.
+pub fn markup_to_ansi(markup: &str) -> Result<String, MarkupError> {
+    let mut output = String::new();
+    for span in parse_markup(markup)? {
+        output.push_str(&span.style.to_ansi());
+        output.push_str(&span.text);
+    }
+    output.push_str("\x1B[0m");
+    Ok(output)
+}
+
+/// Writes markup to the standard output.
+///
+/// Markup errors are reported through the global error handler and nothing is printed.
+pub fn print_markup(markup: &str) {
+    match markup_to_ansi(markup) {
+        Ok(text) => print(&text),
+        Err(e) => handle_parse_error(&e.to_string()),
+    }
+}
+
+/// Writes markup to the standard output, followed by a newline.
+pub fn println_markup(markup: &str) {
+    match markup_to_ansi(markup) {
+        Ok(text) => println(&text),
+        Err(e) => handle_parse_error(&e.to_string()),
+    }
+}
+
+impl ScreenBuffer {
+    /// Draws markup into the buffer on a single row starting at the given position.
+    ///
+    /// Returns the column just after the last character written. Markup errors are
+    /// reported through the global error handler and nothing is drawn.
+    pub fn draw_markup(&mut self, x: u16, y: u16, markup: &str) -> u16 {
+        match parse_markup(markup) {
+            Ok(spans) => spans
+                .iter()
+                .fold(x, |column, span| self.put_str(column, y, &span.text, span.style)),
+            Err(e) => {
+                handle_parse_error(&e.to_string());
+                x
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_text(markup: &str) -> String {
+        parse_markup(markup).unwrap().into_iter().map(|span| span.text).collect()
+    }
+
+    #[test]
+    fn only_brackets_and_backslashes_are_escaped() {
+        assert_eq!(plain_text(r"\[red] and \\"), r"[red] and \");
+        assert_eq!(plain_text(r"C:\path\to"), r"C:\path\to");
+        assert_eq!(plain_text(r"trailing \"), r"trailing \");
+    }
+
+    #[test]
+    fn escaped_text_round_trips() {
+        let text = r"[x] C:\dir\ [y]";
+        assert_eq!(plain_text(&escape_markup(text)), text);
+    }
+
+    #[test]
+    fn reset_replaces_the_base_and_outer_styles() {
+        let base = Style::new().fg(Color::Red).attribute(Attribute::Bright);
+        let spans = parse_markup_with_base("a[underline]b[reset]c[/][/]", base).unwrap();
+        assert_eq!(spans[0].style, base);
+        assert_eq!(spans[1].style, base.attribute(Attribute::Underscore));
+        assert_eq!(spans[2].text, "c");
+        assert_eq!(spans[2].style, Style::default());
+
+        let spans = parse_markup_with_base("[reset blue]x[/]", base).unwrap();
+        assert_eq!(spans[0].style, Style::new().fg(Color::Blue));
+    }
+}
@@ -103,3 +103,10 @@ pub fn math_symbol_to_char(symbol: MathSymbol) -> char {
         MathSymbol::Proportional => '∝',
     }
 }
+
+impl MathSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(math_symbol_to_char(*self))
+    }
+}
@@ -0,0 +1,330 @@
+//! This module keeps a copy of what has been drawn on the screen, so that part of the
+//! screen can be saved and put back later, for example under a dialog.
+//!
+//! Everything written with `print` (and therefore by every drawing function in the crate)
+//! is replayed into an in-memory screen buffer: text, cursor movement, clearing, colors
+//! and attributes, scrolling at the bottom of the viewport and switching to and from the
+//! alternate screen. Only what this crate printed is known; the mirror starts out blank,
+//! so clear the screen first if it must match the terminal exactly. 256-color and RGB
+//! colors are not kept.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::mirror::save_area;
+//!
+//! let saved = save_area(Rect::new(10, 5, 30, 6));
+//! rbox::draw_box(10, 5, 30, 6, BoxStyle::Double);
+//! wait_for_seconds(1);
+//! saved.restore();
+//! ```
+
+use std::sync::Mutex;
+
+use crate::ansi::{segments, Segment};
+use crate::geometry::{Point, Rect};
+use crate::grapheme::grapheme_width;
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::*;
+
+struct Mirror {
+    buffer: ScreenBuffer,
+    // 1-based. The column may be one past the right edge after writing the last column.
+    cursor: Point,
+    style: Style,
+    saved_cursor: Point,
+    // The main screen while the alternate screen is in use.
+    main: Option<(ScreenBuffer, Point)>,
+}
+
+static MIRROR: Mutex<Option<Mirror>> = Mutex::new(None);
+
+impl Mirror {
+    fn new() -> Self {
+        Mirror {
+            buffer: ScreenBuffer::from_viewport(),
+            cursor: Point::ORIGIN,
+            style: Style::default(),
+            saved_cursor: Point::ORIGIN,
+            main: None,
+        }
+    }
+
+    // Follows changes to the viewport size, keeping the top-left part of the screen and
+    // the cursors on it.
+    fn fit_viewport(&mut self) {
+        let (width, height) = get_viewport();
+        if self.buffer.width() != width || self.buffer.height() != height {
+            self.buffer = self.buffer.copy_area(Rect::new(1, 1, width, height));
+        }
+        let fit = |point: Point| Point::new(point.x.clamp(1, width.saturating_add(1)), point.y.clamp(1, height.max(1)));
+        self.cursor = fit(self.cursor);
+        self.saved_cursor = fit(self.saved_cursor);
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        let x = x.clamp(1, self.buffer.width().max(1) as i32);
+        let y = y.clamp(1, self.buffer.height().max(1) as i32);
+        self.cursor = Point::new(x as u16, y as u16);
+    }
+
+    fn line_feed(&mut self) {
+        if self.cursor.y < self.buffer.height() {
+            self.cursor.y += 1;
+        } else {
+            let (width, height) = (self.buffer.width(), self.buffer.height());
+            self.buffer = self.buffer.copy_area(Rect::new(1, 2, width, height));
+        }
+    }
+
+    fn clear(&mut self, area: Rect) {
+        self.buffer.fill(area, ' ', Style::default());
+    }
+
+    fn write(&mut self, cluster: &str) {
+        let width = grapheme_width(cluster) as u16;
+        if width == 0 {
+            return;
+        }
+        if self.cursor.x + width - 1 > self.buffer.width() {
+            self.cursor.x = 1;
+            self.line_feed();
+        }
+        self.buffer.set_grapheme(self.cursor.x, self.cursor.y, cluster, self.style);
+        self.cursor.x += width;
+    }
+
+    fn control(&mut self, ch: char) {
+        match ch {
+            '\r' => self.cursor.x = 1,
+            '\n' => self.line_feed(),
+            '\x08' => self.cursor.x = self.cursor.x.saturating_sub(1).max(1),
+            '\t' => self.move_to((self.cursor.x as i32 + 8) / 8 * 8 + 1, self.cursor.y as i32),
+            _ => {}
+        }
+    }
+
+    fn escape(&mut self, sequence: &str) {
+        if !sequence.starts_with("\x1B[") || sequence.len() < 3 {
+            match sequence {
+                "\x1B7" => self.saved_cursor = self.cursor,
+                "\x1B8" => self.cursor = self.saved_cursor,
+                _ => {}
+            }
+            return;
+        }
+        let final_byte = sequence.as_bytes()[sequence.len() - 1];
+        let body = &sequence[2..sequence.len() - 1];
+        if let Some(private) = body.strip_prefix('?') {
+            if private.split(';').any(|mode| mode == "1049") {
+                self.alternate_screen(final_byte == b'h');
+            }
+            return;
+        }
+        let params: Vec<i32> = body.split(';').map(|param| param.parse().unwrap_or(0)).collect();
+        let first = params[0];
+        let count = first.max(1);
+        let (x, y) = (self.cursor.x as i32, self.cursor.y as i32);
+        let (width, height) = (self.buffer.width(), self.buffer.height());
+        match final_byte {
+            b'H' | b'f' => self.move_to(params.get(1).copied().unwrap_or(1).max(1), count),
+            b'A' => self.move_to(x, y - count),
+            b'B' => self.move_to(x, y + count),
+            b'C' => self.move_to(x + count, y),
+            b'D' => self.move_to(x - count, y),
+            b'G' => self.move_to(count, y),
+            b'd' => self.move_to(x, count),
+            b'K' => {
+                let (x, y) = (self.cursor.x.min(width), self.cursor.y);
+                match first {
+                    0 => self.clear(Rect::new(x, y, (width + 1).saturating_sub(x), 1)),
+                    1 => self.clear(Rect::new(1, y, x, 1)),
+                    _ => self.clear(Rect::new(1, y, width, 1)),
+                }
+            }
+            b'J' => {
+                let (x, y) = (self.cursor.x.min(width), self.cursor.y);
+                match first {
+                    0 => {
+                        self.clear(Rect::new(x, y, (width + 1).saturating_sub(x), 1));
+                        self.clear(Rect::new(1, y + 1, width, height.saturating_sub(y)));
+                    }
+                    1 => {
+                        self.clear(Rect::new(1, 1, width, y.saturating_sub(1)));
+                        self.clear(Rect::new(1, y, x, 1));
+                    }
+                    _ => self.clear(self.buffer.area()),
+                }
+            }
+            b'm' => self.style = apply_sgr(self.style, &params),
+            b's' => self.saved_cursor = self.cursor,
+            b'u' => self.cursor = self.saved_cursor,
+            _ => {}
+        }
+    }
+
+    fn alternate_screen(&mut self, enter: bool) {
+        if enter && self.main.is_none() {
+            let blank = ScreenBuffer::from_viewport();
+            self.main = Some((std::mem::replace(&mut self.buffer, blank), self.cursor));
+        } else if !enter {
+            if let Some((buffer, cursor)) = self.main.take() {
+                self.buffer = buffer;
+                self.cursor = cursor;
+                self.fit_viewport();
+            }
+        }
+    }
+}
+
+// Applies the parameters of an SGR (`ESC [ ... m`) sequence to a style.
+fn apply_sgr(style: Style, params: &[i32]) -> Style {
+    let mut style = style;
+    let mut i = 0;
+    while i < params.len() {
+        let param = params[i];
+        match param {
+            0 => style = Style::default(),
+            1 => style = style.attribute(Attribute::Bright),
+            2 => style = style.attribute(Attribute::Dim),
+            4 => style = style.attribute(Attribute::Underscore),
+            5 => style = style.attribute(Attribute::Blink),
+            7 => style = style.attribute(Attribute::Reverse),
+            8 => style = style.attribute(Attribute::Hidden),
+            30..=37 => style.foreground = color_from_index(param - 30),
+            39 => style.foreground = None,
+            40..=47 => style.background = color_from_index(param - 40),
+            49 => style.background = None,
+            // Extended colors: skip `5;n` or `2;r;g;b`.
+            38 | 48 => {
+                let skip = if params.get(i + 1) == Some(&5) { 2 } else { 4 };
+                if param == 38 {
+                    style.foreground = None;
+                } else {
+                    style.background = None;
+                }
+                i += skip;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    style
+}
+
+fn color_from_index(index: i32) -> Option<Color> {
+    const COLORS: [Color; 8] =
+        [Color::Black, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan, Color::White];
+    COLORS.get(index as usize).copied()
+}
+
+// Replays output into the mirror. Called by `print` with the text actually written.
+pub(crate) fn record(text: &str) {
+    let mut mirror = MIRROR.lock().unwrap_or_else(|e| e.into_inner());
+    let mirror = mirror.get_or_insert_with(Mirror::new);
+    mirror.fit_viewport();
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(sequence) => mirror.escape(sequence),
+            Segment::Grapheme(cluster) => {
+                let mut chars = cluster.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) if ch.is_control() => mirror.control(ch),
+                    // "\r\n" is a single grapheme cluster.
+                    _ if cluster == "\r\n" => {
+                        mirror.control('\r');
+                        mirror.control('\n');
+                    }
+                    _ => mirror.write(cluster),
+                }
+            }
+        }
+    }
+}
+
+fn with_mirror<T>(f: impl FnOnce(&mut Mirror) -> T) -> T {
+    let mut mirror = MIRROR.lock().unwrap_or_else(|e| e.into_inner());
+    let mirror = mirror.get_or_insert_with(Mirror::new);
+    mirror.fit_viewport();
+    f(mirror)
+}
+
+/// Returns a copy of the whole screen as far as it is known.
+pub fn screen_contents() -> ScreenBuffer {
+    with_mirror(|mirror| mirror.buffer.clone())
+}
+
+/// Returns where the cursor is believed to be.
+pub fn cursor_position() -> Point {
+    with_mirror(|mirror| mirror.cursor)
+}
+
+/// Forgets everything recorded, for when the screen was changed by something other than
+/// this crate.
+pub fn reset_mirror() {
+    *MIRROR.lock().unwrap_or_else(|e| e.into_inner()) = Some(Mirror::new());
+}
+
+/// Part of the screen saved by `save_area`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedArea {
+    area: Rect,
+    contents: ScreenBuffer,
+    cursor: Point,
+}
+
+impl SavedArea {
+    /// Returns the saved area, clipped to the viewport.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Returns the saved cells.
+    pub fn contents(&self) -> &ScreenBuffer {
+        &self.contents
+    }
+
+    /// Draws the saved cells back in place and returns the cursor to where it was when
+    /// the area was saved.
+    pub fn restore(&self) {
+        if self.area.is_empty() {
+            return;
+        }
+        self.contents.render_at(self.area.x, self.area.y);
+        print(&format!("\x1B[{};{}H", self.cursor.y, self.cursor.x));
+    }
+}
+
+/// Saves part of the screen so it can be put back with `SavedArea::restore`.
+pub fn save_area(area: Rect) -> SavedArea {
+    with_mirror(|mirror| {
+        let area = area.intersection(&mirror.buffer.area()).unwrap_or(Rect::new(area.x, area.y, 0, 0));
+        let cursor = Point::new(mirror.cursor.x.min(mirror.buffer.width()).max(1), mirror.cursor.y);
+        SavedArea { area, contents: mirror.buffer.copy_area(area), cursor }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinking_the_viewport_keeps_the_cursor_on_screen() {
+        set_viewport(80, 40);
+        let mut mirror = Mirror::new();
+        mirror.escape("\x1B[40;1H");
+        mirror.write("x");
+        mirror.escape("\x1B[?1049h");
+        set_viewport(80, 24);
+        mirror.fit_viewport();
+        mirror.escape("\x1B[?1049l");
+        assert_eq!(mirror.cursor, Point::new(2, 24));
+        mirror.escape("\x1B[J");
+        mirror.escape("\x1B[K");
+
+        mirror.cursor = Point::new(81, 40);
+        mirror.fit_viewport();
+        assert_eq!(mirror.cursor, Point::new(81, 24));
+        mirror.escape("\x1B[J");
+    }
+}
@@ -0,0 +1,162 @@
+//! This module asks the terminal for its default foreground and background colors and its
+//! palette entries using the OSC 10, 11 and 4 queries, so applications can pick a light or
+//! dark theme to match and choose colors with readable contrast.
+//!
+//! Not every terminal answers these queries. Each query is followed by a device attributes
+//! request, which every terminal answers, so a missing reply is noticed straight away; if
+//! nothing comes back at all the query gives up after a timeout and returns `None`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::gradient::{color_to_rgb, Rgb};
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode, read_input_timeout};
+use crate::theme::Theme;
+use crate::*;
+
+static QUERY_TIMEOUT_MILLIS: AtomicU64 = AtomicU64::new(200);
+
+/// Sets how long a query waits for the terminal to answer.
+pub fn set_query_timeout(timeout: Duration) {
+    QUERY_TIMEOUT_MILLIS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Gets how long a query waits for the terminal to answer.
+pub fn get_query_timeout() -> Duration {
+    Duration::from_millis(QUERY_TIMEOUT_MILLIS.load(Ordering::Relaxed))
+}
+
+/// Parses the color in an OSC reply such as `"\x1B]11;rgb:1e1e/1e1e/1e1e\x1B\\"`.
+///
+/// Each component may have one to four hex digits, as the terminal chooses.
+pub fn parse_osc_color(reply: &str) -> Option<Rgb> {
+    let start = reply.find("rgb:")? + 4;
+    let body: String = reply[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit() || *c == '/')
+        .collect();
+    let mut components = body.split('/').map(|part| {
+        if part.is_empty() || part.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(part, 16).ok()?;
+        let max = (1u32 << (4 * part.len())) - 1;
+        Some(((value * 255 + max / 2) / max) as u8)
+    });
+    let r = components.next()??;
+    let g = components.next()??;
+    let b = components.next()??;
+    Some(Rgb::new(r, g, b))
+}
+
+fn query(request: &str, reply_prefix: &str) -> Option<Rgb> {
+    let was_raw = is_raw_mode();
+    if !was_raw && !enable_raw_mode() {
+        return None;
+    }
+
+    print(&format!("{}\x1B[c", request));
+    let timeout = get_query_timeout();
+    let mut reply = Vec::new();
+    loop {
+        let chunk = read_input_timeout(timeout);
+        if chunk.is_empty() {
+            break;
+        }
+        reply.extend_from_slice(&chunk);
+        // The device attributes reply ends with 'c' and always comes last.
+        if let Some(start) = reply.windows(3).position(|w| w == b"\x1B[?") {
+            if reply[start..].contains(&b'c') {
+                break;
+            }
+        }
+    }
+
+    if !was_raw {
+        disable_raw_mode();
+    }
+
+    let reply = String::from_utf8_lossy(&reply);
+    let start = reply.find(reply_prefix)?;
+    parse_osc_color(&reply[start..])
+}
+
+/// Asks the terminal for its default foreground color.
+pub fn query_foreground() -> Option<Rgb> {
+    query("\x1B]10;?\x1B\\", "\x1B]10;")
+}
+
+/// Asks the terminal for its default background color.
+pub fn query_background() -> Option<Rgb> {
+    query("\x1B]11;?\x1B\\", "\x1B]11;")
+}
+
+/// Asks the terminal for the color of a palette entry (0-255).
+pub fn query_palette(index: u8) -> Option<Rgb> {
+    query(&format!("\x1B]4;{};?\x1B\\", index), &format!("\x1B]4;{};", index))
+}
+
+/// Returns the relative luminance of a color, from 0 (black) to 1 (white).
+pub fn relative_luminance(color: Rgb) -> f32 {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.039_28 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+}
+
+/// Returns the WCAG contrast ratio between two colors, from 1 (none) to 21 (black on white).
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Returns the basic color with the best contrast against the given background.
+pub fn readable_foreground(background: Rgb) -> Color {
+    [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ]
+    .into_iter()
+    .max_by(|a, b| {
+        contrast_ratio(color_to_rgb(*a), background).total_cmp(&contrast_ratio(color_to_rgb(*b), background))
+    })
+    .unwrap()
+}
+
+/// Returns true if the color is closer to black than to white.
+pub fn is_dark(color: Rgb) -> bool {
+    relative_luminance(color) < 0.18
+}
+
+/// Works out whether the terminal has a dark background.
+///
+/// Asks the terminal first, then falls back to the `COLORFGBG` environment variable that
+/// some terminals set. Returns `None` if neither gives an answer.
+pub fn has_dark_background() -> Option<bool> {
+    if let Some(background) = query_background() {
+        return Some(is_dark(background));
+    }
+    let colorfgbg = std::env::var("COLORFGBG").ok()?;
+    let background: u8 = colorfgbg.rsplit(';').next()?.parse().ok()?;
+    Some(!matches!(background, 7 | 15))
+}
+
+/// Returns the built-in theme that suits the terminal background, or the dark theme if the
+/// background cannot be determined.
+pub fn detect_theme() -> Theme {
+    match has_dark_background() {
+        Some(false) => Theme::light(),
+        _ => Theme::dark(),
+    }
+}
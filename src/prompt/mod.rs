@@ -0,0 +1,528 @@
+//! This module provides ready-made interactive prompts: yes/no confirmation, choosing
+//! one or several items from a list, numbers within a range and passwords.
+//!
+//! On a terminal the prompts take single key presses in raw mode and redraw themselves
+//! in place below the current line. When input is not a terminal, for example when it is
+//! piped from a file, they fall back to plain line-by-line questions so programs can
+//! still be scripted.
+//!
+//! Every prompt returns `None` if it is cancelled with Esc or Ctrl-C, or if input ends.
+//! Colors come from the current theme: the question uses `Role::Primary`, the answer
+//! `Role::Accent`, the highlighted item `Role::Selection`, hints `Role::Muted` and
+//! validation messages `Role::Error`.
+
+use std::fmt::Display;
+use std::io;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::ansi::{truncate, visible_width};
+use crate::event::{read_key_event, KeyCode, KeyEvent};
+use crate::input::TextInput;
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::theme::{role_style, Role};
+use crate::triangle::{triangle_symbol_to_char, TriangleSymbol};
+use crate::width::str_width;
+use crate::*;
+
+static PAGE_SIZE: AtomicUsize = AtomicUsize::new(7);
+
+/// Sets how many items `select` and `multi_select` show at once.
+pub fn set_page_size(rows: usize) {
+    PAGE_SIZE.store(rows.max(1), Ordering::Relaxed);
+}
+
+/// Gets how many items `select` and `multi_select` show at once.
+pub fn get_page_size() -> usize {
+    PAGE_SIZE.load(Ordering::Relaxed)
+}
+
+/// How `password` shows what is typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordMode {
+    /// Every character is shown as this character.
+    Mask(char),
+    /// Characters are written with `Attribute::Hidden`, so only the cursor moves.
+    Hidden,
+}
+
+// Runs `f` in raw mode, restoring the previous mode afterwards. Returns `None` without
+// calling `f` if raw mode is not available.
+fn with_raw_mode<T>(f: impl FnOnce() -> T) -> Option<T> {
+    let was_raw = is_raw_mode();
+    if !was_raw && !enable_raw_mode() {
+        return None;
+    }
+    let result = f();
+    if !was_raw {
+        disable_raw_mode();
+    }
+    Some(result)
+}
+
+// Reads a line for the fallback prompts. Returns `None` at end of input.
+fn read_answer() -> Option<String> {
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(line.trim().to_string()),
+        Err(e) => {
+            handle_io_error(e);
+            None
+        }
+    }
+}
+
+fn styled(text: &str, role: Role) -> String {
+    format!("{}{}\x1B[0m", role_style(role).to_ansi(), text)
+}
+
+fn question_line(question: &str) -> String {
+    format!("{} ", styled(question, Role::Primary))
+}
+
+// A few lines redrawn in place at the cursor.
+struct InlineBlock {
+    // The row of the block the terminal cursor is on.
+    cursor_row: usize,
+}
+
+impl InlineBlock {
+    fn new() -> Self {
+        InlineBlock { cursor_row: 0 }
+    }
+
+    // Draws the lines, clearing anything left from the previous draw, and puts the
+    // cursor at the given row and column of the block.
+    fn draw(&mut self, lines: &[String], cursor: (usize, usize)) {
+        let width = get_viewport().0.saturating_sub(1) as usize;
+        let mut output = String::new();
+        if self.cursor_row > 0 {
+            output.push_str(&format!("\x1B[{}A", self.cursor_row));
+        }
+        output.push('\r');
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                output.push_str("\r\n");
+            }
+            output.push_str(&truncate(line, width));
+            output.push_str("\x1B[0m\x1B[K");
+        }
+        output.push_str("\x1B[J");
+        let up = lines.len().saturating_sub(1).saturating_sub(cursor.0);
+        if up > 0 {
+            output.push_str(&format!("\x1B[{}A", up));
+        }
+        output.push('\r');
+        if cursor.1 > 0 {
+            output.push_str(&format!("\x1B[{}C", cursor.1.min(width)));
+        }
+        print(&output);
+        self.cursor_row = cursor.0;
+    }
+
+    // Replaces the block with a single summary line and moves below it.
+    fn finish(&mut self, summary: &str) {
+        self.draw(&[summary.to_string()], (0, 0));
+        print("\r\n");
+        self.cursor_row = 0;
+    }
+}
+
+/// Asks a yes/no question. Enter picks `default`.
+pub fn confirm(question: &str, default: bool) -> Option<bool> {
+    let hint = if default { "(Y/n)" } else { "(y/N)" };
+    let prompt = format!("{}{} ", question_line(question), styled(hint, Role::Muted));
+
+    let answer = with_raw_mode(|| {
+        let mut block = InlineBlock::new();
+        block.draw(std::slice::from_ref(&prompt), (0, visible_width(&prompt)));
+        let answer = loop {
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') if !key.ctrl => break Some(true),
+                KeyCode::Char('n') | KeyCode::Char('N') if !key.ctrl => break Some(false),
+                KeyCode::Enter => break Some(default),
+                KeyCode::Esc => break None,
+                _ if key.is_ctrl('c') => break None,
+                _ => print("\x07"),
+            }
+        };
+        let text = match answer {
+            Some(true) => styled("Yes", Role::Accent),
+            Some(false) => styled("No", Role::Accent),
+            None => styled("cancelled", Role::Muted),
+        };
+        block.finish(&format!("{}{}", question_line(question), text));
+        answer
+    });
+    if let Some(answer) = answer {
+        return answer;
+    }
+
+    loop {
+        print(&prompt);
+        match read_answer()?.to_lowercase().as_str() {
+            "" => return Some(default),
+            "y" | "yes" => return Some(true),
+            "n" | "no" => return Some(false),
+            _ => println("Please answer with 'y' or 'n'."),
+        }
+    }
+}
+
+// The state shared by `select` and `multi_select`.
+struct ListState<'a> {
+    options: &'a [&'a str],
+    filter: String,
+    // Indices into `options` of the items matching the filter.
+    visible: Vec<usize>,
+    // Position in `visible` of the highlighted item.
+    highlighted: usize,
+    first: usize,
+    checked: Option<Vec<bool>>,
+}
+
+impl<'a> ListState<'a> {
+    fn new(options: &'a [&'a str], checked: Option<Vec<bool>>) -> Self {
+        let mut state = ListState { options, filter: String::new(), visible: Vec::new(), highlighted: 0, first: 0, checked };
+        state.apply_filter();
+        state
+    }
+
+    fn apply_filter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = (0..self.options.len())
+            .filter(|&i| self.options[i].to_lowercase().contains(&filter))
+            .collect();
+        self.highlighted = 0;
+        self.first = 0;
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let count = self.visible.len() as isize;
+        self.highlighted = (self.highlighted as isize + delta).rem_euclid(count) as usize;
+    }
+
+    fn lines(&mut self, question: &str) -> (Vec<String>, usize) {
+        let page = get_page_size();
+        if self.highlighted < self.first {
+            self.first = self.highlighted;
+        } else if self.highlighted >= self.first + page {
+            self.first = self.highlighted + 1 - page;
+        }
+
+        let hint = match (&self.checked, self.filter.is_empty()) {
+            (Some(_), true) => "(space to toggle, type to filter, enter to confirm)",
+            (None, true) => "(type to filter, enter to choose)",
+            (_, false) => "",
+        };
+        let header = format!("{}{}{}", question_line(question), self.filter, styled(hint, Role::Muted));
+        let cursor_column = str_width(question) + 1 + str_width(&self.filter);
+        let mut lines = vec![header];
+
+        if self.visible.is_empty() {
+            lines.push(styled("  no matches", Role::Muted));
+        }
+        let marker = triangle_symbol_to_char(TriangleSymbol::SmallBlackRightPointing);
+        for position in self.first..(self.first + page).min(self.visible.len()) {
+            let index = self.visible[position];
+            let highlighted = position == self.highlighted;
+            let pointer = if highlighted { marker } else { ' ' };
+            let checkbox = match &self.checked {
+                Some(checked) if checked[index] => "[x] ",
+                Some(_) => "[ ] ",
+                None => "",
+            };
+            let text = format!("{} {}{}", pointer, checkbox, self.options[index]);
+            lines.push(if highlighted { styled(&text, Role::Selection) } else { text });
+        }
+        let hidden = self.visible.len().saturating_sub(self.first + page);
+        if hidden > 0 || self.first > 0 {
+            lines.push(styled(&format!("  ({} of {})", self.highlighted + 1, self.visible.len()), Role::Muted));
+        }
+        (lines, cursor_column)
+    }
+
+    // Handles a key. Returns Some(true) to confirm, Some(false) to cancel, None to go on.
+    fn handle(&mut self, key: &KeyEvent) -> Option<bool> {
+        let page = get_page_size() as isize;
+        match key.code {
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down | KeyCode::Tab => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-page),
+            KeyCode::PageDown => self.move_by(page),
+            KeyCode::Home => self.highlighted = 0,
+            KeyCode::End => self.highlighted = self.visible.len().saturating_sub(1),
+            KeyCode::Enter => return Some(true),
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.filter.clear();
+                self.apply_filter();
+            }
+            KeyCode::Esc => return Some(false),
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.apply_filter();
+            }
+            KeyCode::Char(' ') if self.checked.is_some() && !key.ctrl => {
+                if let (Some(checked), Some(&index)) = (self.checked.as_mut(), self.visible.get(self.highlighted)) {
+                    checked[index] = !checked[index];
+                }
+            }
+            KeyCode::Char(ch) if key.ctrl => match ch {
+                'c' => return Some(false),
+                'p' => self.move_by(-1),
+                'n' => self.move_by(1),
+                'a' => {
+                    if let Some(checked) = self.checked.as_mut() {
+                        let all = self.visible.iter().all(|&i| checked[i]);
+                        for &i in &self.visible {
+                            checked[i] = !all;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            _ => {
+                if let Some(ch) = key.printable() {
+                    self.filter.push(ch);
+                    self.apply_filter();
+                }
+            }
+        }
+        None
+    }
+
+    fn run(&mut self, question: &str) -> Option<bool> {
+        with_raw_mode(|| {
+            hide_cursor();
+            let mut block = InlineBlock::new();
+            let confirmed = loop {
+                let (lines, column) = self.lines(question);
+                block.draw(&lines, (0, column));
+                let Some(key) = read_key_event() else {
+                    break false;
+                };
+                match self.handle(&key) {
+                    Some(true) if self.checked.is_some() || !self.visible.is_empty() => break true,
+                    Some(true) => print("\x07"),
+                    Some(false) => break false,
+                    None => {}
+                }
+            };
+            let answer = if !confirmed {
+                styled("cancelled", Role::Muted)
+            } else if let Some(checked) = &self.checked {
+                let names: Vec<&str> = (0..self.options.len()).filter(|&i| checked[i]).map(|i| self.options[i]).collect();
+                styled(&names.join(", "), Role::Accent)
+            } else {
+                styled(self.options[self.visible[self.highlighted]], Role::Accent)
+            };
+            block.finish(&format!("{}{}", question_line(question), answer));
+            show_cursor();
+            confirmed
+        })
+    }
+}
+
+// Prints the numbered options for the fallback prompts.
+fn print_numbered(question: &str, options: &[&str]) {
+    println(&styled(question, Role::Primary));
+    for (i, option) in options.iter().enumerate() {
+        println(&format!("  {}) {}", i + 1, option));
+    }
+}
+
+// Parses a fallback answer naming an option by number or by its text.
+fn parse_choice(answer: &str, options: &[&str]) -> Option<usize> {
+    match answer.parse::<usize>() {
+        Ok(number) if (1..=options.len()).contains(&number) => Some(number - 1),
+        Ok(_) => None,
+        Err(_) => options.iter().position(|option| option.eq_ignore_ascii_case(answer)),
+    }
+}
+
+/// Asks the user to choose one of the options and returns its index.
+///
+/// Up/Down move the highlight, typing filters the list, Enter chooses and Esc clears the
+/// filter or cancels.
+pub fn select(question: &str, options: &[&str]) -> Option<usize> {
+    if options.is_empty() {
+        return None;
+    }
+    let mut state = ListState::new(options, None);
+    if let Some(confirmed) = state.run(question) {
+        return confirmed.then(|| state.visible[state.highlighted]);
+    }
+
+    print_numbered(question, options);
+    loop {
+        print(&format!("Enter a number (1-{}): ", options.len()));
+        match parse_choice(&read_answer()?, options) {
+            Some(index) => return Some(index),
+            None => println("Please enter one of the listed numbers."),
+        }
+    }
+}
+
+/// Asks the user to tick any number of the options and returns the ticked indices in
+/// order. `selected` lists the indices ticked at the start.
+///
+/// Space toggles the highlighted item, Ctrl-A toggles every visible item, typing filters
+/// the list, Enter confirms and Esc clears the filter or cancels.
+pub fn multi_select(question: &str, options: &[&str], selected: &[usize]) -> Option<Vec<usize>> {
+    let mut checked = vec![false; options.len()];
+    for &i in selected.iter().filter(|&&i| i < options.len()) {
+        checked[i] = true;
+    }
+
+    let mut state = ListState::new(options, Some(checked.clone()));
+    if let Some(confirmed) = state.run(question) {
+        let checked = state.checked.unwrap_or_default();
+        return confirmed.then(|| (0..options.len()).filter(|&i| checked[i]).collect());
+    }
+
+    print_numbered(question, options);
+    loop {
+        print("Enter numbers separated by commas (empty for the default): ");
+        let answer = read_answer()?;
+        if answer.is_empty() {
+            return Some((0..options.len()).filter(|&i| checked[i]).collect());
+        }
+        let choices: Option<Vec<usize>> = answer
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| parse_choice(part, options))
+            .collect();
+        match choices {
+            Some(mut choices) => {
+                choices.sort_unstable();
+                choices.dedup();
+                return Some(choices);
+            }
+            None => println("Please enter numbers from the list."),
+        }
+    }
+}
+
+// Edits a value with a `TextInput` on the prompt line, showing validation errors on the
+// line below. Returns `None` from the outer option if raw mode is unavailable.
+fn edit_inline(question: &str, mut input: TextInput, summary: impl Fn(&str) -> String) -> Option<Option<String>> {
+    with_raw_mode(|| {
+        let mut block = InlineBlock::new();
+        let prompt = question_line(question);
+        let prompt_width = str_width(question) + 1;
+        let field_width = get_viewport().0.saturating_sub(prompt_width as u16 + 1).max(1);
+        let mut show_error = false;
+        let result = loop {
+            let mut field = ScreenBuffer::new(field_width, 1);
+            input.draw(field.area(), &mut field, true);
+            let cursor_column = prompt_width + field.cursor().map_or(0, |cursor| cursor.x as usize - 1);
+            let mut lines = vec![format!("{}{}", prompt, field.row_ansi(1).trim_end())];
+            if let Some(error) = input.error().filter(|_| show_error) {
+                lines.push(styled(error, Role::Error));
+            }
+            block.draw(&lines, (0, cursor_column));
+            show_cursor();
+
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            if key.is_ctrl('c') || key.code == KeyCode::Esc {
+                break None;
+            }
+            input.handle_key(&key);
+            show_error = key.code == KeyCode::Enter || (show_error && !input.is_valid());
+            if input.is_submitted() {
+                break Some(input.text().to_string());
+            }
+        };
+        let answer = match &result {
+            Some(value) => styled(&summary(value), Role::Accent),
+            None => styled("cancelled", Role::Muted),
+        };
+        block.finish(&format!("{}{}", prompt, answer));
+        result
+    })
+}
+
+/// Asks for a number within `range`. Enter on an empty answer picks `default`, if given.
+pub fn number<T>(question: &str, range: RangeInclusive<T>, default: Option<T>) -> Option<T>
+where
+    T: FromStr + PartialOrd + Display + Copy + 'static,
+{
+    let (min, max) = (*range.start(), *range.end());
+    let check = move |value: &str| -> Result<T, String> {
+        match value.trim().parse::<T>() {
+            Ok(number) if number >= min && number <= max => Ok(number),
+            _ => Err(format!("Enter a number from {} to {}", min, max)),
+        }
+    };
+    let question = match default {
+        Some(default) => format!("{} [{}]", question, default),
+        None => question.to_string(),
+    };
+
+    let input = TextInput::new()
+        .filter(|ch| ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E'))
+        .validator(move |value| match (value.is_empty(), default) {
+            (true, Some(_)) => Ok(()),
+            _ => check(value).map(|_| ()),
+        });
+    let parse = move |value: &str| match (value.is_empty(), default) {
+        (true, Some(default)) => Some(default),
+        _ => check(value).ok(),
+    };
+    if let Some(answer) = edit_inline(&question, input, |value| {
+        parse(value).map_or_else(|| value.to_string(), |number| number.to_string())
+    }) {
+        return answer.and_then(|value| parse(&value));
+    }
+
+    loop {
+        print(&question_line(&question));
+        let answer = read_answer()?;
+        match parse(&answer) {
+            Some(number) => return Some(number),
+            None => println(&format!("Enter a number from {} to {}.", min, max)),
+        }
+    }
+}
+
+/// Asks for a password, showing each character as `*`.
+pub fn password(question: &str) -> Option<String> {
+    password_with(question, PasswordMode::Mask('*'))
+}
+
+/// Asks for a password, showing what is typed according to `mode`.
+///
+/// When input is not a terminal the answer is read as a plain line; nothing is echoed in
+/// that case anyway.
+pub fn password_with(question: &str, mode: PasswordMode) -> Option<String> {
+    let input = match mode {
+        PasswordMode::Mask(mask) => TextInput::new().mask(mask),
+        PasswordMode::Hidden => TextInput::new().style(Style::from(Attribute::Hidden)),
+    };
+    if let Some(answer) = edit_inline(question, input, |_| String::from("********")) {
+        return answer;
+    }
+    print(&question_line(question));
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(line.trim_end_matches(['\n', '\r']).to_string()),
+        Err(e) => {
+            handle_io_error(e);
+            None
+        }
+    }
+}
@@ -122,6 +122,22 @@ pub enum DoubleRoundedBox {
     VerticalHorizontal,
 }
 
+/// Represents heavy line box characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeavyBox {
+    Horizontal,
+    Vertical,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    VerticalLeft,
+    VerticalRight,
+    HorizontalDown,
+    HorizontalUp,
+    VerticalHorizontal,
+}
+
 /// Converts SingleBox enum variant to corresponding character.
 ///
 /// # Arguments
@@ -222,6 +238,31 @@ pub fn double_rounded_box_to_char(ch: DoubleRoundedBox) -> char {
     }
 }
 
+/// Converts HeavyBox enum variant to corresponding character.
+///
+/// # Arguments
+///
+/// * `ch` - The HeavyBox variant to convert
+///
+/// # Returns
+///
+/// The Unicode character corresponding to the given HeavyBox variant
+pub fn heavy_box_to_char(ch: HeavyBox) -> char {
+    match ch {
+        HeavyBox::Horizontal => '━',
+        HeavyBox::Vertical => '┃',
+        HeavyBox::TopLeft => '┏',
+        HeavyBox::TopRight => '┓',
+        HeavyBox::BottomLeft => '┗',
+        HeavyBox::BottomRight => '┛',
+        HeavyBox::VerticalLeft => '┣',
+        HeavyBox::VerticalRight => '┫',
+        HeavyBox::HorizontalDown => '┳',
+        HeavyBox::HorizontalUp => '┻',
+        HeavyBox::VerticalHorizontal => '╋',
+    }
+}
+
 /// Represents different box drawing styles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BoxStyle {
@@ -272,7 +313,7 @@ pub fn box_char_to_char(ch: BoxChar) -> char {
 /// # Returns
 ///
 /// The Unicode character for the specified line style and orientation
-fn get_line_char(style: LineStyle, is_vertical: bool) -> char {
+pub(crate) fn get_line_char(style: LineStyle, is_vertical: bool) -> char {
     match style {
         LineStyle::Solid => if is_vertical { '│' } else { '─' },
         LineStyle::Dotted => if is_vertical { '┆' } else { '┄' },
@@ -322,7 +363,7 @@ fn vertical_line(x: u16, y: u16, height: u16, style: LineStyle) {
 /// # Returns
 ///
 /// An array of four characters representing the top-left, top-right, bottom-left, and bottom-right corners
-fn get_box_corners(style: BoxStyle) -> [char; 4] {
+pub(crate) fn get_box_corners(style: BoxStyle) -> [char; 4] {
     match style {
         BoxStyle::Single => ['┌', '┐', '└', '┘'],
         BoxStyle::Double => ['╔', '╗', '╚', '╝'],
@@ -333,6 +374,16 @@ fn get_box_corners(style: BoxStyle) -> [char; 4] {
     }
 }
 
+/// Gets the line style used for the edges of a box
+pub(crate) fn get_box_line_style(style: BoxStyle) -> LineStyle {
+    match style {
+        BoxStyle::Single | BoxStyle::SingleRounded => LineStyle::Solid,
+        BoxStyle::Double | BoxStyle::DoubleRounded => LineStyle::DoubleLine,
+        BoxStyle::Dotted => LineStyle::Dotted,
+        BoxStyle::Dashed => LineStyle::Dashed,
+    }
+}
+
 /// Draws a box with the specified style
 ///
 /// # Arguments
@@ -351,12 +402,7 @@ pub fn draw_box(x: u16, y: u16, width: u16, height: u16, style: BoxStyle) {
     }
 
     let corners = get_box_corners(style);
-    let line_style = match style {
-        BoxStyle::Single | BoxStyle::SingleRounded => LineStyle::Solid,
-        BoxStyle::Double | BoxStyle::DoubleRounded => LineStyle::DoubleLine,
-        BoxStyle::Dotted => LineStyle::Dotted,
-        BoxStyle::Dashed => LineStyle::Dashed,
-    };
+    let line_style = get_box_line_style(style);
 
     // Draw horizontal lines
     horizontal_line(x + 1, y, width - 2, line_style);
@@ -377,6 +423,16 @@ pub fn draw_box(x: u16, y: u16, width: u16, height: u16, style: BoxStyle) {
     put_char(corners[3]);
 }
 
+/// Returns the block character drawn for a ShadeStyle.
+pub(crate) fn shade_char(style: ShadeStyle) -> char {
+    match style {
+        ShadeStyle::Light => block_char_to_char(BlockChar::LightShade),
+        ShadeStyle::Medium => block_char_to_char(BlockChar::MediumShade),
+        ShadeStyle::Dark => block_char_to_char(BlockChar::DarkShade),
+        ShadeStyle::Solid => block_char_to_char(BlockChar::Full),
+    }
+}
+
 /// Draws a shaded rectangle
 ///
 /// # Arguments
@@ -394,12 +450,7 @@ pub fn draw_shaded_rectangle(x: u16, y: u16, width: u16, height: u16, style: Sha
         return;
     }
 
-    let shade_char = match style {
-        ShadeStyle::Light => block_char_to_char(BlockChar::LightShade),
-        ShadeStyle::Medium => block_char_to_char(BlockChar::MediumShade),
-        ShadeStyle::Dark => block_char_to_char(BlockChar::DarkShade),
-        ShadeStyle::Solid => block_char_to_char(BlockChar::Full),
-    };
+    let shade_char = shade_char(style);
 
     for dy in 0..height {
         move_cursor_to(x, y + dy);
@@ -433,6 +484,35 @@ pub fn hide_box(x: u16, y: u16, width: u16, height: u16) {
     }
 }
 
+/// Draws a box with the specified style covering a `Rect`
+///
+/// # Arguments
+///
+/// * `rect` - The area the box covers, including its border
+/// * `style` - The BoxStyle to use
+pub fn draw_box_rect(rect: Rect, style: BoxStyle) {
+    draw_box(rect.x, rect.y, rect.width, rect.height, style);
+}
+
+/// Draws a shaded rectangle covering a `Rect`
+///
+/// # Arguments
+///
+/// * `rect` - The area to shade
+/// * `style` - The ShadeStyle to use
+pub fn draw_shaded_rect(rect: Rect, style: ShadeStyle) {
+    draw_shaded_rectangle(rect.x, rect.y, rect.width, rect.height, style);
+}
+
+/// Hides the box covering a `Rect` by overwriting it with spaces
+///
+/// # Arguments
+///
+/// * `rect` - The area to clear
+pub fn hide_box_rect(rect: Rect) {
+    hide_box(rect.x, rect.y, rect.width, rect.height);
+}
+
 /// Helper function to get the appropriate BoxChar based on style and type.
 ///
 /// # Arguments
@@ -567,4 +647,96 @@ pub fn get_corner_char(style: BoxStyle, corner: &str) -> Option<char> {
         },
     };
     Some(corner_char)
-}
\ No newline at end of file
+}
+
+impl BlockChar {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(block_char_to_char(*self))
+    }
+}
+
+impl SingleBox {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(single_box_to_char(*self))
+    }
+}
+
+impl DoubleBox {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(double_box_to_char(*self))
+    }
+}
+
+impl SingleRoundedBox {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(single_rounded_box_to_char(*self))
+    }
+}
+
+impl DoubleRoundedBox {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(double_rounded_box_to_char(*self))
+    }
+}
+
+impl HeavyBox {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(heavy_box_to_char(*self))
+    }
+}
+
+impl BoxChar {
+    /// Returns the number of terminal columns the character occupies.
+    pub fn width(&self) -> usize {
+        crate::width::char_width(box_char_to_char(*self))
+    }
+}
+
+impl ScreenBuffer {
+    /// Draws a box into the buffer. Parts outside the buffer are clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - The area the box covers, including its border
+    /// * `style` - The BoxStyle to use
+    /// * `color` - The Style the border is drawn with
+    pub fn draw_box(&mut self, area: Rect, style: BoxStyle, color: Style) {
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+        let corners = get_box_corners(style);
+        let line_style = get_box_line_style(style);
+        let (left, top) = (area.left(), area.top());
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+
+        for x in left + 1..right {
+            self.set_char(x, top, get_line_char(line_style, false), color);
+            self.set_char(x, bottom, get_line_char(line_style, false), color);
+        }
+        for y in top + 1..bottom {
+            self.set_char(left, y, get_line_char(line_style, true), color);
+            self.set_char(right, y, get_line_char(line_style, true), color);
+        }
+        self.set_char(left, top, corners[0], color);
+        self.set_char(right, top, corners[1], color);
+        self.set_char(left, bottom, corners[2], color);
+        self.set_char(right, bottom, corners[3], color);
+    }
+
+    /// Fills an area of the buffer with a shade character.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - The area to shade
+    /// * `style` - The ShadeStyle to use
+    /// * `color` - The Style the shade is drawn with
+    pub fn draw_shaded_rect(&mut self, area: Rect, style: ShadeStyle, color: Style) {
+        self.fill(area, shade_char(style), color);
+    }
+}
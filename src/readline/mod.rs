@@ -0,0 +1,628 @@
+//! This module provides a line editor for interactive prompts and REPLs.
+//!
+//! `LineEditor::read_line` edits a line in place after a prompt, with Emacs-style keys,
+//! a history that can be saved to a file, reverse search with Ctrl-R and Tab completion
+//! through a user-provided `Completer`. When there are several completions they are
+//! shown in a popup below the line.
+//!
+//! Keys, in addition to those of `input::TextInput`:
+//!
+//! - Up/Down or Ctrl-P/Ctrl-N: previous and next history entry
+//! - Ctrl-R: reverse search through the history; Ctrl-R again finds older matches,
+//!   Enter runs the match, Esc or Ctrl-G cancels and any other key starts editing it
+//! - Tab: complete; with several completions, Tab/Shift-Tab or Down/Up cycle through
+//!   them, Enter accepts and Esc restores the original word
+//! - Ctrl-K, Ctrl-U, Ctrl-W, Alt-D: delete text and remember it; Ctrl-Y pastes it back
+//! - Ctrl-T: swap the two characters before the cursor
+//! - Ctrl-L: clear the screen
+//! - Ctrl-C: abandon the line; Ctrl-D on an empty line: end of input
+//!
+//! ```no_run
+//! use rpian_terminal::readline::{History, LineEditor, WordCompleter};
+//!
+//! let history = History::open("repl_history", 500).unwrap_or_default();
+//! let mut editor = LineEditor::new()
+//!     .history(history)
+//!     .completer(WordCompleter::new(&["help", "quit", "print"]));
+//! while let Ok(line) = editor.read_line("> ") {
+//!     println!("you typed {}", line);
+//! }
+//! ```
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::event::{read_key_event, KeyCode, KeyEvent};
+use crate::grapheme::prev_boundary;
+use crate::input::TextInput;
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::theme::{role_style, Role};
+use crate::width::str_width;
+use crate::*;
+
+/// Why `LineEditor::read_line` did not return a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadlineError {
+    /// Ctrl-C was pressed.
+    Interrupted,
+    /// Ctrl-D was pressed on an empty line, or input ended.
+    Eof,
+}
+
+impl fmt::Display for ReadlineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadlineError::Interrupted => write!(f, "Interrupted"),
+            ReadlineError::Eof => write!(f, "End of input"),
+        }
+    }
+}
+
+impl std::error::Error for ReadlineError {}
+
+/// Previously entered lines, oldest first, optionally saved to a file.
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: Vec<String>,
+    /// The number of entries kept; older entries are dropped.
+    pub max_entries: usize,
+    path: Option<PathBuf>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl History {
+    /// Creates an empty history kept only in memory.
+    pub fn new(max_entries: usize) -> Self {
+        History { entries: Vec::new(), max_entries, path: None }
+    }
+
+    /// Loads the history from a file, one entry per line, and appends new entries to it.
+    /// A missing file is treated as an empty history.
+    pub fn open<P: AsRef<Path>>(path: P, max_entries: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut history = Self::new(max_entries);
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                history.entries = contents.lines().filter(|line| !line.is_empty()).map(String::from).collect();
+                let excess = history.entries.len().saturating_sub(max_entries);
+                history.entries.drain(..excess);
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    /// Returns the entries, oldest first.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds an entry. Blank lines and repeats of the newest entry are skipped. If the
+    /// history has a file, the entry is appended to it; write errors go to the global
+    /// error handler.
+    pub fn add(&mut self, entry: &str) {
+        if entry.trim().is_empty() || self.entries.last().is_some_and(|last| last == entry) {
+            return;
+        }
+        self.entries.push(entry.to_string());
+        if self.entries.len() > self.max_entries {
+            let excess = self.entries.len() - self.max_entries;
+            self.entries.drain(..excess);
+            if let Err(e) = self.save() {
+                handle_io_error(e);
+            }
+        } else if let Some(path) = &self.path {
+            let result = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "{}", entry));
+            if let Err(e) = result {
+                handle_io_error(e);
+            }
+        }
+    }
+
+    /// Writes all entries to the history file, replacing its contents. Does nothing for
+    /// a history kept only in memory.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut contents = self.entries.join("\n");
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        fs::write(path, contents)
+    }
+
+    /// Returns the index of the newest entry before `before` that contains `query`.
+    pub fn search_backward(&self, query: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())].iter().rposition(|entry| entry.contains(query))
+    }
+}
+
+/// Supplies completions for the word at the cursor.
+pub trait Completer {
+    /// Returns the byte offset in `line` where the word being completed starts, and the
+    /// possible replacements for the text from there to `cursor`.
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>);
+}
+
+impl<F: Fn(&str, usize) -> (usize, Vec<String>)> Completer for F {
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>) {
+        self(line, cursor)
+    }
+}
+
+/// Completes the word at the cursor from a fixed list of words.
+#[derive(Debug, Clone, Default)]
+pub struct WordCompleter {
+    pub words: Vec<String>,
+}
+
+impl WordCompleter {
+    pub fn new(words: &[&str]) -> Self {
+        WordCompleter { words: words.iter().map(|word| word.to_string()).collect() }
+    }
+}
+
+impl Completer for WordCompleter {
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>) {
+        let start = word_start(line, cursor);
+        let prefix = &line[start..cursor];
+        let matches = self.words.iter().filter(|word| word.starts_with(prefix)).cloned().collect();
+        (start, matches)
+    }
+}
+
+/// Returns the byte offset where the whitespace-separated word ending at `cursor` starts.
+pub fn word_start(line: &str, cursor: usize) -> usize {
+    line[..cursor].rfind(char::is_whitespace).map_or(0, |i| i + line[i..].chars().next().unwrap().len_utf8())
+}
+
+// The longest prefix shared by all candidates.
+fn common_prefix(candidates: &[String]) -> String {
+    let Some(first) = candidates.first() else {
+        return String::new();
+    };
+    let mut prefix = first.as_str();
+    for candidate in &candidates[1..] {
+        let length = prefix
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(prefix.len().min(candidate.len()), |((i, _), _)| i);
+        prefix = &prefix[..length];
+    }
+    prefix.to_string()
+}
+
+struct Completion {
+    start: usize,
+    // The text between `start` and the cursor before any candidate was previewed.
+    original: String,
+    candidates: Vec<String>,
+    selected: Option<usize>,
+}
+
+struct Search {
+    query: String,
+    found: Option<usize>,
+    original: String,
+    original_cursor: usize,
+}
+
+/// An interactive line editor with history and completion.
+pub struct LineEditor {
+    pub history: History,
+    completer: Option<Box<dyn Completer>>,
+    /// The most completions shown in the popup at once.
+    pub max_popup_rows: usize,
+    /// The style the prompt is drawn with.
+    pub prompt_style: Style,
+    kill_buffer: String,
+}
+
+impl Default for LineEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        LineEditor {
+            history: History::default(),
+            completer: None,
+            max_popup_rows: 8,
+            prompt_style: Style::default(),
+            kill_buffer: String::new(),
+        }
+    }
+
+    pub fn history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
+    pub fn completer(mut self, completer: impl Completer + 'static) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    pub fn prompt_style(mut self, style: Style) -> Self {
+        self.prompt_style = style;
+        self
+    }
+
+    /// Shows the prompt and edits a line until Enter is pressed. The line is added to the
+    /// history.
+    ///
+    /// If standard input is not a terminal, the prompt is printed and a line is read with
+    /// `read_line` instead.
+    pub fn read_line(&mut self, prompt: &str) -> Result<String, ReadlineError> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            print(&format!("{}{}", self.prompt_style.to_ansi(), prompt));
+            reset_color();
+            let mut line = String::new();
+            match io::stdin().read_line(&mut line) {
+                Ok(0) => return Err(ReadlineError::Eof),
+                Ok(_) => {}
+                Err(e) => {
+                    handle_io_error(e);
+                    return Err(ReadlineError::Eof);
+                }
+            }
+            let line = line.trim_end_matches(['\n', '\r']).to_string();
+            self.history.add(&line);
+            return Ok(line);
+        }
+
+        let result = self.edit(prompt);
+        if !was_raw {
+            disable_raw_mode();
+        }
+        result
+    }
+
+    fn edit(&mut self, prompt: &str) -> Result<String, ReadlineError> {
+        let mut input = TextInput::new();
+        let mut draft = String::new();
+        let mut history_index: Option<usize> = None;
+        let mut completion: Option<Completion> = None;
+        let mut search: Option<Search> = None;
+        let mut popup_rows = 0;
+
+        loop {
+            popup_rows = self.draw(prompt, &input, completion.as_ref(), search.as_ref(), popup_rows);
+            let Some(key) = read_key_event() else {
+                self.finish_line(popup_rows);
+                return Err(ReadlineError::Eof);
+            };
+
+            if let Some(state) = search.as_mut() {
+                match self.search_key(state, &mut input, &key) {
+                    Some(true) => continue,
+                    Some(false) => {
+                        search = None;
+                        continue;
+                    }
+                    None => search = None,
+                }
+            }
+
+            if let Some(state) = completion.as_mut() {
+                if Self::completion_key(state, &mut input, &key) {
+                    if state.selected.is_none() && key.code == KeyCode::Esc {
+                        completion = None;
+                    }
+                    continue;
+                }
+                let accepted = key.code == KeyCode::Enter && state.selected.is_some();
+                completion = None;
+                if accepted {
+                    continue;
+                }
+            }
+
+            match key.code {
+                KeyCode::Enter => {
+                    self.finish_line(popup_rows);
+                    let line = input.text().to_string();
+                    self.history.add(&line);
+                    return Ok(line);
+                }
+                KeyCode::Up | KeyCode::Down => {
+                    self.step_history(&mut input, &mut history_index, &mut draft, key.code == KeyCode::Up)
+                }
+                KeyCode::Tab => completion = self.start_completion(&mut input),
+                KeyCode::Char(ch) if key.ctrl && !key.alt => match ch {
+                    'c' => {
+                        print("^C");
+                        self.finish_line(popup_rows);
+                        return Err(ReadlineError::Interrupted);
+                    }
+                    'd' if input.text().is_empty() => {
+                        self.finish_line(popup_rows);
+                        return Err(ReadlineError::Eof);
+                    }
+                    'p' | 'n' => self.step_history(&mut input, &mut history_index, &mut draft, ch == 'p'),
+                    'r' => {
+                        search = Some(Search {
+                            query: String::new(),
+                            found: None,
+                            original: input.text().to_string(),
+                            original_cursor: input.cursor(),
+                        })
+                    }
+                    'y' => {
+                        let killed = self.kill_buffer.clone();
+                        input.insert_str(&killed);
+                    }
+                    't' => transpose(&mut input),
+                    'l' => {
+                        clear_screen();
+                        popup_rows = 0;
+                    }
+                    'k' | 'u' | 'w' => self.kill(&mut input, &key),
+                    _ => {
+                        input.handle_key(&key);
+                    }
+                },
+                KeyCode::Char('d') | KeyCode::Backspace if key.alt => self.kill(&mut input, &key),
+                _ => {
+                    input.handle_key(&key);
+                }
+            }
+        }
+    }
+
+    // Applies a deleting key and keeps what it deleted for Ctrl-Y.
+    fn kill(&mut self, input: &mut TextInput, key: &KeyEvent) {
+        let before = input.text().to_string();
+        input.handle_key(key);
+        let removed = before.len() - input.text().len();
+        if removed > 0 {
+            let start = input.cursor();
+            self.kill_buffer = before[start..start + removed].to_string();
+        }
+    }
+
+    fn step_history(&self, input: &mut TextInput, index: &mut Option<usize>, draft: &mut String, older: bool) {
+        let count = self.history.len();
+        let next = match (*index, older) {
+            (None, true) if count > 0 => Some(count - 1),
+            (None, _) => return,
+            (Some(0), true) => return,
+            (Some(i), true) => Some(i - 1),
+            (Some(i), false) if i + 1 < count => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        if index.is_none() {
+            *draft = input.text().to_string();
+        }
+        *index = next;
+        match next {
+            Some(i) => input.set_value(&self.history.entries()[i]),
+            None => input.set_value(draft),
+        }
+    }
+
+    fn start_completion(&self, input: &mut TextInput) -> Option<Completion> {
+        let completer = self.completer.as_ref()?;
+        let cursor = input.cursor();
+        let (start, candidates) = completer.complete(input.text(), cursor);
+        let start = start.min(cursor);
+        match candidates.len() {
+            0 => {
+                print("\x07");
+                None
+            }
+            1 => {
+                replace_range(input, start, cursor, &candidates[0]);
+                None
+            }
+            _ => {
+                let prefix = common_prefix(&candidates);
+                if prefix.len() > cursor - start {
+                    replace_range(input, start, cursor, &prefix);
+                }
+                let original = input.text()[start..input.cursor()].to_string();
+                Some(Completion { start, original, candidates, selected: None })
+            }
+        }
+    }
+
+    // Handles a key while the completion popup is open. Returns false if the key closes
+    // the popup and should be handled normally.
+    fn completion_key(state: &mut Completion, input: &mut TextInput, key: &KeyEvent) -> bool {
+        let count = state.candidates.len();
+        let selected = match key.code {
+            KeyCode::Tab if !key.shift => state.selected.map_or(0, |i| (i + 1) % count),
+            KeyCode::Down => state.selected.map_or(0, |i| (i + 1) % count),
+            KeyCode::BackTab | KeyCode::Tab | KeyCode::Up => state.selected.map_or(count - 1, |i| (i + count - 1) % count),
+            KeyCode::Esc => {
+                let end = input.cursor();
+                replace_range(input, state.start, end, &state.original.clone());
+                state.selected = None;
+                return true;
+            }
+            _ => return false,
+        };
+        let end = input.cursor();
+        replace_range(input, state.start, end, &state.candidates[selected].clone());
+        state.selected = Some(selected);
+        true
+    }
+
+    // Handles a key during reverse search. Returns Some(true) to stay in search mode,
+    // Some(false) to leave it with the key used up, and None to leave it and handle the
+    // key normally.
+    fn search_key(&self, state: &mut Search, input: &mut TextInput, key: &KeyEvent) -> Option<bool> {
+        let count = self.history.len();
+        match key.code {
+            KeyCode::Char('r') if key.ctrl => {
+                // Skip older entries identical to the current match.
+                let current = state.found.map(|i| self.history.entries()[i].as_str());
+                let mut before = state.found.unwrap_or(count);
+                while let Some(found) = self.history.search_backward(&state.query, before) {
+                    before = found;
+                    if Some(self.history.entries()[found].as_str()) != current {
+                        state.found = Some(found);
+                        break;
+                    }
+                }
+            }
+            KeyCode::Char('g') if key.ctrl => {
+                input.set_value(&state.original);
+                input.set_cursor(state.original_cursor);
+                return Some(false);
+            }
+            KeyCode::Esc => {
+                input.set_value(&state.original);
+                input.set_cursor(state.original_cursor);
+                return Some(false);
+            }
+            KeyCode::Backspace => {
+                state.query.pop();
+                state.found = self.history.search_backward(&state.query, count);
+            }
+            _ => match key.printable() {
+                Some(ch) => {
+                    state.query.push(ch);
+                    let from = state.found.map_or(count, |i| i + 1);
+                    state.found = self.history.search_backward(&state.query, from);
+                }
+                None => return None,
+            },
+        }
+        if let Some(found) = state.found {
+            let entry = &self.history.entries()[found];
+            input.set_value(entry);
+            if let Some(position) = entry.find(&state.query) {
+                input.set_cursor(position);
+            }
+        }
+        Some(true)
+    }
+
+    // Draws the prompt, the line and the completion popup on the current terminal line,
+    // returning the number of popup rows drawn.
+    fn draw(
+        &self,
+        prompt: &str,
+        input: &TextInput,
+        completion: Option<&Completion>,
+        search: Option<&Search>,
+        previous_popup_rows: usize,
+    ) -> usize {
+        let (viewport_width, _) = get_viewport();
+        let search_prompt;
+        let (prompt, prompt_style) = match search {
+            Some(state) => {
+                let failed = if state.found.is_none() && !state.query.is_empty() { "failed " } else { "" };
+                search_prompt = format!("({}reverse-i-search)'{}': ", failed, state.query);
+                (search_prompt.as_str(), role_style(Role::Muted))
+            }
+            None => (prompt, self.prompt_style),
+        };
+        let prompt_width = str_width(prompt) as u16;
+        let field_width = viewport_width.saturating_sub(prompt_width + 1).max(1);
+
+        let mut field = ScreenBuffer::new(field_width, 1);
+        input.draw(field.area(), &mut field, true);
+        let cursor_column = prompt_width + field.cursor().map_or(0, |cursor| cursor.x - 1);
+
+        let mut output = String::from("\r");
+        output.push_str(&prompt_style.to_ansi());
+        output.push_str(prompt);
+        output.push_str("\x1B[0m");
+        output.push_str(field.row_ansi(1).trim_end_matches("\x1B[0m").trim_end());
+        output.push_str("\x1B[0m\x1B[K");
+        if previous_popup_rows > 0 {
+            output.push_str("\x1B[J");
+        }
+
+        let mut popup_rows = 0;
+        if let Some(state) = completion {
+            let rows = self.max_popup_rows.max(1);
+            let selected = state.selected.unwrap_or(0);
+            let first = selected.saturating_sub(rows - 1).min(state.candidates.len().saturating_sub(rows));
+            for (i, candidate) in state.candidates.iter().enumerate().skip(first).take(rows) {
+                let style = if Some(i) == state.selected { role_style(Role::Selection) } else { role_style(Role::Muted) };
+                let text = crate::ansi::truncate(candidate, viewport_width.saturating_sub(1) as usize);
+                output.push_str(&format!("\r\n{}{}\x1B[0m", style.to_ansi(), text));
+                popup_rows += 1;
+            }
+            let hidden = state.candidates.len().saturating_sub(first + popup_rows);
+            if hidden > 0 {
+                output.push_str(&format!("\r\n{}... {} more\x1B[0m", role_style(Role::Muted).to_ansi(), hidden));
+                popup_rows += 1;
+            }
+            if popup_rows > 0 {
+                output.push_str(&format!("\x1B[{}A", popup_rows));
+            }
+        }
+
+        output.push('\r');
+        if cursor_column > 0 {
+            output.push_str(&format!("\x1B[{}C", cursor_column));
+        }
+        print(&output);
+        show_cursor();
+        popup_rows
+    }
+
+    // Clears any popup and moves to the start of the next line.
+    fn finish_line(&self, popup_rows: usize) {
+        if popup_rows > 0 {
+            print("\x1B[J");
+        }
+        print("\r\n");
+    }
+}
+
+fn replace_range(input: &mut TextInput, start: usize, end: usize, replacement: &str) {
+    let text = input.text();
+    let updated = format!("{}{}{}", &text[..start], replacement, &text[end..]);
+    input.set_value(&updated);
+    input.set_cursor(start + replacement.len());
+}
+
+// Swaps the two grapheme clusters before the cursor, or around it at the end of a word.
+fn transpose(input: &mut TextInput) {
+    let text = input.text().to_string();
+    let mut cursor = input.cursor();
+    if cursor < text.len() && cursor > 0 {
+        cursor = crate::grapheme::next_boundary(&text, cursor);
+    }
+    let middle = prev_boundary(&text, cursor);
+    let start = prev_boundary(&text, middle);
+    if start == middle {
+        return;
+    }
+    let swapped = format!("{}{}{}{}", &text[..start], &text[middle..cursor], &text[start..middle], &text[cursor..]);
+    input.set_value(&swapped);
+    input.set_cursor(cursor);
+}
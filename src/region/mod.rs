@@ -0,0 +1,202 @@
+//! This module provides regions: rectangular areas of the screen with their own origin
+//! and clipping.
+//!
+//! While a region is active, every position passed to `move_cursor_to` (and therefore to
+//! `draw_box`, `horizontal_line` and the other drawing functions) is relative to the
+//! region's top-left corner, and any output that falls outside the region is dropped.
+//! Regions nest: a region entered inside another is positioned in its parent's
+//! coordinates and clipped to its parent.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::region::with_region;
+//!
+//! with_region(Rect::new(41, 1, 40, 12), || {
+//!     // Drawn at column 41 of the screen and cut off at column 80.
+//!     rbox::draw_box(1, 1, 60, 5, BoxStyle::Single);
+//! });
+//! ```
+//!
+//! Screen-wide operations such as `clear_screen` are not affected by regions.
+
+use crate::ansi::{segments, Segment};
+use crate::geometry::{Point, Rect, Size};
+use crate::grapheme::grapheme_width;
+use crate::*;
+use std::sync::Mutex;
+
+/// An active region in absolute screen coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// The full area of the region. Its top-left corner is local position `(1, 1)`.
+    pub area: Rect,
+    /// The visible part of the region, after clipping to its parents and the viewport.
+    pub clip: Rect,
+}
+
+impl Region {
+    /// Returns the region's area in its own coordinates, starting at `(1, 1)`.
+    pub fn local_rect(&self) -> Rect {
+        Rect::new(1, 1, self.area.width, self.area.height)
+    }
+
+    /// Converts a position inside the region to an absolute screen position.
+    pub fn to_absolute(&self, point: Point) -> Option<Point> {
+        self.area.position().offset(point.x as i32 - 1, point.y as i32 - 1)
+    }
+
+    /// Converts an absolute screen position to a position inside the region, or `None`
+    /// if it lies above or to the left of the region.
+    pub fn to_local(&self, point: Point) -> Option<Point> {
+        let offset = point - self.area.position();
+        Point::ORIGIN.offset(offset.dx, offset.dy)
+    }
+}
+
+struct Frame {
+    region: Region,
+    // The cursor position inside the region, as last set by `move_cursor_to` and advanced
+    // by printed text.
+    cursor: Point,
+    // True if the terminal cursor is not at `cursor` and must be moved before the next
+    // visible character.
+    needs_move: bool,
+}
+
+static REGIONS: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+fn regions() -> std::sync::MutexGuard<'static, Vec<Frame>> {
+    REGIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Keeps a region active until it is dropped.
+#[must_use = "the region is left as soon as the guard is dropped"]
+pub struct RegionGuard {
+    depth: usize,
+}
+
+impl Drop for RegionGuard {
+    fn drop(&mut self) {
+        let mut stack = regions();
+        stack.truncate(self.depth);
+        if let Some(parent) = stack.last_mut() {
+            parent.needs_move = true;
+        }
+    }
+}
+
+/// Enters a region. The rectangle is given in the coordinates of the current region, or
+/// of the screen if no region is active. The region stays active until the returned
+/// guard is dropped.
+pub fn enter_region(rect: Rect) -> RegionGuard {
+    let mut stack = regions();
+    let (viewport_width, viewport_height) = get_viewport();
+    let (origin, parent_clip) = match stack.last() {
+        Some(parent) => (parent.region.area.position(), parent.region.clip),
+        None => (Point::ORIGIN, Rect::new(1, 1, viewport_width, viewport_height)),
+    };
+
+    let position = origin.saturating_offset(rect.x as i32 - 1, rect.y as i32 - 1);
+    let area = Rect::from_point_size(position, rect.size());
+    let clip = area
+        .intersection(&parent_clip)
+        .unwrap_or(Rect::from_point_size(position, Size::default()));
+
+    let depth = stack.len();
+    stack.push(Frame { region: Region { area, clip }, cursor: Point::ORIGIN, needs_move: true });
+    RegionGuard { depth }
+}
+
+/// Runs a function with a region active and leaves the region afterwards.
+pub fn with_region<T>(rect: Rect, f: impl FnOnce() -> T) -> T {
+    let _guard = enter_region(rect);
+    f()
+}
+
+/// Returns the innermost active region, if any.
+pub fn current_region() -> Option<Region> {
+    regions().last().map(|frame| frame.region)
+}
+
+/// Returns the size of the innermost active region, or of the viewport if no region is
+/// active.
+pub fn region_size() -> Size {
+    match current_region() {
+        Some(region) => region.area.size(),
+        None => get_viewport().into(),
+    }
+}
+
+/// Moves the cursor inside the active region. Returns false if no region is active.
+pub(crate) fn move_cursor(x: u16, y: u16) -> bool {
+    let mut stack = regions();
+    match stack.last_mut() {
+        Some(frame) => {
+            frame.cursor = Point::new(x, y);
+            frame.needs_move = true;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Rewrites output for the active region: characters outside the region are dropped and
+/// cursor positioning sequences are translated to screen coordinates. Returns `None` if
+/// no region is active.
+pub(crate) fn clip_output(text: &str) -> Option<String> {
+    let mut stack = regions();
+    let frame = stack.last_mut()?;
+    let mut output = String::with_capacity(text.len());
+
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(escape) => match parse_cursor_position(escape) {
+                Some(point) => {
+                    frame.cursor = point;
+                    frame.needs_move = true;
+                }
+                None => output.push_str(escape),
+            },
+            Segment::Grapheme("\n") | Segment::Grapheme("\r\n") => {
+                frame.cursor = Point::new(1, frame.cursor.y.saturating_add(1));
+                frame.needs_move = true;
+            }
+            Segment::Grapheme("\r") => {
+                frame.cursor.x = 1;
+                frame.needs_move = true;
+            }
+            Segment::Grapheme(grapheme) => {
+                let width = grapheme_width(grapheme) as u16;
+                let visible = frame.region.to_absolute(frame.cursor).filter(|start| {
+                    let cell = Rect::from_point_size(*start, Size::new(width.max(1), 1));
+                    frame.region.clip.contains_rect(&cell)
+                });
+                match visible {
+                    Some(position) => {
+                        if frame.needs_move {
+                            output.push_str(&format!("\x1B[{};{}H", position.y, position.x));
+                            frame.needs_move = false;
+                        }
+                        output.push_str(grapheme);
+                    }
+                    None => frame.needs_move = true,
+                }
+                frame.cursor.x = frame.cursor.x.saturating_add(width);
+            }
+        }
+    }
+    Some(output)
+}
+
+// Parses `ESC [ row ; column H`, returning the position it moves to.
+fn parse_cursor_position(escape: &str) -> Option<Point> {
+    let params = escape.strip_prefix("\x1B[")?.strip_suffix(['H', 'f'])?;
+    let mut parts = params.split(';');
+    let parse = |part: Option<&str>| match part {
+        None | Some("") => Some(1),
+        Some(value) => value.parse::<u16>().ok(),
+    };
+    let y = parse(parts.next())?;
+    let x = parse(parts.next())?;
+    Some(Point::new(x.max(1), y.max(1)))
+}
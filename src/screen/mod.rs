@@ -0,0 +1,298 @@
+//! This module provides an off-screen buffer of styled cells. Drawing into the buffer does
+//! not touch the terminal; `render` copies the whole buffer to the screen in one pass.
+//!
+//! Coordinates are 1-based, like `move_cursor_to`. Anything drawn outside the buffer is
+//! clipped.
+//!
+//! Each cell holds one grapheme cluster, so an accented letter or an emoji sequence is
+//! never split across cells. A wide cluster, such as most emoji, fills two cells: the
+//! cluster itself and a continuation cell to its right with an empty `symbol`.
+//! Continuation cells are skipped when rendering.
+
+use crate::grapheme::{grapheme_width, graphemes};
+use crate::style::Style;
+use crate::*;
+
+/// A single grapheme cluster together with its style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: String,
+    pub style: Style,
+}
+
+impl Cell {
+    /// Returns true if the cell is covered by the right half of a wide cluster.
+    pub fn is_continuation(&self) -> bool {
+        self.symbol.is_empty()
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell { symbol: String::from(" "), style: Style::default() }
+    }
+}
+
+/// A rectangular grid of cells that can be drawn into and rendered to the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenBuffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+    cursor: Option<Point>,
+}
+
+impl ScreenBuffer {
+    /// Creates a buffer of the given size filled with blank cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        ScreenBuffer {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+            cursor: None,
+        }
+    }
+
+    /// Creates a buffer with the size of the current viewport.
+    pub fn from_viewport() -> Self {
+        let (width, height) = get_viewport();
+        Self::new(width, height)
+    }
+
+    /// Returns the width of the buffer in cells.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Returns the height of the buffer in cells.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns the area the buffer covers, starting at `(1, 1)`.
+    pub fn area(&self) -> Rect {
+        Rect::new(1, 1, self.width, self.height)
+    }
+
+    /// Resets every cell to a blank, unstyled space and hides the cursor.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+        self.cursor = None;
+    }
+
+    /// Fills the part of a rectangle inside the buffer with a character.
+    pub fn fill(&mut self, area: Rect, ch: char, style: Style) {
+        let Some(area) = area.intersection(&self.area()) else {
+            return;
+        };
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                self.set_char(x, y, ch, style);
+            }
+        }
+    }
+
+    /// Sets where the terminal cursor is shown after rendering, or hides it with `None`.
+    pub fn set_cursor(&mut self, cursor: Option<Point>) {
+        self.cursor = cursor;
+    }
+
+    /// Returns where the terminal cursor should be shown, if anywhere.
+    pub fn cursor(&self) -> Option<Point> {
+        self.cursor
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        if x == 0 || y == 0 || x > self.width || y > self.height {
+            None
+        } else {
+            Some((y - 1) as usize * self.width as usize + (x - 1) as usize)
+        }
+    }
+
+    /// Returns the cell at the given position, or `None` if it lies outside the buffer.
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    // Blanks the other half of any wide cluster that overlaps the given cell.
+    fn break_wide_cluster(&mut self, x: u16, y: u16) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        if self.cells[i].is_continuation() {
+            if let Some(left) = self.index(x.wrapping_sub(1), y) {
+                self.cells[left].symbol = String::from(" ");
+            }
+            self.cells[i].symbol = String::from(" ");
+        }
+        if let Some(right) = self.index(x + 1, y) {
+            if self.cells[right].is_continuation() {
+                self.cells[right].symbol = String::from(" ");
+            }
+        }
+    }
+
+    /// Writes a grapheme cluster with the given style at the given position.
+    ///
+    /// A wide cluster also fills the cell to its right; if that cell is outside the
+    /// buffer, a space is written instead. Zero-width clusters are ignored.
+    pub fn set_grapheme(&mut self, x: u16, y: u16, cluster: &str, style: Style) {
+        let width = grapheme_width(cluster);
+        if width == 0 || self.index(x, y).is_none() {
+            return;
+        }
+        self.break_wide_cluster(x, y);
+        let i = self.index(x, y).unwrap();
+        if width == 2 {
+            if self.index(x + 1, y).is_none() {
+                self.cells[i] = Cell { symbol: String::from(" "), style };
+                return;
+            }
+            self.break_wide_cluster(x + 1, y);
+            self.cells[i + 1] = Cell { symbol: String::new(), style };
+        }
+        self.cells[i] = Cell { symbol: cluster.to_string(), style };
+    }
+
+    /// Writes a character with the given style at the given position.
+    pub fn set_char(&mut self, x: u16, y: u16, ch: char, style: Style) {
+        self.set_grapheme(x, y, ch.encode_utf8(&mut [0; 4]), style);
+    }
+
+    /// Writes a string on a single row starting at the given position.
+    ///
+    /// The position advances by the display width of each grapheme cluster. Returns the
+    /// column just after the last cluster written.
+    pub fn put_str(&mut self, x: u16, y: u16, text: &str, style: Style) -> u16 {
+        let mut column = x;
+        for cluster in graphemes(text) {
+            self.set_grapheme(column, y, cluster, style);
+            column = column.saturating_add(grapheme_width(cluster) as u16);
+        }
+        column
+    }
+
+    /// Copies part of the buffer into a new buffer the size of `area`. Parts of `area`
+    /// outside this buffer are left blank.
+    pub fn copy_area(&self, area: Rect) -> ScreenBuffer {
+        let mut copy = ScreenBuffer::new(area.width, area.height);
+        for y in 0..area.height {
+            for x in 0..area.width {
+                if let Some(cell) = self.get(area.x + x, area.y + y) {
+                    if !cell.is_continuation() {
+                        copy.set_grapheme(x + 1, y + 1, &cell.symbol, cell.style);
+                    }
+                }
+            }
+        }
+        copy
+    }
+
+    /// Draws another buffer into this one with its top-left corner at the given position.
+    pub fn draw_buffer(&mut self, source: &ScreenBuffer, x: u16, y: u16) {
+        for row in 1..=source.height {
+            for column in 1..=source.width {
+                let cell = source.get(column, row).unwrap();
+                if !cell.is_continuation() {
+                    self.set_grapheme(x + column - 1, y + row - 1, &cell.symbol, cell.style);
+                }
+            }
+        }
+    }
+
+    /// Returns the text of a row without styles, mainly useful for inspecting the buffer.
+    pub fn row_text(&self, y: u16) -> String {
+        (1..=self.width)
+            .filter_map(|x| self.get(x, y))
+            .map(|cell| cell.symbol.as_str())
+            .collect()
+    }
+
+    /// Returns a row as a string with escape sequences for its styles, ending with a
+    /// reset. Useful for printing a row at the current cursor position.
+    pub fn row_ansi(&self, y: u16) -> String {
+        let mut output = String::new();
+        let mut current: Option<Style> = None;
+        for cell in (1..=self.width).filter_map(|x| self.get(x, y)) {
+            if cell.is_continuation() {
+                continue;
+            }
+            if current != Some(cell.style) {
+                output.push_str(&cell.style.to_ansi());
+                current = Some(cell.style);
+            }
+            output.push_str(&cell.symbol);
+        }
+        output.push_str("\x1B[0m");
+        output
+    }
+
+    /// Draws the whole buffer to the terminal with its top-left corner at (1, 1).
+    pub fn render(&self) {
+        self.render_at(1, 1);
+    }
+
+    /// Draws the whole buffer to the terminal with its top-left corner at the given position.
+    pub fn render_at(&self, x: u16, y: u16) {
+        let (viewport_width, viewport_height) = get_viewport();
+        if x == 0 || y == 0 || x + self.width - 1 > viewport_width || y + self.height - 1 > viewport_height {
+            handle_boundary_error("Screen buffer extends beyond viewport");
+            return;
+        }
+
+        let mut output = String::new();
+        let mut current: Option<Style> = None;
+        for row in 0..self.height {
+            output.push_str(&format!("\x1B[{};{}H", y + row, x));
+            for column in 1..=self.width {
+                let cell = &self.cells[self.index(column, row + 1).unwrap()];
+                if cell.is_continuation() {
+                    continue;
+                }
+                if current != Some(cell.style) {
+                    output.push_str(&cell.style.to_ansi());
+                    current = Some(cell.style);
+                }
+                output.push_str(&cell.symbol);
+            }
+        }
+        output.push_str("\x1B[0m");
+        print(&output);
+    }
+
+    /// Draws only the cells that differ from `previous`, which should be the buffer last
+    /// drawn at the same position. Falls back to `render_at` if the sizes differ.
+    pub fn render_diff_at(&self, previous: &ScreenBuffer, x: u16, y: u16) {
+        if self.width != previous.width || self.height != previous.height {
+            self.render_at(x, y);
+            return;
+        }
+
+        let mut output = String::new();
+        let mut current: Option<Style> = None;
+        let mut next_position: Option<(u16, u16)> = None;
+        for row in 1..=self.height {
+            for column in 1..=self.width {
+                let i = self.index(column, row).unwrap();
+                let cell = &self.cells[i];
+                if cell.is_continuation() || *cell == previous.cells[i] {
+                    continue;
+                }
+                if next_position != Some((column, row)) {
+                    output.push_str(&format!("\x1B[{};{}H", y + row - 1, x + column - 1));
+                }
+                if current != Some(cell.style) {
+                    output.push_str(&cell.style.to_ansi());
+                    current = Some(cell.style);
+                }
+                output.push_str(&cell.symbol);
+                next_position = Some((column + grapheme_width(&cell.symbol).max(1) as u16, row));
+            }
+        }
+        if !output.is_empty() {
+            output.push_str("\x1B[0m");
+            print(&output);
+        }
+    }
+}
@@ -57,3 +57,10 @@ pub fn star_symbol_to_char(symbol: StarSymbol) -> char {
         StarSymbol::HeavyFourBalloonStar => '✣',
     }
 }
+
+impl StarSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(star_symbol_to_char(*self))
+    }
+}
@@ -0,0 +1,150 @@
+//! This module provides the `Style` type, which bundles a foreground color, a background
+//! color and a set of text attributes so they can be applied to the terminal in one call.
+
+use crate::*;
+
+/// A combination of optional colors and text attributes.
+///
+/// Colors left as `None` fall back to the terminal defaults when the style is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    attributes: u16,
+}
+
+impl Style {
+    /// Creates an empty style that uses the terminal defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the style with the given foreground color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Returns a copy of the style with the given background color.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Returns a copy of the style with the given attribute switched on.
+    ///
+    /// `Attribute::Reset` clears every attribute set so far.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        if attribute == Attribute::Reset {
+            self.attributes = 0;
+        } else {
+            self.attributes |= 1 << attribute as u16;
+        }
+        self
+    }
+
+    /// Returns true if the given attribute is part of the style.
+    pub fn has_attribute(&self, attribute: Attribute) -> bool {
+        attribute != Attribute::Reset && self.attributes & (1 << attribute as u16) != 0
+    }
+
+    /// Returns the attributes that are part of the style.
+    pub fn attributes(&self) -> Vec<Attribute> {
+        ALL_ATTRIBUTES
+            .iter()
+            .copied()
+            .filter(|a| self.has_attribute(*a))
+            .collect()
+    }
+
+    /// Returns true if the style has no colors and no attributes.
+    pub fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+
+    /// Layers `other` on top of this style.
+    ///
+    /// Colors set in `other` replace the ones in `self`; attributes are combined.
+    pub fn patch(&self, other: Style) -> Style {
+        Style {
+            foreground: other.foreground.or(self.foreground),
+            background: other.background.or(self.background),
+            attributes: self.attributes | other.attributes,
+        }
+    }
+
+    /// Builds the escape sequence that resets the terminal and then applies this style.
+    pub fn to_ansi(&self) -> String {
+        let mut codes = vec![String::from("0")];
+        for attribute in self.attributes() {
+            codes.push((attribute as u8).to_string());
+        }
+        if let Some(color) = self.foreground {
+            codes.push(format!("3{}", color as u8));
+        }
+        if let Some(color) = self.background {
+            codes.push(format!("4{}", color as u8));
+        }
+        format!("\x1B[{}m", codes.join(";"))
+    }
+}
+
+impl From<Color> for Style {
+    fn from(color: Color) -> Self {
+        Style::new().fg(color)
+    }
+}
+
+impl From<Attribute> for Style {
+    fn from(attribute: Attribute) -> Self {
+        Style::new().attribute(attribute)
+    }
+}
+
+const ALL_ATTRIBUTES: [Attribute; 6] = [
+    Attribute::Bright,
+    Attribute::Dim,
+    Attribute::Underscore,
+    Attribute::Blink,
+    Attribute::Reverse,
+    Attribute::Hidden,
+];
+
+/// Applies the given style to subsequent text output, replacing any previous style.
+pub fn set_style(style: Style) {
+    print(&style.to_ansi());
+}
+
+/// Converts a color name such as `"red"` to the corresponding `Color`.
+///
+/// Matching is case-insensitive; unknown names return `None`.
+pub fn color_from_name(name: &str) -> Option<Color> {
+    match name.to_ascii_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
+/// Converts an attribute name such as `"bold"` to the corresponding `Attribute`.
+///
+/// Both the crate's names (`bright`, `underscore`) and the common aliases
+/// (`bold`, `underline`) are accepted. Unknown names return `None`.
+pub fn attribute_from_name(name: &str) -> Option<Attribute> {
+    match name.to_ascii_lowercase().as_str() {
+        "reset" => Some(Attribute::Reset),
+        "bright" | "bold" => Some(Attribute::Bright),
+        "dim" => Some(Attribute::Dim),
+        "underscore" | "underline" => Some(Attribute::Underscore),
+        "blink" => Some(Attribute::Blink),
+        "reverse" => Some(Attribute::Reverse),
+        "hidden" => Some(Attribute::Hidden),
+        _ => None,
+    }
+}
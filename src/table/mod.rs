@@ -0,0 +1,792 @@
+//! This module provides a table widget that draws rows of text in a grid of box-drawing
+//! lines.
+//!
+//! Each `Column` has a header, an optional width `Constraint`, an alignment and an
+//! optional formatter that turns the stored cell value into the text shown. Columns
+//! without a width fit their widest cell; text that does not fit is cut short with an
+//! ellipsis. The grid uses the junctions of `SingleBox`, or of `DoubleBox` for the double
+//! box styles, with a separator line below the header and optional zebra striping.
+//!
+//! Up, Down, PageUp, PageDown, Home and End move the cursor and Enter chooses the row under
+//! it. The digit keys sort by the first nine columns, pressing the same digit again
+//! reverses the order, and 0 goes back to the original order. Clicking a header sorts by
+//! that column, clicking a row moves the cursor and the mouse wheel scrolls.
+//!
+//! `render_to_string` draws the whole table without a cursor, for printing reports.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::table::{Column, Table};
+//! use rpian_terminal::text::HorizontalAlign;
+//!
+//! let table = Table::new(vec![Column::new("Name"), Column::new("Size").align(HorizontalAlign::Right)])
+//!     .row(&["notes.txt", "1204"])
+//!     .row(&["photo.jpg", "88310"]);
+//! println(&table.render_to_string());
+//! ```
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::ansi::{center, pad_left, pad_right, truncate_with_ellipsis};
+use crate::event::{read_key_event, Event, KeyCode, KeyEvent, MouseButton, MouseKind};
+use crate::geometry::{Rect, Size};
+use crate::layout::{solve, Constraint};
+use crate::rbox::{
+    double_box_to_char, get_box_corners, get_box_line_style, get_line_char, single_box_to_char, DoubleBox, SingleBox,
+};
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::text::HorizontalAlign;
+use crate::theme::{role_style, Role};
+use crate::triangle::{triangle_symbol_to_char, TriangleSymbol};
+use crate::widget::{EventResult, Widget};
+use crate::width::str_width;
+use crate::*;
+
+/// Turns a cell value into the text shown in a `Table`.
+pub type Formatter = Box<dyn Fn(&str) -> String>;
+
+/// A column of a `Table`.
+pub struct Column {
+    pub header: String,
+    /// How wide the text of the column is, or `None` to fit the widest cell.
+    pub width: Option<Constraint>,
+    /// How cells are aligned. `Justify` is treated as `Left`.
+    pub align: HorizontalAlign,
+    formatter: Option<Formatter>,
+}
+
+impl Column {
+    pub fn new(header: &str) -> Self {
+        Column { header: header.to_string(), width: None, align: HorizontalAlign::Left, formatter: None }
+    }
+
+    pub fn width(mut self, width: Constraint) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn align(mut self, align: HorizontalAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets a function that turns a cell value into the text shown. Sorting still uses the
+    /// original value.
+    pub fn format(mut self, formatter: impl Fn(&str) -> String + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Returns the text shown for a cell value. Line breaks and tabs become spaces, since a
+    /// cell is a single line.
+    pub fn format_cell(&self, value: &str) -> String {
+        let text = match &self.formatter {
+            Some(formatter) => formatter(value),
+            None => value.to_string(),
+        };
+        text.replace("\r\n", " ").replace(['\r', '\n', '\t'], " ")
+    }
+
+    // Pads `text` to `width` columns according to the alignment.
+    fn align_text(&self, text: &str, width: usize) -> String {
+        match self.align {
+            HorizontalAlign::Right => pad_left(text, width),
+            HorizontalAlign::Center => center(text, width),
+            _ => pad_right(text, width),
+        }
+    }
+}
+
+/// The direction rows are sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// A grid of rows with a header, sorting and a row cursor.
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+    pub box_style: BoxStyle,
+    pub show_header: bool,
+    pub header_style: Style,
+    pub border_style: Style,
+    /// The style of the row under the cursor while the table has focus.
+    pub highlight: Style,
+    /// The style of every other row, or `None` for no striping.
+    pub zebra: Option<Style>,
+    /// Ends text that had to be cut short.
+    pub ellipsis: String,
+    /// Spaces on each side of the text in a cell.
+    pub padding: u16,
+    // Row indices in the order they are shown.
+    order: Vec<usize>,
+    sort: Option<(usize, SortOrder)>,
+    // A position in `order`.
+    cursor: usize,
+    focused: bool,
+    submitted: bool,
+    cancelled: bool,
+    // The first visible position and the area of the last render.
+    offset: Cell<usize>,
+    area: Cell<Rect>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Table {
+            columns,
+            rows: Vec::new(),
+            box_style: BoxStyle::Single,
+            show_header: true,
+            header_style: role_style(Role::Accent),
+            border_style: role_style(Role::Border),
+            highlight: role_style(Role::Selection),
+            zebra: None,
+            ellipsis: String::from("…"),
+            padding: 1,
+            order: Vec::new(),
+            sort: None,
+            cursor: 0,
+            focused: false,
+            submitted: false,
+            cancelled: false,
+            offset: Cell::new(0),
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    /// Creates a table with a left-aligned, content-sized column for each header.
+    pub fn with_headers(headers: &[&str]) -> Self {
+        Self::new(headers.iter().map(|header| Column::new(header)).collect())
+    }
+
+    pub fn box_style(mut self, box_style: BoxStyle) -> Self {
+        self.box_style = box_style;
+        self
+    }
+
+    pub fn show_header(mut self, show_header: bool) -> Self {
+        self.show_header = show_header;
+        self
+    }
+
+    pub fn header_style(mut self, header_style: Style) -> Self {
+        self.header_style = header_style;
+        self
+    }
+
+    pub fn border_style(mut self, border_style: Style) -> Self {
+        self.border_style = border_style;
+        self
+    }
+
+    pub fn highlight(mut self, highlight: Style) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    pub fn zebra(mut self, zebra: Option<Style>) -> Self {
+        self.zebra = zebra;
+        self
+    }
+
+    pub fn ellipsis(mut self, ellipsis: &str) -> Self {
+        self.ellipsis = ellipsis.to_string();
+        self
+    }
+
+    pub fn padding(mut self, padding: u16) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Adds a row. Missing cells are shown empty and extra cells are ignored.
+    pub fn row(mut self, cells: &[&str]) -> Self {
+        self.add_row(cells.iter().map(|cell| cell.to_string()).collect());
+        self
+    }
+
+    /// Adds a row, keeping the current sort order.
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+        if self.sort.is_some() {
+            self.apply_sort();
+        } else {
+            self.order.push(self.rows.len() - 1);
+        }
+    }
+
+    /// Removes every row.
+    pub fn clear_rows(&mut self) {
+        self.rows.clear();
+        self.order.clear();
+        self.cursor = 0;
+        self.offset.set(0);
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn column_mut(&mut self, index: usize) -> Option<&mut Column> {
+        self.columns.get_mut(index)
+    }
+
+    /// Returns the rows in the order they were added.
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the index, in the order rows were added, of the row under the cursor.
+    pub fn selected_row(&self) -> Option<usize> {
+        self.order.get(self.cursor).copied()
+    }
+
+    /// Moves the cursor to a row, given by its index in the order rows were added.
+    pub fn select_row(&mut self, index: usize) {
+        if let Some(position) = self.order.iter().position(|&row| row == index) {
+            self.cursor = position;
+        }
+    }
+
+    /// Returns the column the rows are sorted by and the direction, if they are sorted.
+    pub fn sort_order(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Sorts the rows by a column. Values that are finite numbers are compared as numbers and
+    /// come before text, which is compared without regard to case.
+    pub fn sort_by(&mut self, column: usize, order: SortOrder) {
+        if column < self.columns.len() {
+            self.sort = Some((column, order));
+            self.apply_sort();
+        }
+    }
+
+    /// Sorts by a column in ascending order, or reverses the order if the rows are already
+    /// sorted by it.
+    pub fn toggle_sort(&mut self, column: usize) {
+        let order = match self.sort {
+            Some((current, SortOrder::Ascending)) if current == column => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        };
+        self.sort_by(column, order);
+    }
+
+    /// Shows the rows in the order they were added again.
+    pub fn clear_sort(&mut self) {
+        self.sort = None;
+        self.apply_sort();
+    }
+
+    // Rebuilds the display order, keeping the cursor on the same row.
+    fn apply_sort(&mut self) {
+        let current = self.selected_row();
+        self.order = (0..self.rows.len()).collect();
+        if let Some((column, order)) = self.sort {
+            let rows = &self.rows;
+            self.order.sort_by(|&a, &b| {
+                let ordering = compare_cells(cell(rows, a, column), cell(rows, b, column));
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+        if let Some(current) = current {
+            self.select_row(current);
+        }
+    }
+
+    /// Returns true once Enter has been pressed.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Returns true once Esc or Ctrl-C has been pressed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Clears the submitted and cancelled flags.
+    pub fn reset_state(&mut self) {
+        self.submitted = false;
+        self.cancelled = false;
+    }
+
+    // Returns the header text, with an arrow if the rows are sorted by the column.
+    fn header_text(&self, column: usize) -> String {
+        let header = &self.columns[column].header;
+        match self.sort {
+            Some((sorted, order)) if sorted == column => {
+                let arrow = match order {
+                    SortOrder::Ascending => TriangleSymbol::SmallBlackUpPointing,
+                    SortOrder::Descending => TriangleSymbol::SmallBlackDownPointing,
+                };
+                format!("{} {}", header, triangle_symbol_to_char(arrow))
+            }
+            _ => header.clone(),
+        }
+    }
+
+    // Returns the width of the widest text in a column, including the header.
+    fn content_width(&self, column: usize) -> u16 {
+        let header = if self.show_header { str_width(&self.header_text(column)) } else { 0 };
+        let widest = (0..self.rows.len())
+            .map(|row| str_width(&self.columns[column].format_cell(cell(&self.rows, row, column))))
+            .max()
+            .unwrap_or(0);
+        header.max(widest).min(u16::MAX as usize) as u16
+    }
+
+    // Returns the number of cells taken by borders and padding.
+    fn overhead(&self) -> u16 {
+        let count = self.columns.len();
+        let cells = count.saturating_mul(2 * self.padding as usize + 1).saturating_add(1);
+        cells.min(u16::MAX as usize) as u16
+    }
+
+    // Works out the width of the text in each column for a table `width` cells wide.
+    fn column_widths(&self, width: u16) -> Vec<u16> {
+        let constraints: Vec<Constraint> = (0..self.columns.len())
+            .map(|column| self.columns[column].width.unwrap_or(Constraint::Length(self.content_width(column))))
+            .collect();
+        solve(&constraints, width.saturating_sub(self.overhead()))
+    }
+
+    // Returns the width of each column when there is no limit on the table's width.
+    // Proportional widths fit the content.
+    fn natural_widths(&self) -> Vec<u16> {
+        (0..self.columns.len())
+            .map(|column| {
+                let content = self.content_width(column);
+                match self.columns[column].width {
+                    Some(Constraint::Length(width)) => width,
+                    Some(Constraint::Min(width)) => content.max(width),
+                    Some(Constraint::Max(width)) => content.min(width),
+                    _ => content,
+                }
+            })
+            .collect()
+    }
+
+    fn grid_width(&self, widths: &[u16]) -> u16 {
+        widths.iter().fold(self.overhead(), |total, width| total.saturating_add(*width))
+    }
+
+    // The number of rows taken by the header and its separator.
+    fn header_rows(&self) -> u16 {
+        if self.show_header {
+            2
+        } else {
+            0
+        }
+    }
+
+    // The number of rows that fit in the last rendered area.
+    fn visible_rows(&self) -> usize {
+        self.area.get().height.saturating_sub(2 + self.header_rows()) as usize
+    }
+
+    // Returns the left end, right end, crossing and line of a separator row. The corners
+    // match `draw_box`; the junctions come from `SingleBox` or, for double styles, `DoubleBox`.
+    fn junctions(&self, row: Junction) -> [char; 4] {
+        let double = matches!(self.box_style, BoxStyle::Double | BoxStyle::DoubleRounded);
+        let [left, right, cross] = match (row, double) {
+            (Junction::Middle, false) => {
+                let junctions = [SingleBox::VerticalLeft, SingleBox::VerticalRight, SingleBox::VerticalHorizontal];
+                junctions.map(single_box_to_char)
+            }
+            (Junction::Middle, true) => {
+                let junctions = [DoubleBox::VerticalLeft, DoubleBox::VerticalRight, DoubleBox::VerticalHorizontal];
+                junctions.map(double_box_to_char)
+            }
+            (Junction::Top, false) => [' ', ' ', single_box_to_char(SingleBox::HorizontalDown)],
+            (Junction::Top, true) => [' ', ' ', double_box_to_char(DoubleBox::HorizontalDown)],
+            (Junction::Bottom, false) => [' ', ' ', single_box_to_char(SingleBox::HorizontalUp)],
+            (Junction::Bottom, true) => [' ', ' ', double_box_to_char(DoubleBox::HorizontalUp)],
+        };
+        let corners = get_box_corners(self.box_style);
+        let (left, right) = match row {
+            Junction::Top => (corners[0], corners[1]),
+            Junction::Middle => (left, right),
+            Junction::Bottom => (corners[2], corners[3]),
+        };
+        [left, right, cross, get_line_char(get_box_line_style(self.box_style), false)]
+    }
+
+    // Draws a horizontal separator with junctions where the column lines cross it.
+    fn draw_separator(&self, buffer: &mut ScreenBuffer, x: u16, y: u16, widths: &[u16], row: Junction) {
+        let [left, right, cross, horizontal] = self.junctions(row);
+        let mut column = x;
+        buffer.set_char(column, y, left, self.border_style);
+        for (i, width) in widths.iter().enumerate() {
+            for _ in 0..width + 2 * self.padding {
+                column += 1;
+                buffer.set_char(column, y, horizontal, self.border_style);
+            }
+            column += 1;
+            let junction = if i + 1 == widths.len() { right } else { cross };
+            buffer.set_char(column, y, junction, self.border_style);
+        }
+    }
+
+    // Draws a row of cells between vertical lines.
+    fn draw_cells(&self, buffer: &mut ScreenBuffer, x: u16, y: u16, widths: &[u16], cells: &[String], style: Style) {
+        let vertical = get_line_char(get_box_line_style(self.box_style), true);
+        let mut column = x;
+        buffer.set_char(column, y, vertical, self.border_style);
+        for (i, width) in widths.iter().enumerate() {
+            let span = width + 2 * self.padding;
+            buffer.fill(Rect::new(column + 1, y, span, 1), ' ', style);
+            let text = truncate_with_ellipsis(&cells[i], *width as usize, &self.ellipsis);
+            let text = self.columns[i].align_text(&text, *width as usize);
+            buffer.put_str(column + 1 + self.padding, y, &text, style);
+            column += span + 1;
+            buffer.set_char(column, y, vertical, self.border_style);
+        }
+    }
+
+    // Draws the grid with the rows at the given display positions.
+    fn draw_grid(
+        &self,
+        buffer: &mut ScreenBuffer,
+        x: u16,
+        y: u16,
+        widths: &[u16],
+        rows: Range<usize>,
+        cursor: Option<usize>,
+    ) {
+        let mut row = y;
+        self.draw_separator(buffer, x, row, widths, Junction::Top);
+        row += 1;
+        if self.show_header {
+            self.draw_header(buffer, x, row, widths);
+            self.draw_separator(buffer, x, row + 1, widths, Junction::Middle);
+            row += 2;
+        }
+        for position in rows {
+            self.draw_row(buffer, x, row, widths, position, cursor);
+            row += 1;
+        }
+        self.draw_separator(buffer, x, row, widths, Junction::Bottom);
+    }
+
+    fn draw_header(&self, buffer: &mut ScreenBuffer, x: u16, y: u16, widths: &[u16]) {
+        let headers: Vec<String> = (0..self.columns.len()).map(|column| self.header_text(column)).collect();
+        self.draw_cells(buffer, x, y, widths, &headers, self.header_style);
+    }
+
+    // Draws the row at `position` in the current sort order.
+    fn draw_row(
+        &self,
+        buffer: &mut ScreenBuffer,
+        x: u16,
+        y: u16,
+        widths: &[u16],
+        position: usize,
+        cursor: Option<usize>,
+    ) {
+        let index = self.order[position];
+        let cells: Vec<String> = (0..self.columns.len())
+            .map(|column| self.columns[column].format_cell(cell(&self.rows, index, column)))
+            .collect();
+        let style = match self.zebra {
+            _ if cursor == Some(position) => self.highlight,
+            Some(zebra) if position % 2 == 1 => zebra,
+            _ => Style::default(),
+        };
+        self.draw_cells(buffer, x, y, widths, &cells, style);
+    }
+
+    // Scrolls so the cursor is visible in `rows` rows.
+    fn scroll_to_cursor(&self, rows: usize) {
+        let mut offset = self.offset.get().min(self.len().saturating_sub(rows));
+        if self.cursor < offset {
+            offset = self.cursor;
+        } else if rows > 0 && self.cursor >= offset + rows {
+            offset = self.cursor + 1 - rows;
+        }
+        self.offset.set(offset);
+    }
+
+    /// Draws the visible rows into `area` of a buffer. `active` highlights the cursor.
+    ///
+    /// Columns are sized to fit the width of `area`. The table is only as tall as its rows
+    /// need, so part of `area` may be left untouched.
+    pub fn draw(&self, area: Rect, buffer: &mut ScreenBuffer, active: bool) {
+        self.area.set(area);
+        if self.columns.is_empty() || area.height < 2 + self.header_rows() || area.width <= self.overhead() {
+            return;
+        }
+        let rows = self.visible_rows();
+        self.scroll_to_cursor(rows);
+        let offset = self.offset.get();
+        let widths = self.column_widths(area.width);
+        let count = rows.min(self.len() - offset);
+        self.draw_grid(buffer, area.x, area.y, &widths, offset..offset + count, active.then_some(self.cursor));
+    }
+
+    /// Draws every row of the table, with each column as wide as its content, and returns
+    /// the lines joined with newlines. Lines with styled cells contain escape sequences.
+    pub fn render_to_string(&self) -> String {
+        if self.columns.is_empty() {
+            return String::new();
+        }
+        let widths = self.natural_widths();
+        // Each line is drawn into the same one-row buffer, so there is no limit on the
+        // number of rows.
+        let mut buffer = ScreenBuffer::new(self.grid_width(&widths), 1);
+        let mut lines = Vec::with_capacity(self.len() + 4);
+        let mut render = |draw: &dyn Fn(&mut ScreenBuffer)| {
+            buffer.fill(buffer.area(), ' ', Style::default());
+            draw(&mut buffer);
+            let plain = (1..=buffer.width()).all(|x| buffer.get(x, 1).is_some_and(|cell| cell.style.is_plain()));
+            lines.push(if plain { buffer.row_text(1) } else { buffer.row_ansi(1) });
+        };
+        render(&|buffer| self.draw_separator(buffer, 1, 1, &widths, Junction::Top));
+        if self.show_header {
+            render(&|buffer| self.draw_header(buffer, 1, 1, &widths));
+            render(&|buffer| self.draw_separator(buffer, 1, 1, &widths, Junction::Middle));
+        }
+        for position in 0..self.len() {
+            render(&|buffer| self.draw_row(buffer, 1, 1, &widths, position, None));
+        }
+        render(&|buffer| self.draw_separator(buffer, 1, 1, &widths, Junction::Bottom));
+        lines.join("\n")
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let last = self.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + delta).clamp(0, last.max(0)) as usize;
+    }
+
+    /// Reacts to a key. Returns false if the key was not used.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let page = self.visible_rows().max(1) as isize;
+        match key.code {
+            KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Down => self.move_cursor(1),
+            KeyCode::PageUp => self.move_cursor(-page),
+            KeyCode::PageDown => self.move_cursor(page),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len().saturating_sub(1),
+            KeyCode::Char('0') if !key.ctrl && !key.alt => self.clear_sort(),
+            KeyCode::Char(ch @ '1'..='9') if !key.ctrl && !key.alt => {
+                let column = ch as usize - '1' as usize;
+                if column >= self.columns.len() {
+                    return false;
+                }
+                self.toggle_sort(column);
+            }
+            KeyCode::Enter => self.submitted = true,
+            KeyCode::Esc => self.cancelled = true,
+            _ if key.is_ctrl('c') => self.cancelled = true,
+            _ => return false,
+        }
+        true
+    }
+
+    // Returns the column whose cell spans `x`, if any.
+    fn column_at(&self, x: u16) -> Option<usize> {
+        let area = self.area.get();
+        let mut left = area.x + 1;
+        for (i, width) in self.column_widths(area.width).iter().enumerate() {
+            let right = left + width + 2 * self.padding;
+            if (left..right).contains(&x) {
+                return Some(i);
+            }
+            left = right + 1;
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, kind: MouseKind, x: u16, y: u16) -> bool {
+        let area = self.area.get();
+        if !area.contains(Point::new(x, y)) {
+            return false;
+        }
+        let rows = self.visible_rows();
+        let body = area.y + 1 + self.header_rows();
+        match kind {
+            MouseKind::ScrollUp => {
+                self.offset.set(self.offset.get().saturating_sub(3));
+            }
+            MouseKind::ScrollDown => {
+                let max_offset = self.len().saturating_sub(rows);
+                self.offset.set((self.offset.get() + 3).min(max_offset));
+            }
+            MouseKind::Press(MouseButton::Left) if self.show_header && y == area.y + 1 => match self.column_at(x) {
+                Some(column) => self.toggle_sort(column),
+                None => return false,
+            },
+            MouseKind::Press(MouseButton::Left) if y >= body => {
+                let position = self.offset.get() + (y - body) as usize;
+                if position >= self.len().min(self.offset.get() + rows) {
+                    return false;
+                }
+                self.cursor = position;
+                return true;
+            }
+            _ => return false,
+        }
+        // Keep the cursor inside the scrolled view.
+        let offset = self.offset.get();
+        self.cursor = self.cursor.clamp(offset, (offset + rows).saturating_sub(1).max(offset));
+        true
+    }
+
+    /// Shows the table in `area` until Enter or Esc is pressed.
+    ///
+    /// Returns the index, in the order rows were added, of the row chosen with Enter, or
+    /// `None` if the table was cancelled or has no rows. If standard input is not a
+    /// terminal, prints the table and reads a row number from a line instead.
+    pub fn run(&mut self, area: Rect) -> Option<usize> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            return self.run_lines();
+        }
+
+        self.reset_state();
+        hide_cursor();
+        let mut previous: Option<ScreenBuffer> = None;
+        let result = loop {
+            let mut buffer = ScreenBuffer::new(area.width, area.height);
+            self.draw(buffer.area(), &mut buffer, true);
+            match &previous {
+                Some(previous) => buffer.render_diff_at(previous, area.x, area.y),
+                None => buffer.render_at(area.x, area.y),
+            }
+            previous = Some(buffer);
+
+            let Some(key) = read_key_event() else {
+                break None;
+            };
+            self.handle_key(&key);
+            if self.submitted {
+                break self.selected_row();
+            }
+            if self.cancelled {
+                break None;
+            }
+        };
+        show_cursor();
+
+        if !was_raw {
+            disable_raw_mode();
+        }
+        result
+    }
+
+    // Prints the table and reads the number of a row, counted from 1 in the order shown,
+    // for when input is not a terminal.
+    fn run_lines(&mut self) -> Option<usize> {
+        println(&self.render_to_string());
+        let mut line = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(e) => {
+                handle_io_error(e);
+                return None;
+            }
+        }
+        let number = line.trim().parse::<usize>().ok().filter(|number| (1..=self.len()).contains(number))?;
+        self.cursor = number - 1;
+        self.selected_row()
+    }
+}
+
+// The separator rows of the grid.
+#[derive(Clone, Copy)]
+enum Junction {
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl Widget for Table {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        self.draw(area, buffer, self.focused);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let width = self.grid_width(&self.natural_widths());
+        let height = (self.len() + 2 + self.header_rows() as usize).min(u16::MAX as usize) as u16;
+        Size::new(width.min(available.width), height.min(available.height))
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            // Leave Tab for moving focus, and Esc and Ctrl-C for the application.
+            Event::Key(key) if matches!(key.code, KeyCode::Tab | KeyCode::BackTab | KeyCode::Esc) => {
+                EventResult::Ignored
+            }
+            Event::Key(key) if key.is_ctrl('c') => EventResult::Ignored,
+            Event::Key(key) if self.handle_key(key) => EventResult::Consumed,
+            Event::Mouse(mouse) if self.handle_mouse(mouse.kind, mouse.x, mouse.y) => EventResult::Consumed,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
+
+// Returns a cell of a row, or "" if the row is too short.
+fn cell(rows: &[Vec<String>], row: usize, column: usize) -> &str {
+    rows[row].get(column).map_or("", String::as_str)
+}
+
+// Returns the value of a cell that holds a finite number. "NaN", "inf" and the like are
+// treated as text.
+pub(crate) fn parse_number(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+// Orders numbers before text, numbers by value and text without regard to case.
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (parse_number(a), parse_number(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_more_rows_than_fit_in_a_screen_buffer() {
+        let mut table = Table::with_headers(&["n"]);
+        table.border_style = Style::default();
+        for n in 0..70_000 {
+            table.add_row(vec![n.to_string()]);
+        }
+        let text = table.render_to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 70_000 + 4);
+        assert_eq!(lines[lines.len() - 2], "│ 69999 │");
+    }
+}
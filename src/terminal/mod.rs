@@ -0,0 +1,150 @@
+//! This module switches the terminal between its normal line-by-line input mode and a raw
+//! mode in which every key press is delivered immediately and nothing is echoed.
+//!
+//! The crate has no external dependencies, so the terminal settings are changed by running
+//! the standard `stty` utility on the terminal attached to standard input.
+
+use std::io::{self, IsTerminal, Read};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::handle_io_error;
+use crate::{get_viewport, set_viewport};
+
+// Holds the `stty -g` state from before raw mode was enabled.
+static SAVED_MODE: Mutex<Option<String>> = Mutex::new(None);
+
+// The read timeout currently set on the terminal in tenths of a second, with 0 meaning
+// reads block until a byte arrives. Cached so polling loops only run `stty` when it changes.
+static READ_TIMEOUT: AtomicU8 = AtomicU8::new(0);
+
+// Switches raw-mode reads between blocking and timing out, skipping `stty` when the
+// terminal is already set that way.
+fn set_read_timeout(tenths: u8) {
+    if READ_TIMEOUT.load(Ordering::Relaxed) == tenths {
+        return;
+    }
+    let tenths_arg = tenths.to_string();
+    let min = if tenths == 0 { "1" } else { "0" };
+    if stty(&["min", min, "time", &tenths_arg]).is_some() {
+        READ_TIMEOUT.store(tenths, Ordering::Relaxed);
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
+
+/// Returns true if standard input is connected to a terminal.
+pub fn is_tty() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Switches the terminal to raw mode.
+///
+/// Returns false, leaving the terminal untouched, if standard input is not a terminal or
+/// its settings cannot be changed.
+pub fn enable_raw_mode() -> bool {
+    let mut saved = SAVED_MODE.lock().unwrap_or_else(|e| e.into_inner());
+    if saved.is_some() {
+        return true;
+    }
+    if !is_tty() {
+        return false;
+    }
+    let Some(state) = stty(&["-g"]) else {
+        return false;
+    };
+    if stty(&["-icanon", "-echo", "-isig", "-ixon", "min", "1", "time", "0"]).is_none() {
+        return false;
+    }
+    *saved = Some(state);
+    READ_TIMEOUT.store(0, Ordering::Relaxed);
+    true
+}
+
+/// Restores the terminal settings saved by `enable_raw_mode`.
+pub fn disable_raw_mode() {
+    let mut saved = SAVED_MODE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(state) = saved.take() {
+        stty(&[&state]);
+    }
+    READ_TIMEOUT.store(0, Ordering::Relaxed);
+}
+
+fn read_chunk() -> Vec<u8> {
+    let mut buffer = [0u8; 64];
+    match io::stdin().read(&mut buffer) {
+        Ok(count) => buffer[..count].to_vec(),
+        Err(e) => {
+            handle_io_error(e);
+            Vec::new()
+        }
+    }
+}
+
+/// Returns true while raw mode is enabled.
+pub fn is_raw_mode() -> bool {
+    SAVED_MODE.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// Reads whatever input is available, waiting until at least one byte arrives.
+///
+/// In raw mode a single key press, including a multi-byte escape sequence, usually comes
+/// back as one chunk. Returns an empty vector at end of input.
+pub fn read_input() -> Vec<u8> {
+    if is_raw_mode() {
+        set_read_timeout(0);
+    }
+    read_chunk()
+}
+
+/// Like `read_input`, but gives up and returns an empty vector after `timeout`.
+///
+/// Only has an effect in raw mode. The timeout is rounded up to a tenth of a second, the
+/// granularity the terminal supports.
+pub fn read_input_timeout(timeout: Duration) -> Vec<u8> {
+    if !is_raw_mode() {
+        return Vec::new();
+    }
+    let tenths = timeout.as_millis().div_ceil(100).clamp(1, 255) as u8;
+    set_read_timeout(tenths);
+    read_chunk()
+}
+
+/// Returns the size of the terminal as `(columns, rows)`, or `None` if standard input is
+/// not a terminal.
+pub fn terminal_size() -> Option<(u16, u16)> {
+    if !is_tty() {
+        return None;
+    }
+    let size = stty(&["size"])?;
+    let mut parts = size.split_whitespace().map(|part| part.parse::<u16>().ok());
+    let rows = parts.next()??;
+    let columns = parts.next()??;
+    Some((columns, rows))
+}
+
+/// Sets the viewport to the current terminal size. Returns true if the size changed, so
+/// a program polling this in its main loop knows to redraw its layout.
+pub fn sync_viewport() -> bool {
+    match terminal_size() {
+        Some(size) if size != get_viewport() => {
+            set_viewport(size.0, size.1);
+            true
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,287 @@
+//! This module lays out text inside a rectangle: it wraps words to the rectangle's width,
+//! breaks words that are too long, aligns each line horizontally and the block of lines
+//! vertically, and reports what happens when the text does not fit.
+//!
+//! The rectangle is given as `x`, `y`, `width` and `height`, the same way as for
+//! `rbox::draw_box`. To put text inside a box, pass the box's interior, e.g.
+//! `(x + 1, y + 1, width - 2, height - 2)`.
+
+use crate::grapheme::{grapheme_width, graphemes, truncate_to_width};
+use crate::screen::ScreenBuffer;
+use crate::style::{set_style, Style};
+use crate::width::str_width;
+use crate::*;
+
+/// How lines are aligned between the left and right edges of the rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HorizontalAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Spreads words so lines reach both edges. The last line of a paragraph stays left
+    /// aligned.
+    Justify,
+}
+
+/// How the block of lines is placed between the top and bottom of the rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// What to do with lines that do not fit in the rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Show the first lines and drop the rest.
+    #[default]
+    Clip,
+    /// Show the first lines and end the last visible line with `…`.
+    Ellipsis,
+    /// Show the lines starting at the given line, for scrolling through long text.
+    Scroll(usize),
+}
+
+/// Options for laying out text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextLayout {
+    pub align: HorizontalAlign,
+    pub vertical_align: VerticalAlign,
+    pub overflow: Overflow,
+    /// Break words that are wider than the rectangle with a hyphen instead of splitting
+    /// them without one.
+    pub hyphenate: bool,
+    /// The style the text is drawn with.
+    pub style: Style,
+}
+
+/// A line of laid-out text and the position it should be drawn at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedLine {
+    pub x: u16,
+    pub y: u16,
+    pub text: String,
+}
+
+/// The result of laying out text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaidOutText {
+    /// The visible lines, positioned inside the rectangle.
+    pub lines: Vec<PlacedLine>,
+    /// The number of lines the wrapped text has in total.
+    pub total_lines: usize,
+    /// True if some lines are not visible.
+    pub overflowed: bool,
+    /// The largest useful value for `Overflow::Scroll`.
+    pub max_scroll: usize,
+}
+
+// Splits a word that is wider than `width` into pieces that fit.
+fn break_word(word: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = word;
+    while str_width(rest) > width {
+        // Leave a column for the hyphen, and go without one when nothing fits beside it.
+        let hyphenated = if hyphenate && width > 1 { truncate_to_width(rest, width - 1) } else { "" };
+        let piece = if hyphenated.is_empty() {
+            let mut piece = truncate_to_width(rest, width.max(1));
+            if piece.is_empty() {
+                // A single cluster wider than the rectangle; take it anyway.
+                piece = graphemes(rest).next().unwrap();
+            }
+            pieces.push(piece.to_string());
+            piece
+        } else {
+            pieces.push(format!("{}-", hyphenated));
+            hyphenated
+        };
+        rest = &rest[piece.len()..];
+    }
+    if !rest.is_empty() {
+        pieces.push(rest.to_string());
+    }
+    pieces
+}
+
+/// Wraps text into lines no wider than `width` columns.
+///
+/// Lines break at spaces; existing line breaks start a new paragraph. Each returned line
+/// is paired with true if it is the last line of its paragraph.
+pub fn wrap_text(text: &str, width: usize, hyphenate: bool) -> Vec<(String, bool)> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut current = String::new();
+        let mut current_width = 0;
+        for word in paragraph.split_whitespace() {
+            for piece in break_word(word, width, hyphenate) {
+                let piece_width = str_width(&piece);
+                if current.is_empty() {
+                    current = piece;
+                    current_width = piece_width;
+                } else if current_width + 1 + piece_width <= width {
+                    current.push(' ');
+                    current.push_str(&piece);
+                    current_width += 1 + piece_width;
+                } else {
+                    lines.push((std::mem::take(&mut current), false));
+                    current = piece;
+                    current_width = piece_width;
+                }
+            }
+        }
+        lines.push((current, true));
+    }
+    lines
+}
+
+fn justify(line: &str, width: usize) -> String {
+    let words: Vec<&str> = line.split(' ').collect();
+    if words.len() < 2 {
+        return line.to_string();
+    }
+    let gaps = words.len() - 1;
+    let spaces = width.saturating_sub(words.iter().map(|w| str_width(w)).sum::<usize>());
+    let mut output = String::from(words[0]);
+    for (i, word) in words[1..].iter().enumerate() {
+        let count = spaces / gaps + usize::from(i < spaces % gaps);
+        output.push_str(&" ".repeat(count));
+        output.push_str(word);
+    }
+    output
+}
+
+/// Lays out text inside a rectangle without drawing it.
+///
+/// # Arguments
+///
+/// * `text` - The text to lay out
+/// * `x` - The x-coordinate of the top-left corner
+/// * `y` - The y-coordinate of the top-left corner
+/// * `width` - The width of the rectangle
+/// * `height` - The height of the rectangle
+/// * `options` - Alignment, overflow and hyphenation options
+pub fn layout_text(text: &str, x: u16, y: u16, width: u16, height: u16, options: &TextLayout) -> LaidOutText {
+    let width_columns = width as usize;
+    let wrapped = wrap_text(text, width_columns, options.hyphenate);
+    let total_lines = wrapped.len();
+    let visible = height as usize;
+    let max_scroll = total_lines.saturating_sub(visible);
+    let overflowed = total_lines > visible;
+
+    let start = match options.overflow {
+        Overflow::Scroll(offset) => offset.min(max_scroll),
+        _ => 0,
+    };
+    let mut shown: Vec<(String, bool)> = wrapped.into_iter().skip(start).take(visible).collect();
+
+    if options.overflow == Overflow::Ellipsis && overflowed {
+        if let Some((last, end)) = shown.last_mut() {
+            let kept = truncate_to_width(last, width_columns.saturating_sub(1)).trim_end();
+            *last = format!("{}…", kept);
+            *end = true;
+        }
+    }
+
+    let top = match options.vertical_align {
+        VerticalAlign::Top => 0,
+        VerticalAlign::Middle => (visible - shown.len()) / 2,
+        VerticalAlign::Bottom => visible - shown.len(),
+    };
+
+    let lines = shown
+        .into_iter()
+        .enumerate()
+        .map(|(i, (line, paragraph_end))| {
+            let line = if options.align == HorizontalAlign::Justify && !paragraph_end {
+                justify(&line, width_columns)
+            } else {
+                line
+            };
+            let spare = width_columns.saturating_sub(str_width(&line)) as u16;
+            let offset = match options.align {
+                HorizontalAlign::Left | HorizontalAlign::Justify => 0,
+                HorizontalAlign::Center => spare / 2,
+                HorizontalAlign::Right => spare,
+            };
+            PlacedLine { x: x + offset, y: y + (top + i) as u16, text: line }
+        })
+        .collect();
+
+    LaidOutText { lines, total_lines, overflowed, max_scroll }
+}
+
+/// Lays out text inside a rectangle and draws it at the cursor positions it works out.
+///
+/// Takes the same arguments as `layout_text` and returns its result.
+pub fn draw_text(text: &str, x: u16, y: u16, width: u16, height: u16, options: &TextLayout) -> LaidOutText {
+    let layout = layout_text(text, x, y, width, height, options);
+    if !options.style.is_plain() {
+        set_style(options.style);
+    }
+    for line in &layout.lines {
+        move_cursor_to(line.x, line.y);
+        // A cluster wider than the rectangle is kept whole by wrapping, so clip it here.
+        print(truncate_to_width(&line.text, x.saturating_add(width).saturating_sub(line.x) as usize));
+    }
+    if !options.style.is_plain() {
+        reset_color();
+    }
+    layout
+}
+
+/// Lays out and draws text inside a `Rect`, such as one produced by `layout::Layout`.
+pub fn draw_text_rect(text: &str, area: Rect, options: &TextLayout) -> LaidOutText {
+    draw_text(text, area.x, area.y, area.width, area.height, options)
+}
+
+impl ScreenBuffer {
+    /// Lays out text inside a rectangle of the buffer and draws it.
+    ///
+    /// Takes the same arguments as `text::layout_text` and returns its result.
+    pub fn draw_text(
+        &mut self,
+        text: &str,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        options: &TextLayout,
+    ) -> LaidOutText {
+        let layout = layout_text(text, x, y, width, height, options);
+        for line in &layout.lines {
+            let mut column = line.x;
+            for cluster in graphemes(&line.text) {
+                if column + grapheme_width(cluster) as u16 > x + width {
+                    break;
+                }
+                self.set_grapheme(column, line.y, cluster, options.style);
+                column += grapheme_width(cluster) as u16;
+            }
+        }
+        layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrapped(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+        wrap_text(text, width, hyphenate).into_iter().map(|(line, _)| line).collect()
+    }
+
+    #[test]
+    fn hyphenated_pieces_fit_the_width() {
+        assert_eq!(wrapped("abcdefgh", 4, true), ["abc-", "def-", "gh"]);
+        assert_eq!(wrapped("abcdefgh", 3, false), ["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn wide_clusters_are_not_hyphenated_when_the_hyphen_would_not_fit() {
+        assert_eq!(wrapped("漢字漢字", 2, true), ["漢", "字", "漢", "字"]);
+        assert_eq!(wrapped("a漢字", 3, true), ["a-", "漢-", "字"]);
+    }
+}
@@ -0,0 +1,244 @@
+//! This module provides themes: a mapping from semantic roles (primary, accent, border, ...)
+//! to styles. Code that draws with roles instead of fixed colors can be re-skinned by
+//! switching the current theme with `set_theme`.
+//!
+//! Themes can be loaded from a simple text file with one `key = value` pair per line.
+//! The `name` key names the theme; every other key is a role and its value uses the same
+//! words as a markup tag. Blank lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # ocean.theme
+//! name = ocean
+//! primary = bold cyan
+//! border = blue
+//! error = bold white on red
+//! ```
+//!
+//! Roles missing from the file keep their style from the dark theme.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::RwLock;
+
+use crate::line::Line;
+use crate::markup::parse_tag;
+use crate::rbox::{draw_box, BoxStyle};
+use crate::style::{set_style, Style};
+use crate::*;
+
+/// Semantic roles that a theme assigns styles to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Primary,
+    Accent,
+    Border,
+    Error,
+    Warning,
+    Muted,
+    Selection,
+}
+
+impl Role {
+    /// Every role, in declaration order.
+    pub const ALL: [Role; 7] = [
+        Role::Primary,
+        Role::Accent,
+        Role::Border,
+        Role::Error,
+        Role::Warning,
+        Role::Muted,
+        Role::Selection,
+    ];
+
+    /// Returns the lowercase name used for the role in theme files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Primary => "primary",
+            Role::Accent => "accent",
+            Role::Border => "border",
+            Role::Error => "error",
+            Role::Warning => "warning",
+            Role::Muted => "muted",
+            Role::Selection => "selection",
+        }
+    }
+
+    /// Converts a role name such as `"error"` to the corresponding `Role`.
+    pub fn from_name(name: &str) -> Option<Role> {
+        Role::ALL.iter().copied().find(|role| role.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A named set of styles, one for each role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    styles: [Style; 7],
+}
+
+impl Theme {
+    /// The built-in theme for terminals with a dark background.
+    pub fn dark() -> Self {
+        Theme {
+            name: String::from("dark"),
+            styles: [
+                Style::new().fg(Color::White).attribute(Attribute::Bright),
+                Style::new().fg(Color::Cyan),
+                Style::new().fg(Color::Blue),
+                Style::new().fg(Color::Red).attribute(Attribute::Bright),
+                Style::new().fg(Color::Yellow),
+                Style::new().fg(Color::White).attribute(Attribute::Dim),
+                Style::new().fg(Color::Black).bg(Color::Cyan),
+            ],
+        }
+    }
+
+    /// The built-in theme for terminals with a light background.
+    pub fn light() -> Self {
+        Theme {
+            name: String::from("light"),
+            styles: [
+                Style::new().fg(Color::Black).attribute(Attribute::Bright),
+                Style::new().fg(Color::Blue),
+                Style::new().fg(Color::Black),
+                Style::new().fg(Color::Red),
+                Style::new().fg(Color::Magenta),
+                Style::new().fg(Color::Black).attribute(Attribute::Dim),
+                Style::new().fg(Color::White).bg(Color::Blue),
+            ],
+        }
+    }
+
+    /// Returns the style for a role.
+    pub fn style(&self, role: Role) -> Style {
+        self.styles[role as usize]
+    }
+
+    /// Replaces the style for a role.
+    pub fn set(&mut self, role: Role, style: Style) {
+        self.styles[role as usize] = style;
+    }
+
+    /// Returns a copy of the theme with the style for a role replaced.
+    pub fn with(mut self, role: Role, style: Style) -> Self {
+        self.set(role, style);
+        self
+    }
+
+    /// Parses a theme from the text of a theme file.
+    pub fn parse(text: &str) -> Result<Theme, ThemeError> {
+        let mut theme = Theme::dark();
+        theme.name = String::from("custom");
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| ThemeError::Parse { line: line_number, message };
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(String::from("expected 'key = value'")))?;
+            let (key, value) = (key.trim(), value.trim());
+            if key.eq_ignore_ascii_case("name") {
+                theme.name = value.to_string();
+                continue;
+            }
+            let role = Role::from_name(key).ok_or_else(|| error(format!("unknown role '{}'", key)))?;
+            let style = parse_tag(value).map_err(|e| error(e.to_string()))?;
+            theme.set(role, style);
+        }
+        Ok(theme)
+    }
+
+    /// Loads a theme from a theme file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Theme, ThemeError> {
+        let text = fs::read_to_string(path).map_err(ThemeError::Io)?;
+        Theme::parse(&text)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+/// Errors that can occur while loading a theme.
+#[derive(Debug)]
+pub enum ThemeError {
+    /// The theme file could not be read.
+    Io(io::Error),
+    /// A line of the theme file could not be understood.
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "could not read theme: {}", e),
+            ThemeError::Parse { line, message } => write!(f, "theme line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+static CURRENT_THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// Makes the given theme the current one. Takes effect for everything drawn afterwards.
+pub fn set_theme(theme: Theme) {
+    let mut current = CURRENT_THEME.write().unwrap_or_else(|e| e.into_inner());
+    *current = Some(theme);
+}
+
+/// Returns a copy of the current theme. The dark theme is current until another is set.
+pub fn current_theme() -> Theme {
+    let current = CURRENT_THEME.read().unwrap_or_else(|e| e.into_inner());
+    current.clone().unwrap_or_default()
+}
+
+/// Returns the current theme's style for a role.
+pub fn role_style(role: Role) -> Style {
+    let current = CURRENT_THEME.read().unwrap_or_else(|e| e.into_inner());
+    match current.as_ref() {
+        Some(theme) => theme.style(role),
+        None => Theme::dark().style(role),
+    }
+}
+
+/// Applies the current theme's style for a role to subsequent text output.
+pub fn set_role(role: Role) {
+    set_style(role_style(role));
+}
+
+/// Prints text in the current theme's style for a role, then resets the style.
+pub fn print_with_role(text: &str, role: Role) {
+    set_role(role);
+    print(text);
+    reset_color();
+}
+
+/// Draws a box in the current theme's border style.
+///
+/// # Arguments
+///
+/// * `x` - The x-coordinate of the top-left corner
+/// * `y` - The y-coordinate of the top-left corner
+/// * `width` - The width of the box
+/// * `height` - The height of the box
+/// * `style` - The BoxStyle to use
+pub fn draw_themed_box(x: u16, y: u16, width: u16, height: u16, style: BoxStyle) {
+    set_role(Role::Border);
+    draw_box(x, y, width, height, style);
+    reset_color();
+}
+
+/// Draws a line in the current theme's style for a role.
+pub fn draw_themed_line(line: &Line, role: Role) {
+    set_role(role);
+    line.draw(true);
+    reset_color();
+}
@@ -0,0 +1,647 @@
+//! This module draws hierarchical data as a tree, either printed once or as an
+//! interactive view that expands and collapses.
+//!
+//! Any type can be shown by implementing `TreeItem`, which gives an item's label and its
+//! children. `TreeNode` is a ready-made implementation for building trees by hand. The
+//! guides between items come from the light, heavy, double or rounded box-drawing sets,
+//! chosen with `TreeStyle`:
+//!
+//! ```text
+//! src
+//! ├── lib.rs
+//! └── tree
+//!     └── mod.rs
+//! ```
+//!
+//! `TreeView` only asks an item for its children when it is first expanded, so it can
+//! browse large or slow data such as a file system. Up, Down, PageUp, PageDown, Home and
+//! End move the cursor; Right expands an item or moves to its first child, Left collapses
+//! it or moves to its parent, Space toggles it and Enter chooses it. Clicking an item moves
+//! the cursor to it, clicking its triangle toggles it and the mouse wheel scrolls.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::tree::{print_tree, TreeNode, TreeStyle};
+//!
+//! let tree = TreeNode::new("src")
+//!     .child(TreeNode::new("lib.rs"))
+//!     .child(TreeNode::new("tree").child(TreeNode::new("mod.rs")));
+//! print_tree(&tree, TreeStyle::Rounded);
+//! ```
+
+use std::cell::Cell;
+
+use crate::ansi::truncate;
+use crate::event::{read_key_event, Event, KeyCode, KeyEvent, MouseButton, MouseKind};
+use crate::geometry::{Rect, Size};
+use crate::rbox::{
+    double_box_to_char, heavy_box_to_char, single_box_to_char, single_rounded_box_to_char, DoubleBox, HeavyBox,
+    SingleBox, SingleRoundedBox,
+};
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode};
+use crate::theme::{role_style, Role};
+use crate::triangle::{triangle_symbol_to_char, TriangleSymbol};
+use crate::widget::{EventResult, Widget};
+use crate::width::str_width;
+use crate::*;
+
+// The most rows the non-terminal fallback of `TreeView::run` lists.
+const FALLBACK_ROWS: usize = 100;
+
+/// Hierarchical data that can be drawn as a tree.
+pub trait TreeItem: Sized {
+    /// Returns the text shown for the item.
+    fn label(&self) -> String;
+
+    /// Returns the children of the item. `TreeView` calls this only when the item is first
+    /// expanded.
+    fn children(&self) -> Vec<Self>;
+
+    /// Returns true if the item has, or may have, children. Override this when finding the
+    /// children is slow, so a `TreeView` can show an item as expandable without loading them.
+    fn has_children(&self) -> bool {
+        !self.children().is_empty()
+    }
+}
+
+/// A labelled node with child nodes, for building a tree by hand.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn new(label: &str) -> Self {
+        TreeNode { label: label.to_string(), children: Vec::new() }
+    }
+
+    /// Adds a child node.
+    pub fn child(mut self, child: TreeNode) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+impl TreeItem for TreeNode {
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    fn children(&self) -> Vec<TreeNode> {
+        self.children.clone()
+    }
+
+    fn has_children(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// Lets a `TreeView` borrow a tree of `TreeNode`s instead of copying it.
+impl<'a> TreeItem for &'a TreeNode {
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    fn children(&self) -> Vec<&'a TreeNode> {
+        self.children.iter().collect()
+    }
+
+    fn has_children(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// The box-drawing set the guides of a tree are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreeStyle {
+    /// `├──`, `└──` and `│`.
+    #[default]
+    Light,
+    /// `┣━━`, `┗━━` and `┃`.
+    Heavy,
+    /// `╠══`, `╚══` and `║`.
+    Double,
+    /// `├──`, `╰──` and `│`.
+    Rounded,
+}
+
+impl TreeStyle {
+    // Returns the guides for an item with later siblings, for the last item, and for the
+    // levels below an item with later siblings. Each is four columns wide.
+    fn guides(&self) -> [String; 3] {
+        let [branch, last, horizontal, vertical] = match self {
+            TreeStyle::Light => {
+                [SingleBox::VerticalLeft, SingleBox::BottomLeft, SingleBox::Horizontal, SingleBox::Vertical]
+                    .map(single_box_to_char)
+            }
+            TreeStyle::Heavy => {
+                [HeavyBox::VerticalLeft, HeavyBox::BottomLeft, HeavyBox::Horizontal, HeavyBox::Vertical]
+                    .map(heavy_box_to_char)
+            }
+            TreeStyle::Double => {
+                [DoubleBox::VerticalLeft, DoubleBox::BottomLeft, DoubleBox::Horizontal, DoubleBox::Vertical]
+                    .map(double_box_to_char)
+            }
+            TreeStyle::Rounded => [
+                SingleRoundedBox::VerticalLeft,
+                SingleRoundedBox::BottomLeft,
+                SingleRoundedBox::Horizontal,
+                SingleRoundedBox::Vertical,
+            ]
+            .map(single_rounded_box_to_char),
+        };
+        [
+            format!("{}{}{} ", branch, horizontal, horizontal),
+            format!("{}{}{} ", last, horizontal, horizontal),
+            format!("{}   ", vertical),
+        ]
+    }
+}
+
+// Returns the guides before an item. `last` tells, for each level from the root down to
+// the item, whether the item or its ancestor at that level is the last of its siblings.
+// Roots have no guides.
+fn prefix(last: &[bool], guides: &[String; 3]) -> String {
+    let mut prefix = String::new();
+    let levels = last.len();
+    for (level, &is_last) in last.iter().enumerate().skip(1) {
+        let guide = match (level + 1 == levels, is_last) {
+            (true, false) => &guides[0],
+            (true, true) => &guides[1],
+            (false, false) => &guides[2],
+            (false, true) => "    ",
+        };
+        prefix.push_str(guide);
+    }
+    prefix
+}
+
+fn push_lines<T: TreeItem>(item: &T, last: &mut Vec<bool>, guides: &[String; 3], lines: &mut Vec<String>) {
+    lines.push(format!("{}{}", prefix(last, guides), item.label()));
+    let children = item.children();
+    let count = children.len();
+    for (i, child) in children.iter().enumerate() {
+        last.push(i + 1 == count);
+        push_lines(child, last, guides, lines);
+        last.pop();
+    }
+}
+
+/// Returns the lines of a whole tree, with `root` on the first line.
+pub fn tree_lines<T: TreeItem>(root: &T, style: TreeStyle) -> Vec<String> {
+    let mut lines = Vec::new();
+    push_lines(root, &mut vec![true], &style.guides(), &mut lines);
+    lines
+}
+
+/// Returns a whole tree as text, with lines joined by newlines.
+pub fn render_tree<T: TreeItem>(root: &T, style: TreeStyle) -> String {
+    tree_lines(root, style).join("\n")
+}
+
+/// Prints a whole tree at the cursor position.
+pub fn print_tree<T: TreeItem>(root: &T, style: TreeStyle) {
+    for line in tree_lines(root, style) {
+        println(&line);
+    }
+}
+
+// An item of a `TreeView` and its children, once they are loaded.
+struct Node<T> {
+    item: T,
+    expanded: bool,
+    children: Option<Vec<Node<T>>>,
+}
+
+impl<T: TreeItem> Node<T> {
+    fn new(item: T) -> Self {
+        Node { item, expanded: false, children: None }
+    }
+
+    fn has_children(&self) -> bool {
+        match &self.children {
+            Some(children) => !children.is_empty(),
+            None => self.item.has_children(),
+        }
+    }
+
+    fn load(&mut self) {
+        if self.children.is_none() {
+            self.children = Some(self.item.children().into_iter().map(Node::new).collect());
+        }
+    }
+}
+
+// A visible row of a `TreeView`: the indices leading to its node from the roots, and for
+// each level whether the node there is the last of its siblings.
+struct Row {
+    path: Vec<usize>,
+    last: Vec<bool>,
+}
+
+/// An interactive tree that loads children as items are expanded.
+pub struct TreeView<T: TreeItem> {
+    roots: Vec<Node<T>>,
+    pub style: TreeStyle,
+    /// Drawn before collapsed items that have children.
+    pub collapsed_symbol: TriangleSymbol,
+    /// Drawn before expanded items.
+    pub expanded_symbol: TriangleSymbol,
+    /// The style of the item under the cursor while the view has focus.
+    pub highlight: Style,
+    // The path to the item under the cursor.
+    cursor: Vec<usize>,
+    focused: bool,
+    submitted: bool,
+    cancelled: bool,
+    // The first visible row and the area of the last render.
+    offset: Cell<usize>,
+    area: Cell<Rect>,
+}
+
+impl<T: TreeItem> TreeView<T> {
+    /// Creates a view with the given top-level items, all collapsed.
+    pub fn new(roots: Vec<T>) -> Self {
+        let roots: Vec<Node<T>> = roots.into_iter().map(Node::new).collect();
+        let cursor = if roots.is_empty() { Vec::new() } else { vec![0] };
+        TreeView {
+            roots,
+            style: TreeStyle::Light,
+            collapsed_symbol: TriangleSymbol::SmallBlackRightPointing,
+            expanded_symbol: TriangleSymbol::SmallBlackDownPointing,
+            highlight: role_style(Role::Selection),
+            cursor,
+            focused: false,
+            submitted: false,
+            cancelled: false,
+            offset: Cell::new(0),
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    pub fn style(mut self, style: TreeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn collapsed_symbol(mut self, collapsed_symbol: TriangleSymbol) -> Self {
+        self.collapsed_symbol = collapsed_symbol;
+        self
+    }
+
+    pub fn expanded_symbol(mut self, expanded_symbol: TriangleSymbol) -> Self {
+        self.expanded_symbol = expanded_symbol;
+        self
+    }
+
+    pub fn highlight(mut self, highlight: Style) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    fn node(&self, path: &[usize]) -> Option<&Node<T>> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get(*first)?;
+        for &index in rest {
+            node = node.children.as_ref()?.get(index)?;
+        }
+        Some(node)
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut Node<T>> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get_mut(*first)?;
+        for &index in rest {
+            node = node.children.as_mut()?.get_mut(index)?;
+        }
+        Some(node)
+    }
+
+    /// Returns the item at a path of child indices, starting with the index of a top-level
+    /// item, if it has been loaded.
+    pub fn item(&self, path: &[usize]) -> Option<&T> {
+        self.node(path).map(|node| &node.item)
+    }
+
+    /// Returns the item under the cursor.
+    pub fn selected(&self) -> Option<&T> {
+        self.item(&self.cursor)
+    }
+
+    /// Returns the path to the item under the cursor.
+    pub fn selected_path(&self) -> &[usize] {
+        &self.cursor
+    }
+
+    /// Returns true if the item at a path is expanded.
+    pub fn is_expanded(&self, path: &[usize]) -> bool {
+        self.node(path).is_some_and(|node| node.expanded)
+    }
+
+    /// Expands the item at a path, loading its children the first time.
+    pub fn expand(&mut self, path: &[usize]) {
+        if let Some(node) = self.node_mut(path) {
+            node.load();
+            node.expanded = node.has_children();
+        }
+    }
+
+    /// Collapses the item at a path. A cursor inside it moves to the item.
+    pub fn collapse(&mut self, path: &[usize]) {
+        if let Some(node) = self.node_mut(path) {
+            node.expanded = false;
+            if self.cursor.len() > path.len() && self.cursor.starts_with(path) {
+                self.cursor = path.to_vec();
+            }
+        }
+    }
+
+    /// Expands the item at a path if it is collapsed, and collapses it otherwise.
+    pub fn toggle(&mut self, path: &[usize]) {
+        if self.is_expanded(path) {
+            self.collapse(path);
+        } else {
+            self.expand(path);
+        }
+    }
+
+    /// Returns true once Enter has been pressed.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Returns true once Esc or Ctrl-C has been pressed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Clears the submitted and cancelled flags.
+    pub fn reset_state(&mut self) {
+        self.submitted = false;
+        self.cancelled = false;
+    }
+
+    // Lists the rows that are shown, from the top.
+    fn rows(&self) -> Vec<Row> {
+        fn visit<T: TreeItem>(nodes: &[Node<T>], path: &mut Vec<usize>, last: &mut Vec<bool>, rows: &mut Vec<Row>) {
+            for (i, node) in nodes.iter().enumerate() {
+                path.push(i);
+                last.push(i + 1 == nodes.len());
+                rows.push(Row { path: path.clone(), last: last.clone() });
+                if let (true, Some(children)) = (node.expanded, &node.children) {
+                    visit(children, path, last, rows);
+                }
+                path.pop();
+                last.pop();
+            }
+        }
+        let mut rows = Vec::new();
+        visit(&self.roots, &mut Vec::new(), &mut Vec::new(), &mut rows);
+        rows
+    }
+
+    fn cursor_row(&self, rows: &[Row]) -> usize {
+        rows.iter().position(|row| row.path == self.cursor).unwrap_or(0)
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let rows = self.rows();
+        if rows.is_empty() {
+            return;
+        }
+        let row = (self.cursor_row(&rows) as isize + delta).clamp(0, rows.len() as isize - 1) as usize;
+        self.cursor = rows[row].path.clone();
+    }
+
+    // Scrolls so the cursor row is visible in `height` rows.
+    fn scroll_to_cursor(&self, row: usize, count: usize, height: usize) {
+        let mut offset = self.offset.get().min(count.saturating_sub(height));
+        if row < offset {
+            offset = row;
+        } else if height > 0 && row >= offset + height {
+            offset = row + 1 - height;
+        }
+        self.offset.set(offset);
+    }
+
+    /// Reacts to a key. Returns false if the key was not used.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let page = self.area.get().height.max(1) as isize;
+        let cursor = self.cursor.clone();
+        match key.code {
+            KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Down => self.move_cursor(1),
+            KeyCode::PageUp => self.move_cursor(-page),
+            KeyCode::PageDown => self.move_cursor(page),
+            KeyCode::Home => self.move_cursor(-(self.rows().len() as isize)),
+            KeyCode::End => self.move_cursor(self.rows().len() as isize),
+            KeyCode::Right => {
+                if self.is_expanded(&cursor) {
+                    self.move_cursor(1);
+                } else {
+                    self.expand(&cursor);
+                }
+            }
+            KeyCode::Left => {
+                if self.is_expanded(&cursor) {
+                    self.collapse(&cursor);
+                } else if cursor.len() > 1 {
+                    self.cursor.pop();
+                }
+            }
+            KeyCode::Char(' ') if !key.ctrl && !key.alt => self.toggle(&cursor),
+            KeyCode::Enter => self.submitted = !self.cursor.is_empty(),
+            KeyCode::Esc => self.cancelled = true,
+            _ if key.is_ctrl('c') => self.cancelled = true,
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, kind: MouseKind, x: u16, y: u16) -> bool {
+        let area = self.area.get();
+        if !area.contains(Point::new(x, y)) {
+            return false;
+        }
+        let rows = self.rows();
+        let max_offset = rows.len().saturating_sub(area.height as usize);
+        match kind {
+            MouseKind::ScrollUp => self.offset.set(self.offset.get().saturating_sub(3)),
+            MouseKind::ScrollDown => self.offset.set((self.offset.get() + 3).min(max_offset)),
+            MouseKind::Press(MouseButton::Left) => {
+                let Some(row) = rows.get(self.offset.get() + (y - area.y) as usize) else {
+                    return false;
+                };
+                let symbol = area.x + str_width(&prefix(&row.last, &self.style.guides())) as u16;
+                self.cursor = row.path.clone();
+                if x == symbol {
+                    self.toggle(&row.path);
+                }
+                return true;
+            }
+            _ => return false,
+        }
+        // Keep the cursor inside the scrolled view.
+        let offset = self.offset.get();
+        let last = (offset + area.height as usize).min(rows.len()).saturating_sub(1);
+        let row = self.cursor_row(&rows).clamp(offset, last.max(offset));
+        if let Some(row) = rows.get(row) {
+            self.cursor = row.path.clone();
+        }
+        true
+    }
+
+    // Returns the guides, triangle and label of a row.
+    fn row_parts(&self, row: &Row, guides: &[String; 3]) -> (String, char, String) {
+        let node = self.node(&row.path).unwrap();
+        let symbol = if node.expanded {
+            triangle_symbol_to_char(self.expanded_symbol)
+        } else if node.has_children() {
+            triangle_symbol_to_char(self.collapsed_symbol)
+        } else {
+            ' '
+        };
+        (prefix(&row.last, guides), symbol, node.item.label())
+    }
+
+    /// Draws the visible rows into `area` of a buffer. `active` highlights the cursor.
+    pub fn draw(&self, area: Rect, buffer: &mut ScreenBuffer, active: bool) {
+        self.area.set(area);
+        if area.is_empty() {
+            return;
+        }
+        let rows = self.rows();
+        let height = area.height as usize;
+        self.scroll_to_cursor(self.cursor_row(&rows), rows.len(), height);
+        let guides = self.style.guides();
+        let offset = self.offset.get();
+        for (i, row) in rows.iter().skip(offset).take(height).enumerate() {
+            let y = area.y + i as u16;
+            let style = if active && row.path == self.cursor { self.highlight } else { Style::default() };
+            let (prefix, symbol, label) = self.row_parts(row, &guides);
+            let x = buffer.put_str(area.x, y, &truncate(&prefix, area.width as usize), role_style(Role::Border));
+            if x >= area.right() {
+                continue;
+            }
+            buffer.set_char(x, y, symbol, role_style(Role::Accent));
+            let label = truncate(&format!(" {}", label), (area.right() - x - 1) as usize);
+            let end = buffer.put_str(x + 1, y, &label, style);
+            if style != Style::default() && end < area.right() {
+                buffer.put_str(end, y, " ", style);
+            }
+        }
+    }
+
+    /// Shows the view in `area` until Enter or Esc is pressed.
+    ///
+    /// Returns the item chosen with Enter, or `None` if the view was cancelled. If standard
+    /// input is not a terminal, prints the visible rows and reads the number of one instead.
+    pub fn run(&mut self, area: Rect) -> Option<&T> {
+        let was_raw = is_raw_mode();
+        if !was_raw && !enable_raw_mode() {
+            return self.run_lines();
+        }
+
+        self.reset_state();
+        hide_cursor();
+        let mut previous: Option<ScreenBuffer> = None;
+        let chosen = loop {
+            let mut buffer = ScreenBuffer::new(area.width, area.height);
+            self.draw(buffer.area(), &mut buffer, true);
+            match &previous {
+                Some(previous) => buffer.render_diff_at(previous, area.x, area.y),
+                None => buffer.render_at(area.x, area.y),
+            }
+            previous = Some(buffer);
+
+            let Some(key) = read_key_event() else {
+                break false;
+            };
+            self.handle_key(&key);
+            if self.submitted || self.cancelled {
+                break self.submitted;
+            }
+        };
+        show_cursor();
+
+        if !was_raw {
+            disable_raw_mode();
+        }
+        if chosen {
+            self.selected()
+        } else {
+            None
+        }
+    }
+
+    // Lists the visible rows and reads the number of one, for when input is not a terminal.
+    fn run_lines(&mut self) -> Option<&T> {
+        let rows = self.rows();
+        let guides = self.style.guides();
+        let digits = rows.len().to_string().len();
+        for (i, row) in rows.iter().take(FALLBACK_ROWS).enumerate() {
+            let (prefix, symbol, label) = self.row_parts(row, &guides);
+            println(&format!("{:>width$}) {}{} {}", i + 1, prefix, symbol, label, width = digits));
+        }
+        if rows.len() > FALLBACK_ROWS {
+            println(&format!("... and {} more", rows.len() - FALLBACK_ROWS));
+        }
+        let mut line = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(e) => {
+                handle_io_error(e);
+                return None;
+            }
+        }
+        let number = line.trim().parse::<usize>().ok().filter(|number| (1..=rows.len()).contains(number))?;
+        self.cursor = rows[number - 1].path.clone();
+        self.selected()
+    }
+}
+
+impl<T: TreeItem> Widget for TreeView<T> {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        self.draw(area, buffer, self.focused);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let rows = self.rows();
+        let guides = self.style.guides();
+        let widest = rows
+            .iter()
+            .take(available.height as usize)
+            .map(|row| {
+                let (prefix, _, label) = self.row_parts(row, &guides);
+                str_width(&prefix) + 2 + str_width(&label)
+            })
+            .max()
+            .unwrap_or(0);
+        Size::new((widest as u16).min(available.width), (rows.len() as u16).min(available.height))
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            // Leave Tab for moving focus, and Esc and Ctrl-C for the application.
+            Event::Key(key) if matches!(key.code, KeyCode::Tab | KeyCode::BackTab | KeyCode::Esc) => {
+                EventResult::Ignored
+            }
+            Event::Key(key) if key.is_ctrl('c') => EventResult::Ignored,
+            Event::Key(key) if self.handle_key(key) => EventResult::Consumed,
+            Event::Mouse(mouse) if self.handle_mouse(mouse.kind, mouse.x, mouse.y) => EventResult::Consumed,
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
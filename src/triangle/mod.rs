@@ -57,3 +57,10 @@ pub fn triangle_symbol_to_char(symbol: TriangleSymbol) -> char {
         TriangleSymbol::BlackRightPointingDouble => '⏵',
     }
 }
+
+impl TriangleSymbol {
+    /// Returns the number of terminal columns the symbol occupies (1 or 2).
+    pub fn width(&self) -> usize {
+        crate::width::char_width(triangle_symbol_to_char(*self))
+    }
+}
@@ -0,0 +1,522 @@
+//! This module provides widgets: interactive components that draw themselves into a
+//! rectangle of a `ScreenBuffer` and react to key presses.
+//!
+//! Where `line::Shape` draws a line straight to the terminal, a `Widget` only draws into a
+//! buffer. An `App` owns a tree of widgets and runs the pipeline for every frame: it lays
+//! the tree out over the viewport, renders it into a fresh buffer, writes the cells that
+//! changed since the last frame to the terminal and passes input to the focused widget.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::layout::Constraint;
+//! use rpian_terminal::widget::{App, Label, Panel, Stack};
+//!
+//! let root = Stack::vertical()
+//!     .child(Constraint::Length(3), Panel::new("Title", Label::new("Hello")))
+//!     .child(Constraint::Fill(1), Panel::new("Body", Label::new("Press Ctrl-C to quit")));
+//! App::new(root).run();
+//! ```
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use crate::event::{disable_mouse_capture, enable_mouse_capture, read_event, Event, KeyCode, MouseKind};
+use crate::geometry::{Rect, Size};
+use crate::layout::{Constraint, Layout, LayoutDirection};
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::terminal::{disable_raw_mode, enable_raw_mode};
+use crate::text::{wrap_text, TextLayout};
+use crate::theme::{role_style, Role};
+use crate::width::str_width;
+use crate::*;
+
+/// What a widget did with an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventResult {
+    /// The widget handled the event.
+    Consumed,
+    /// The widget did not use the event, so its parent may handle it.
+    Ignored,
+    /// The application should stop.
+    Quit,
+}
+
+/// A component that draws itself into a rectangle of a screen buffer.
+pub trait Widget {
+    /// Draws the widget into `area` of the buffer. The widget should not draw outside
+    /// `area`.
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer);
+
+    /// Returns the size the widget would like, given the space available. By default a
+    /// widget takes all of it.
+    fn preferred_size(&self, available: Size) -> Size {
+        available
+    }
+
+    /// Reacts to an event. Key events are only delivered to the focused widget, and mouse
+    /// events to the widget under the pointer.
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        EventResult::Ignored
+    }
+
+    /// Returns true if the widget, or one of its children, can take keyboard focus.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Gives or takes away keyboard focus.
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// Gives focus to the widget when it is reached by Tab (`forward`) or Shift-Tab.
+    /// Containers use this to focus their first or last focusable child.
+    fn focus_from(&mut self, _forward: bool) {
+        self.set_focused(true);
+    }
+}
+
+impl<W: Widget + ?Sized> Widget for Box<W> {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        (**self).render(area, buffer)
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        (**self).preferred_size(available)
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        (**self).handle_event(event)
+    }
+
+    fn is_focusable(&self) -> bool {
+        (**self).is_focusable()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        (**self).set_focused(focused)
+    }
+
+    fn focus_from(&mut self, forward: bool) {
+        (**self).focus_from(forward)
+    }
+}
+
+/// A block of text, wrapped and aligned with a `TextLayout`.
+#[derive(Debug, Clone, Default)]
+pub struct Label {
+    pub text: String,
+    pub layout: TextLayout,
+}
+
+impl Label {
+    pub fn new(text: &str) -> Self {
+        Label { text: text.to_string(), layout: TextLayout::default() }
+    }
+
+    pub fn layout(mut self, layout: TextLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+impl Widget for Label {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        buffer.draw_text(&self.text, area.x, area.y, area.width, area.height, &self.layout);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let lines = wrap_text(&self.text, available.width as usize, self.layout.hyphenate);
+        let width = lines.iter().map(|(line, _)| str_width(line)).max().unwrap_or(0);
+        Size::new(width as u16, (lines.len() as u16).min(available.height))
+    }
+}
+
+/// A box with an optional title around another widget.
+///
+/// The border is drawn in the theme's `Role::Border` style, or `Role::Accent` while the
+/// widget inside has focus.
+pub struct Panel {
+    pub title: String,
+    pub box_style: BoxStyle,
+    pub child: Box<dyn Widget>,
+    focused: bool,
+}
+
+impl Panel {
+    pub fn new(title: &str, child: impl Widget + 'static) -> Self {
+        Panel { title: title.to_string(), box_style: BoxStyle::SingleRounded, child: Box::new(child), focused: false }
+    }
+
+    pub fn box_style(mut self, box_style: BoxStyle) -> Self {
+        self.box_style = box_style;
+        self
+    }
+}
+
+impl Widget for Panel {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        let border = role_style(if self.focused { Role::Accent } else { Role::Border });
+        buffer.draw_box(area, self.box_style, border);
+        if !self.title.is_empty() && area.width > 4 {
+            let title = crate::ansi::truncate(&format!(" {} ", self.title), area.width as usize - 4);
+            buffer.put_str(area.x + 2, area.y, &title, border);
+        }
+        self.child.render(area.inset(1, 1), buffer);
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let inner = self.child.preferred_size(Size::new(
+            available.width.saturating_sub(2),
+            available.height.saturating_sub(2),
+        ));
+        let title_width = str_width(&self.title) as u16 + 6;
+        Size::new((inner.width + 2).max(title_width).min(available.width), (inner.height + 2).min(available.height))
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        self.child.handle_event(event)
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.child.is_focusable()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        self.child.set_focused(focused);
+    }
+
+    fn focus_from(&mut self, forward: bool) {
+        self.focused = true;
+        self.child.focus_from(forward);
+    }
+}
+
+/// Places child widgets side by side or stacked, sized by layout constraints.
+///
+/// Tab and Shift-Tab move focus between focusable children when the focused child does
+/// not use them itself. A mouse click focuses the child under the pointer.
+pub struct Stack {
+    pub direction: LayoutDirection,
+    // A child with no constraint is given its preferred size.
+    children: Vec<(Option<Constraint>, Box<dyn Widget>)>,
+    pub gap: u16,
+    pub margin: u16,
+    focus: Option<usize>,
+    focused: bool,
+    // The area of the last render, for finding the child under the mouse.
+    area: Cell<Rect>,
+}
+
+impl Stack {
+    pub fn new(direction: LayoutDirection) -> Self {
+        Stack {
+            direction,
+            children: Vec::new(),
+            gap: 0,
+            margin: 0,
+            focus: None,
+            focused: false,
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    pub fn horizontal() -> Self {
+        Self::new(LayoutDirection::Horizontal)
+    }
+
+    pub fn vertical() -> Self {
+        Self::new(LayoutDirection::Vertical)
+    }
+
+    /// Adds a child sized by a constraint.
+    pub fn child(mut self, constraint: Constraint, widget: impl Widget + 'static) -> Self {
+        self.children.push((Some(constraint), Box::new(widget)));
+        self
+    }
+
+    /// Adds a child sized by its preferred size.
+    pub fn auto(mut self, widget: impl Widget + 'static) -> Self {
+        self.children.push((None, Box::new(widget)));
+        self
+    }
+
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the index of the child with focus, if any.
+    pub fn focused_child(&self) -> Option<usize> {
+        self.focus
+    }
+
+    /// Works out the area of every child.
+    pub fn layout(&self, area: Rect) -> Vec<Rect> {
+        let horizontal = self.direction == LayoutDirection::Horizontal;
+        let inner = area.inset(self.margin, self.margin);
+        let constraints = self
+            .children
+            .iter()
+            .map(|(constraint, widget)| {
+                constraint.unwrap_or_else(|| {
+                    let size = widget.preferred_size(inner.size());
+                    Constraint::Length(if horizontal { size.width } else { size.height })
+                })
+            })
+            .collect();
+        Layout::new(self.direction, constraints).margin(self.margin).gap(self.gap).split(area)
+    }
+
+    // Moves focus to the next focusable child in the given direction. Returns false if
+    // there is none, leaving focus unchanged.
+    fn move_focus(&mut self, forward: bool) -> bool {
+        let count = self.children.len();
+        let candidates: Vec<usize> = match (self.focus, forward) {
+            (Some(current), true) => (current + 1..count).collect(),
+            (Some(current), false) => (0..current).rev().collect(),
+            (None, true) => (0..count).collect(),
+            (None, false) => (0..count).rev().collect(),
+        };
+        let Some(next) = candidates.into_iter().find(|&i| self.children[i].1.is_focusable()) else {
+            return false;
+        };
+        if let Some(current) = self.focus {
+            self.children[current].1.set_focused(false);
+        }
+        self.children[next].1.focus_from(forward);
+        self.focus = Some(next);
+        true
+    }
+}
+
+impl Widget for Stack {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        self.area.set(area);
+        for ((_, widget), child_area) in self.children.iter().zip(self.layout(area)) {
+            if !child_area.is_empty() {
+                widget.render(child_area, buffer);
+            }
+        }
+    }
+
+    fn preferred_size(&self, available: Size) -> Size {
+        let horizontal = self.direction == LayoutDirection::Horizontal;
+        let (mut along, mut across) = (0u16, 0u16);
+        for (constraint, widget) in &self.children {
+            let size = widget.preferred_size(available);
+            let (size_along, size_across) = if horizontal { (size.width, size.height) } else { (size.height, size.width) };
+            along = along.saturating_add(match constraint {
+                Some(Constraint::Length(length)) => *length,
+                _ => size_along,
+            });
+            across = across.max(size_across);
+        }
+        along = along.saturating_add(self.gap * self.children.len().saturating_sub(1) as u16 + 2 * self.margin);
+        across = across.saturating_add(2 * self.margin);
+        let size = if horizontal { Size::new(along, across) } else { Size::new(across, along) };
+        Size::new(size.width.min(available.width), size.height.min(available.height))
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Key(key) => {
+                if let Some(current) = self.focus {
+                    let result = self.children[current].1.handle_event(event);
+                    if result != EventResult::Ignored {
+                        return result;
+                    }
+                }
+                let forward = match key.code {
+                    KeyCode::Tab if !key.shift => true,
+                    KeyCode::Tab | KeyCode::BackTab => false,
+                    _ => return EventResult::Ignored,
+                };
+                if self.move_focus(forward) {
+                    EventResult::Consumed
+                } else {
+                    EventResult::Ignored
+                }
+            }
+            Event::Mouse(mouse) => {
+                let areas = self.layout(self.area.get());
+                let Some(index) = areas.iter().position(|area| area.contains(mouse.position())) else {
+                    return EventResult::Ignored;
+                };
+                if matches!(mouse.kind, MouseKind::Press(_))
+                    && self.focus != Some(index)
+                    && self.children[index].1.is_focusable()
+                {
+                    if let Some(current) = self.focus {
+                        self.children[current].1.set_focused(false);
+                    }
+                    self.children[index].1.focus_from(true);
+                    self.focus = Some(index);
+                }
+                self.children[index].1.handle_event(event)
+            }
+            Event::Resize(_) => {
+                for (_, widget) in &mut self.children {
+                    widget.handle_event(event);
+                }
+                EventResult::Consumed
+            }
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.children.iter().any(|(_, widget)| widget.is_focusable())
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        match self.focus {
+            Some(current) => self.children[current].1.set_focused(focused),
+            None if focused => {
+                self.move_focus(true);
+            }
+            None => {}
+        }
+    }
+
+    fn focus_from(&mut self, forward: bool) {
+        if let Some(current) = self.focus.take() {
+            self.children[current].1.set_focused(false);
+        }
+        self.focused = true;
+        self.move_focus(forward);
+    }
+}
+
+/// Runs a widget tree: renders a frame whenever something may have changed and passes
+/// input to the widgets until one of them asks to quit.
+pub struct App {
+    root: Box<dyn Widget>,
+    previous: Option<ScreenBuffer>,
+    /// How often to check whether the terminal has been resized.
+    pub resize_interval: Duration,
+    /// The style the screen is cleared to before each frame.
+    pub background: Style,
+    /// Whether `run` asks the terminal to report the mouse.
+    pub mouse_capture: bool,
+}
+
+impl App {
+    pub fn new(root: impl Widget + 'static) -> Self {
+        let mut root: Box<dyn Widget> = Box::new(root);
+        if root.is_focusable() {
+            root.focus_from(true);
+        }
+        App {
+            root,
+            previous: None,
+            resize_interval: Duration::from_millis(250),
+            background: Style::default(),
+            mouse_capture: false,
+        }
+    }
+
+    /// Returns the root widget.
+    pub fn root(&self) -> &dyn Widget {
+        self.root.as_ref()
+    }
+
+    /// Returns the root widget for changing it between frames.
+    pub fn root_mut(&mut self) -> &mut dyn Widget {
+        self.root.as_mut()
+    }
+
+    /// Lays out and renders the widget tree into a buffer the size of the viewport.
+    pub fn render_to_buffer(&self) -> ScreenBuffer {
+        let mut buffer = ScreenBuffer::from_viewport();
+        buffer.fill(buffer.area(), ' ', self.background);
+        self.root.render(buffer.area(), &mut buffer);
+        buffer
+    }
+
+    /// Renders one frame, writing only the cells that changed since the previous frame.
+    pub fn render_frame(&mut self) {
+        let buffer = self.render_to_buffer();
+        match &self.previous {
+            Some(previous) => buffer.render_diff_at(previous, 1, 1),
+            None => buffer.render(),
+        }
+        match buffer.cursor() {
+            Some(cursor) => {
+                move_cursor_to(cursor.x, cursor.y);
+                show_cursor();
+            }
+            None => hide_cursor(),
+        }
+        self.previous = Some(buffer);
+    }
+
+    /// Passes an event to the widget tree. Ctrl-C quits if no widget uses it, and Tab
+    /// wraps around to the first focusable widget.
+    pub fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::Resize(_) = event {
+            // Redraw everything: the terminal may have reflowed or cleared the old frame.
+            self.previous = None;
+            clear_screen();
+        }
+        let result = self.root.handle_event(event);
+        match (result, event) {
+            (EventResult::Ignored, Event::Key(key)) if key.is_ctrl('c') => EventResult::Quit,
+            (EventResult::Ignored, Event::Key(key)) if matches!(key.code, KeyCode::Tab | KeyCode::BackTab) => {
+                if self.root.is_focusable() {
+                    self.root.set_focused(false);
+                    self.root.focus_from(key.code == KeyCode::Tab && !key.shift);
+                }
+                EventResult::Consumed
+            }
+            _ => result,
+        }
+    }
+
+    /// Runs the application in raw mode on the alternate screen until a widget returns
+    /// `EventResult::Quit` or input ends, then restores the terminal.
+    pub fn run(&mut self) {
+        let raw = enable_raw_mode();
+        crate::terminal::sync_viewport();
+        print("\x1B[?1049h");
+        if self.mouse_capture {
+            enable_mouse_capture();
+        }
+        clear_screen();
+        self.previous = None;
+
+        loop {
+            self.render_frame();
+            let Some(event) = read_event(self.resize_interval) else {
+                break;
+            };
+            if self.handle_event(&event) == EventResult::Quit {
+                break;
+            }
+        }
+
+        if self.mouse_capture {
+            disable_mouse_capture();
+        }
+        reset_color();
+        show_cursor();
+        print("\x1B[?1049l");
+        if raw {
+            disable_raw_mode();
+        }
+    }
+}
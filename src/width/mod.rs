@@ -0,0 +1,49 @@
+//! This module works out how many terminal columns a character or string occupies.
+//!
+//! Most characters take one column, but East Asian wide characters and emoji such as
+//! `EmojiSymbol::Robot` take two, and combining marks take none. The lookup tables in
+//! `tables.rs` are generated from the Unicode database by
+//! `scripts/generate_width_tables.py`, so nothing is downloaded at build time.
+
+mod tables;
+
+use crate::grapheme::{grapheme_width, graphemes};
+use tables::{WIDE, ZERO_WIDTH};
+
+fn in_table(table: &[(u32, u32)], ch: char) -> bool {
+    let cp = ch as u32;
+    table
+        .binary_search_by(|(start, end)| {
+            if cp < *start {
+                std::cmp::Ordering::Greater
+            } else if cp > *end {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Returns the number of columns a character occupies: 0, 1 or 2.
+///
+/// Control characters count as 0 columns.
+pub fn char_width(ch: char) -> usize {
+    if (ch as u32) < 0x7F {
+        return if ch.is_control() { 0 } else { 1 };
+    }
+    if ch.is_control() || in_table(ZERO_WIDTH, ch) {
+        0
+    } else if in_table(WIDE, ch) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Returns the number of columns a string occupies, measured cluster by cluster so that
+/// emoji sequences count as one glyph. Escape sequences are not recognised; use
+/// `ansi::visible_width` for strings that contain them.
+pub fn str_width(text: &str) -> usize {
+    graphemes(text).map(grapheme_width).sum()
+}
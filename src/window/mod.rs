@@ -0,0 +1,560 @@
+//! This module provides overlapping windows and a window manager that stacks them.
+//!
+//! A `Window` is a titled box, drawn with any `BoxStyle`, around a widget. The
+//! `WindowManager` is itself a widget: it keeps its windows in z-order and composites them
+//! into the screen buffer every frame, bottom window first, so moving a window over another
+//! never leaves stale cells behind the way `rbox::hide_box` does. The last row of its area
+//! is a taskbar listing every window.
+//!
+//! Keys go to the window on top, except for these, which the manager handles itself:
+//!
+//! * Alt-Tab or Alt-. and Alt-,: focus the next or previous window, restoring it if
+//!   minimized
+//! * Alt-M: move the top window with the arrow keys; Enter keeps the position, Esc undoes it
+//! * Alt-R: resize the top window the same way
+//! * Alt-N: minimize the top window
+//! * Alt-X: close the top window
+//!
+//! With mouse capture on (see `widget::App::mouse_capture`) a click raises a window,
+//! dragging the title bar moves it, dragging the bottom-right corner resizes it, the `[_]`
+//! and `[x]` buttons minimize and close it, and clicking the taskbar restores it.
+//!
+//! ```no_run
+//! use rpian_terminal::*;
+//! use rpian_terminal::widget::{App, Label};
+//! use rpian_terminal::window::{Window, WindowManager};
+//!
+//! let mut desktop = WindowManager::new();
+//! desktop.add(Window::new("Notes", Rect::new(3, 2, 30, 8), Label::new("Drag me")));
+//! desktop.add(Window::new("More", Rect::new(20, 6, 30, 8), Label::new("Or me")).box_style(BoxStyle::Double));
+//! let mut app = App::new(desktop);
+//! app.mouse_capture = true;
+//! app.run();
+//! ```
+
+use std::cell::Cell;
+
+use crate::event::{Event, KeyCode, KeyEvent, MouseButton, MouseKind};
+use crate::geometry::{Point, Rect, Size};
+use crate::screen::ScreenBuffer;
+use crate::style::Style;
+use crate::theme::{role_style, Role};
+use crate::widget::{EventResult, Widget};
+use crate::width::str_width;
+use crate::*;
+
+/// Identifies a window in a `WindowManager`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowId(u32);
+
+/// A titled box around a widget.
+pub struct Window {
+    pub title: String,
+    /// The area of the window including its border, relative to the top-left corner of
+    /// the desktop, which is `(1, 1)`.
+    pub area: Rect,
+    pub box_style: BoxStyle,
+    /// The smallest size the window can be resized to.
+    pub min_size: Size,
+    content: Box<dyn Widget>,
+    minimized: bool,
+    id: WindowId,
+}
+
+impl Window {
+    pub fn new(title: &str, area: Rect, content: impl Widget + 'static) -> Self {
+        Window {
+            title: title.to_string(),
+            area,
+            box_style: BoxStyle::Single,
+            min_size: Size::new(12, 3),
+            content: Box::new(content),
+            minimized: false,
+            id: WindowId(0),
+        }
+    }
+
+    pub fn box_style(mut self, box_style: BoxStyle) -> Self {
+        self.box_style = box_style;
+        self
+    }
+
+    pub fn min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Returns the id the window was given when added to a `WindowManager`.
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    /// Returns the widget inside the window.
+    pub fn content(&self) -> &dyn Widget {
+        self.content.as_ref()
+    }
+
+    pub fn content_mut(&mut self) -> &mut dyn Widget {
+        self.content.as_mut()
+    }
+}
+
+// What the arrow keys or a mouse drag currently do to the top window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    // The window's area before the move or resize started, for undoing it.
+    Move(Rect),
+    Resize(Rect),
+}
+
+// A mouse drag in progress: where the button was pressed, relative to the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Drag {
+    resize: bool,
+    grab: Point,
+}
+
+/// Stacks windows in z-order over a shaded desktop and composites them every frame.
+pub struct WindowManager {
+    // Bottom to top; the last window that is not minimized has focus.
+    windows: Vec<Window>,
+    next_id: u32,
+    /// The shade the desktop behind the windows is filled with.
+    pub background: ShadeStyle,
+    mode: Mode,
+    drag: Option<Drag>,
+    has_focus: bool,
+    // The area of the last render, for mouse positions and keeping windows on screen.
+    area: Cell<Rect>,
+}
+
+impl Default for WindowManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowManager {
+    pub fn new() -> Self {
+        WindowManager {
+            windows: Vec::new(),
+            next_id: 1,
+            background: ShadeStyle::Light,
+            mode: Mode::Normal,
+            drag: None,
+            has_focus: false,
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    pub fn background(mut self, background: ShadeStyle) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Adds a window on top of the others and gives it focus.
+    pub fn add(&mut self, mut window: Window) -> WindowId {
+        let id = WindowId(self.next_id);
+        self.next_id += 1;
+        window.id = id;
+        window.minimized = false;
+        self.change_focus(|manager| manager.windows.push(window));
+        id
+    }
+
+    /// Removes a window, returning it.
+    pub fn close(&mut self, id: WindowId) -> Option<Window> {
+        let index = self.index(id)?;
+        let mut closed = None;
+        self.change_focus(|manager| closed = Some(manager.windows.remove(index)));
+        closed
+    }
+
+    /// Hides a window; it stays in the taskbar until restored.
+    pub fn minimize(&mut self, id: WindowId) {
+        self.change_focus(|manager| {
+            if let Some(window) = manager.window_mut(id) {
+                window.minimized = true;
+            }
+        });
+    }
+
+    /// Shows a minimized window again on top of the others.
+    pub fn restore(&mut self, id: WindowId) {
+        self.raise(id);
+    }
+
+    /// Brings a window to the top, restoring it if minimized, and gives it focus.
+    pub fn raise(&mut self, id: WindowId) {
+        let Some(index) = self.index(id) else {
+            return;
+        };
+        self.change_focus(|manager| {
+            let mut window = manager.windows.remove(index);
+            window.minimized = false;
+            manager.windows.push(window);
+        });
+    }
+
+    /// Returns the window with focus: the top window that is not minimized.
+    pub fn focused(&self) -> Option<WindowId> {
+        self.windows.iter().rev().find(|window| !window.minimized).map(|window| window.id)
+    }
+
+    pub fn window(&self, id: WindowId) -> Option<&Window> {
+        self.windows.iter().find(|window| window.id == id)
+    }
+
+    pub fn window_mut(&mut self, id: WindowId) -> Option<&mut Window> {
+        self.windows.iter_mut().find(|window| window.id == id)
+    }
+
+    /// Returns the windows from bottom to top.
+    pub fn windows(&self) -> impl Iterator<Item = &Window> {
+        self.windows.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Moves a window so its top-left corner is at the given desktop position.
+    pub fn move_window(&mut self, id: WindowId, x: u16, y: u16) {
+        if let Some(window) = self.window_mut(id) {
+            window.area.x = x.max(1);
+            window.area.y = y.max(1);
+        }
+    }
+
+    /// Changes the size of a window, keeping it at least its `min_size`.
+    pub fn resize_window(&mut self, id: WindowId, size: Size) {
+        if let Some(window) = self.window_mut(id) {
+            window.area.width = size.width.max(window.min_size.width).max(2);
+            window.area.height = size.height.max(window.min_size.height).max(2);
+        }
+    }
+
+    // Keeps a window of the given size entirely on the desktop.
+    fn clamp_position(&self, area: Rect, x: i32, y: i32) -> (u16, u16) {
+        let desktop = self.desktop();
+        let x = x.clamp(1, desktop.width.saturating_sub(area.width) as i32 + 1);
+        let y = y.clamp(1, desktop.height.saturating_sub(area.height) as i32 + 1);
+        (x as u16, y as u16)
+    }
+
+    fn index(&self, id: WindowId) -> Option<usize> {
+        self.windows.iter().position(|window| window.id == id)
+    }
+
+    // Runs a change to the window stack and moves keyboard focus if the top window changed.
+    fn change_focus(&mut self, change: impl FnOnce(&mut Self)) {
+        let before = self.focused();
+        change(self);
+        let after = self.focused();
+        if before == after {
+            return;
+        }
+        self.mode = Mode::Normal;
+        self.drag = None;
+        if !self.has_focus {
+            return;
+        }
+        if let Some(window) = before.and_then(|id| self.window_mut(id)) {
+            window.content.set_focused(false);
+        }
+        if let Some(window) = after.and_then(|id| self.window_mut(id)) {
+            window.content.focus_from(true);
+        }
+    }
+
+    // The area windows are drawn in: everything except the taskbar row.
+    fn desktop(&self) -> Rect {
+        let area = self.area.get();
+        Rect::new(area.x, area.y, area.width, area.height.saturating_sub(1))
+    }
+
+    // Works out where a window appears on screen: its area moved onto the desktop, and
+    // shrunk if the desktop is too small for it.
+    fn placement(&self, window: &Window) -> Rect {
+        let desktop = self.desktop();
+        let width = window.area.width.min(desktop.width);
+        let height = window.area.height.min(desktop.height);
+        let x = (desktop.x + window.area.x.max(1) - 1).min(desktop.right() - width);
+        let y = (desktop.y + window.area.y.max(1) - 1).min(desktop.bottom() - height);
+        Rect::new(x, y, width, height)
+    }
+
+    // Returns the taskbar entry of every window, in the order they were added.
+    fn taskbar(&self) -> Vec<(WindowId, Rect)> {
+        let area = self.area.get();
+        let mut ids: Vec<&Window> = self.windows.iter().collect();
+        ids.sort_by_key(|window| window.id);
+        let mut x = area.x;
+        let mut entries = Vec::new();
+        for window in ids {
+            let width = (str_width(&window.title) as u16 + 2).min(20);
+            if x + width > area.right() {
+                break;
+            }
+            entries.push((window.id, Rect::new(x, area.bottom() - 1, width, 1)));
+            x += width + 1;
+        }
+        entries
+    }
+
+    // Returns the id of the window after or before the focused one in taskbar order.
+    fn cycle(&self, forward: bool) -> Option<WindowId> {
+        let mut ids: Vec<WindowId> = self.windows.iter().map(|window| window.id).collect();
+        ids.sort();
+        let current = self.focused().and_then(|id| ids.iter().position(|&other| other == id));
+        let count = ids.len();
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+            (None, _) => count.checked_sub(1)?,
+        };
+        ids.get(next).copied()
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent) -> Option<EventResult> {
+        let id = self.focused();
+        match self.mode {
+            Mode::Move(original) | Mode::Resize(original) => {
+                let id = id?;
+                let (dx, dy) = match key.code {
+                    KeyCode::Left => (-1, 0),
+                    KeyCode::Right => (1, 0),
+                    KeyCode::Up => (0, -1),
+                    KeyCode::Down => (0, 1),
+                    KeyCode::Enter => {
+                        self.mode = Mode::Normal;
+                        return Some(EventResult::Consumed);
+                    }
+                    KeyCode::Esc => {
+                        if let Some(window) = self.window_mut(id) {
+                            window.area = original;
+                        }
+                        self.mode = Mode::Normal;
+                        return Some(EventResult::Consumed);
+                    }
+                    _ => return Some(EventResult::Consumed),
+                };
+                let area = self.window(id)?.area;
+                if let Mode::Move(_) = self.mode {
+                    let (x, y) = self.clamp_position(area, area.x as i32 + dx, area.y as i32 + dy);
+                    self.move_window(id, x, y);
+                } else {
+                    let width = (area.width as i32 + dx).max(0) as u16;
+                    let height = (area.height as i32 + dy).max(0) as u16;
+                    self.resize_window(id, Size::new(width, height));
+                }
+                Some(EventResult::Consumed)
+            }
+            Mode::Normal if key.alt && !key.ctrl => {
+                match key.code {
+                    KeyCode::Tab | KeyCode::Char('.') | KeyCode::Char(',') => {
+                        let next = self.cycle(key.code != KeyCode::Char(','))?;
+                        self.raise(next);
+                    }
+                    KeyCode::Char('m') => self.mode = Mode::Move(self.window(id?)?.area),
+                    KeyCode::Char('r') => self.mode = Mode::Resize(self.window(id?)?.area),
+                    KeyCode::Char('n') => self.minimize(id?),
+                    KeyCode::Char('x') => {
+                        self.close(id?);
+                    }
+                    _ => return None,
+                }
+                Some(EventResult::Consumed)
+            }
+            Mode::Normal => None,
+        }
+    }
+
+    fn handle_mouse(&mut self, event: &Event, kind: MouseKind, position: Point) -> EventResult {
+        match (kind, self.drag) {
+            (MouseKind::Drag(MouseButton::Left), Some(drag)) => {
+                let Some(id) = self.focused() else {
+                    return EventResult::Consumed;
+                };
+                let desktop = self.desktop();
+                let position = Point::new(
+                    position.x.clamp(desktop.left(), desktop.right().max(desktop.left() + 1) - 1),
+                    position.y.clamp(desktop.top(), desktop.bottom().max(desktop.top() + 1) - 1),
+                );
+                let placement = self.placement(self.window(id).unwrap());
+                if drag.resize {
+                    // Dragging above or left of the window shrinks it to its minimum size.
+                    let width = (position.x + 1).saturating_sub(placement.x);
+                    let height = (position.y + 1).saturating_sub(placement.y);
+                    self.resize_window(id, Size::new(width, height));
+                } else {
+                    let x = position.x as i32 + 1 - (desktop.x + drag.grab.x) as i32;
+                    let y = position.y as i32 + 1 - (desktop.y + drag.grab.y) as i32;
+                    let (x, y) = self.clamp_position(placement, x, y);
+                    self.move_window(id, x, y);
+                }
+                return EventResult::Consumed;
+            }
+            (MouseKind::Release(_), Some(_)) => {
+                self.drag = None;
+                return EventResult::Consumed;
+            }
+            _ => {}
+        }
+
+        if let MouseKind::Press(MouseButton::Left) = kind {
+            if let Some((id, _)) = self.taskbar().into_iter().find(|(_, entry)| entry.contains(position)) {
+                if self.focused() == Some(id) {
+                    self.minimize(id);
+                } else {
+                    self.raise(id);
+                }
+                return EventResult::Consumed;
+            }
+        }
+
+        let Some(index) = self
+            .windows
+            .iter()
+            .rposition(|window| !window.minimized && self.placement(window).contains(position))
+        else {
+            return EventResult::Ignored;
+        };
+        let id = self.windows[index].id;
+        let placement = self.placement(&self.windows[index]);
+        if let MouseKind::Press(_) = kind {
+            self.raise(id);
+        }
+        if let MouseKind::Press(MouseButton::Left) = kind {
+            let grab = Point::new(position.x - placement.x, position.y - placement.y);
+            if position.y == placement.top() {
+                match title_buttons(placement) {
+                    Some((minimize, _)) if minimize.contains(position) => self.minimize(id),
+                    Some((_, close)) if close.contains(position) => {
+                        self.close(id);
+                    }
+                    _ => self.drag = Some(Drag { resize: false, grab }),
+                }
+                return EventResult::Consumed;
+            }
+            if position.x == placement.right() - 1 && position.y == placement.bottom() - 1 {
+                self.drag = Some(Drag { resize: true, grab });
+                return EventResult::Consumed;
+            }
+        }
+        if placement.inset(1, 1).contains(position) {
+            self.windows[index].content.handle_event(event);
+        }
+        EventResult::Consumed
+    }
+}
+
+// Returns the areas of the minimize and close buttons on the title bar, if the window is
+// wide enough for them.
+fn title_buttons(area: Rect) -> Option<(Rect, Rect)> {
+    if area.width < 12 {
+        return None;
+    }
+    let x = area.right() - 8;
+    Some((Rect::new(x, area.y, 3, 1), Rect::new(x + 3, area.y, 3, 1)))
+}
+
+impl Widget for WindowManager {
+    fn render(&self, area: Rect, buffer: &mut ScreenBuffer) {
+        self.area.set(area);
+        let desktop = self.desktop();
+        buffer.draw_shaded_rect(desktop, self.background, role_style(Role::Muted));
+
+        let focused = self.focused();
+        for window in self.windows.iter().filter(|window| !window.minimized) {
+            let placement = self.placement(window);
+            if placement.width < 2 || placement.height < 2 {
+                continue;
+            }
+            let is_focused = Some(window.id) == focused;
+            let border = match self.mode {
+                Mode::Move(_) | Mode::Resize(_) if is_focused => role_style(Role::Warning),
+                _ if is_focused => role_style(Role::Accent),
+                _ => role_style(Role::Border),
+            };
+            // Only the focused window may place the cursor.
+            let cursor = buffer.cursor();
+            buffer.fill(placement, ' ', Style::default());
+            buffer.draw_box(placement, window.box_style, border);
+
+            let buttons = title_buttons(placement);
+            let title_end = buttons.map_or(placement.right() - 2, |(minimize, _)| minimize.x - 1);
+            if !window.title.is_empty() && title_end > placement.x + 4 {
+                let title = crate::ansi::truncate(&format!(" {} ", window.title), (title_end - placement.x - 2) as usize);
+                buffer.put_str(placement.x + 2, placement.y, &title, border);
+            }
+            if let Some((minimize, close)) = buttons {
+                buffer.put_str(minimize.x, minimize.y, "[_]", border);
+                buffer.put_str(close.x, close.y, "[x]", border);
+            }
+
+            window.content.render(placement.inset(1, 1), buffer);
+            if !is_focused {
+                buffer.set_cursor(cursor);
+            }
+        }
+
+        let bar = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+        buffer.fill(bar, ' ', role_style(Role::Muted));
+        for (id, entry) in self.taskbar() {
+            let window = self.window(id).unwrap();
+            let style = if Some(id) == focused { role_style(Role::Selection) } else { role_style(Role::Primary) };
+            let label = crate::ansi::truncate(&format!(" {} ", window.title), entry.width as usize);
+            buffer.fill(entry, ' ', style);
+            buffer.put_str(entry.x, entry.y, &label, style);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::Key(key) => {
+                if let Some(result) = self.handle_key(key) {
+                    return result;
+                }
+                match self.focused().and_then(|id| self.index(id)) {
+                    Some(index) => self.windows[index].content.handle_event(event),
+                    None => EventResult::Ignored,
+                }
+            }
+            Event::Mouse(mouse) => self.handle_mouse(event, mouse.kind, mouse.position()),
+            Event::Resize(_) => {
+                for window in &mut self.windows {
+                    window.content.handle_event(event);
+                }
+                EventResult::Consumed
+            }
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.has_focus = focused;
+        if let Some(index) = self.focused().and_then(|id| self.index(id)) {
+            self.windows[index].content.set_focused(focused);
+        }
+    }
+
+    fn focus_from(&mut self, forward: bool) {
+        self.has_focus = true;
+        if let Some(index) = self.focused().and_then(|id| self.index(id)) {
+            self.windows[index].content.focus_from(forward);
+        }
+    }
+}